@@ -0,0 +1,35 @@
+use crate::achievements::{Achievements, ACHIEVEMENTS};
+use crate::console::Console;
+use crate::i18n;
+use std::sync::{Arc, Mutex};
+
+pub fn register_commands(console: &mut Console, achievements: Arc<Mutex<Achievements>>) {
+    console.register(
+        "achievements",
+        "",
+        "list the achievements and what's unlocked",
+        move |_| {
+            let achievements = achievements
+                .lock()
+                .map_err(|_| String::from("Failed to gain achievements lock"))?;
+
+            let lines: Vec<_> = ACHIEVEMENTS
+                .iter()
+                .enumerate()
+                .map(|(idx, achievement)| {
+                    format!(
+                        "[{}] {}, {}",
+                        if achievements.is_unlocked(idx) {
+                            "x"
+                        } else {
+                            " "
+                        },
+                        i18n::text(achievement.name),
+                        i18n::text(achievement.description)
+                    )
+                })
+                .collect();
+            Ok(lines.join("\n"))
+        },
+    );
+}
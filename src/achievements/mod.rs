@@ -0,0 +1,425 @@
+//! Achievements unlocked by what happens to the pet and in the games, kept across pets.
+//!
+//! Every achievement is a row in [`ACHIEVEMENTS`] with a check over the pet and the [`Progress`]
+//! tallied so far, adding one only takes a new row, and a new tally if nothing counts it yet.
+
+pub mod commands;
+
+use crate::i18n::Key;
+use crate::minigame::GameResult;
+use crate::pet::care::Form;
+use crate::pet::world::{HappeningKind, HAPPENINGS};
+use crate::pet::{LifeStage, Pet, PetEvent, STAT_MAX};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Counted across every pet.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Progress {
+    pub evolutions: u32,
+    pub games_played: u32,
+    pub perfect_games: u32,
+    /// Forms pets have grown into adults with, one bit per [`Form`].
+    pub adult_forms: u8,
+    /// Happenings seen, one bit per [`HappeningKind`].
+    pub happenings: u8,
+}
+
+impl Progress {
+    pub fn has_raised(&self, form: Form) -> bool {
+        self.adult_forms & (1 << form.to_u8()) != 0
+    }
+
+    pub fn has_seen(&self, kind: HappeningKind) -> bool {
+        self.happenings & (1 << kind.to_u8()) != 0
+    }
+}
+
+pub struct Achievement {
+    pub name: Key,
+    pub description: Key,
+    pub unlocked_by: fn(&Pet, &Progress) -> bool,
+}
+
+/// Time since the pet's last care mistake, or since it hatched if it never made one.
+fn clean_for(pet: &Pet) -> Duration {
+    let hatched_at = LifeStage::Egg.ends_at().unwrap_or_default();
+    let since = pet
+        .care
+        .log
+        .recent()
+        .next()
+        .map_or(hatched_at, |mistake| mistake.age);
+
+    pet.age.saturating_sub(since)
+}
+
+/// Saved by position, only ever append.
+pub const ACHIEVEMENTS: [Achievement; 10] = [
+    Achievement {
+        name: Key::FirstSteps,
+        description: Key::FirstStepsInfo,
+        unlocked_by: |_, progress| progress.evolutions > 0,
+    },
+    Achievement {
+        name: Key::SpotlessWeek,
+        description: Key::SpotlessWeekInfo,
+        unlocked_by: |pet, _| pet.stage != LifeStage::Egg && clean_for(pet) >= 7 * DAY,
+    },
+    Achievement {
+        name: Key::Perfectionist,
+        description: Key::PerfectionistInfo,
+        unlocked_by: |_, progress| progress.perfect_games > 0,
+    },
+    Achievement {
+        name: Key::FullHouse,
+        description: Key::FullHouseInfo,
+        unlocked_by: |_, progress| {
+            [Form::Good, Form::Average, Form::Poor]
+                .iter()
+                .all(|form| progress.has_raised(*form))
+        },
+    },
+    Achievement {
+        name: Key::AllGrownUp,
+        description: Key::AllGrownUpInfo,
+        unlocked_by: |_, progress| progress.adult_forms != 0,
+    },
+    Achievement {
+        name: Key::GoldenYears,
+        description: Key::GoldenYearsInfo,
+        unlocked_by: |pet, _| pet.stage == LifeStage::Senior,
+    },
+    Achievement {
+        name: Key::GameOn,
+        description: Key::GameOnInfo,
+        unlocked_by: |_, progress| progress.games_played >= 50,
+    },
+    Achievement {
+        name: Key::GoodManners,
+        description: Key::GoodMannersInfo,
+        unlocked_by: |pet, _| pet.discipline.level == STAT_MAX,
+    },
+    Achievement {
+        name: Key::MakeAWish,
+        description: Key::MakeAWishInfo,
+        unlocked_by: |_, progress| progress.has_seen(HappeningKind::ShootingStar),
+    },
+    Achievement {
+        name: Key::SeenItAll,
+        description: Key::SeenItAllInfo,
+        unlocked_by: |_, progress| progress.happenings.count_ones() as usize == HAPPENINGS.len(),
+    },
+];
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Achievements {
+    /// One bit per row of [`ACHIEVEMENTS`].
+    pub unlocked: u64,
+    pub progress: Progress,
+    /// Unlocked but not shown yet, not saved.
+    pub(crate) unseen: VecDeque<usize>,
+}
+
+impl Achievements {
+    pub fn is_unlocked(&self, idx: usize) -> bool {
+        self.unlocked & (1 << idx) != 0
+    }
+
+    /// Tallies the pet's events, returning what that unlocked.
+    pub fn record_pet(&mut self, pet: &Pet, events: &[PetEvent]) -> Vec<usize> {
+        for event in events {
+            match event {
+                PetEvent::Evolved(stage) => {
+                    self.progress.evolutions += 1;
+                    if *stage == LifeStage::Adult {
+                        self.progress.adult_forms |= 1 << pet.form.to_u8();
+                    }
+                }
+                PetEvent::Happened(kind) => self.progress.happenings |= 1 << kind.to_u8(),
+                _ => {}
+            }
+        }
+
+        self.check(pet)
+    }
+
+    /// Tallies a finished game, returning what that unlocked.
+    pub fn record_game(&mut self, pet: &Pet, result: &GameResult) -> Vec<usize> {
+        self.progress.games_played += 1;
+        if result.is_perfect() {
+            self.progress.perfect_games += 1;
+        }
+
+        self.check(pet)
+    }
+
+    /// The oldest unlock that hasn't been shown yet.
+    pub fn take_unseen(&mut self) -> Option<&'static Achievement> {
+        self.unseen.pop_front().map(|idx| &ACHIEVEMENTS[idx])
+    }
+
+    fn check(&mut self, pet: &Pet) -> Vec<usize> {
+        let unlocked: Vec<_> = (0..ACHIEVEMENTS.len())
+            .filter(|idx| !self.is_unlocked(*idx))
+            .filter(|idx| (ACHIEVEMENTS[*idx].unlocked_by)(pet, &self.progress))
+            .collect();
+
+        for idx in &unlocked {
+            self.unlocked |= 1 << idx;
+            self.unseen.push_back(*idx);
+        }
+        unlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::care::{CareMistake, CareMistakeKind};
+
+    fn idx(name: Key) -> usize {
+        ACHIEVEMENTS
+            .iter()
+            .position(|achievement| achievement.name == name)
+            .unwrap()
+    }
+
+    fn hatched() -> Duration {
+        LifeStage::Egg.ends_at().unwrap()
+    }
+
+    /// A senior that just made a care mistake, so it's been around long enough for
+    /// Golden Years without having kept a spotless week.
+    fn senior() -> Pet {
+        let mut pet = Pet::quiet(LifeStage::Senior);
+        pet.care.log.record(CareMistake {
+            kind: CareMistakeKind::Hunger,
+            age: pet.age,
+        });
+        pet
+    }
+
+    #[test]
+    fn first_evolution_unlocks_first_steps() {
+        let mut achievements = Achievements::default();
+        let pet = Pet::quiet(LifeStage::Baby);
+
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+        assert_eq!(
+            achievements.record_pet(&pet, &[PetEvent::Evolved(LifeStage::Baby)]),
+            vec![idx(Key::FirstSteps)]
+        );
+        assert_eq!(achievements.progress.evolutions, 1);
+    }
+
+    #[test]
+    fn adults_are_tallied_by_form() {
+        let mut achievements = Achievements::default();
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        let grown_up = [PetEvent::Evolved(LifeStage::Adult)];
+
+        pet.form = Form::Poor;
+        assert_eq!(
+            achievements.record_pet(&pet, &grown_up),
+            vec![idx(Key::FirstSteps), idx(Key::AllGrownUp)]
+        );
+        assert_eq!(achievements.progress.adult_forms, 1 << Form::Poor.to_u8());
+        assert!(achievements.progress.has_raised(Form::Poor));
+        assert!(!achievements.progress.has_raised(Form::Good));
+
+        // another stage doesn't count the form
+        pet.form = Form::Good;
+        achievements.record_pet(&pet, &[PetEvent::Evolved(LifeStage::Teen)]);
+        assert!(!achievements.progress.has_raised(Form::Good));
+
+        assert_eq!(achievements.record_pet(&pet, &grown_up), vec![]);
+        pet.form = Form::Average;
+        assert_eq!(
+            achievements.record_pet(&pet, &grown_up),
+            vec![idx(Key::FullHouse)]
+        );
+        assert_eq!(achievements.progress.evolutions, 4);
+    }
+
+    #[test]
+    fn perfect_game_unlocks_perfectionist() {
+        let mut achievements = Achievements::default();
+        let pet = Pet::quiet(LifeStage::Child);
+
+        assert_eq!(
+            achievements.record_game(&pet, &GameResult::new(4, 5)),
+            vec![]
+        );
+        assert_eq!(
+            achievements.record_game(&pet, &GameResult::new(5, 5)),
+            vec![idx(Key::Perfectionist)]
+        );
+        assert_eq!(achievements.progress.games_played, 2);
+        assert_eq!(achievements.progress.perfect_games, 1);
+    }
+
+    #[test]
+    fn fiftieth_game_unlocks_game_on() {
+        let mut achievements = Achievements::default();
+        let pet = Pet::quiet(LifeStage::Child);
+
+        for _ in 0..49 {
+            assert_eq!(
+                achievements.record_game(&pet, &GameResult::new(0, 5)),
+                vec![]
+            );
+        }
+        assert_eq!(
+            achievements.record_game(&pet, &GameResult::new(0, 5)),
+            vec![idx(Key::GameOn)]
+        );
+    }
+
+    #[test]
+    fn senior_unlocks_golden_years() {
+        let mut achievements = Achievements::default();
+
+        assert_eq!(
+            achievements.record_pet(&Pet::quiet(LifeStage::Adult), &[]),
+            vec![]
+        );
+        assert_eq!(
+            achievements.record_pet(&senior(), &[]),
+            vec![idx(Key::GoldenYears)]
+        );
+    }
+
+    #[test]
+    fn full_discipline_unlocks_good_manners() {
+        let mut achievements = Achievements::default();
+        let mut pet = Pet::quiet(LifeStage::Child);
+
+        pet.discipline.level = STAT_MAX - 1;
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+        pet.discipline.level = STAT_MAX;
+        assert_eq!(
+            achievements.record_pet(&pet, &[]),
+            vec![idx(Key::GoodManners)]
+        );
+    }
+
+    #[test]
+    fn happenings_unlock_make_a_wish_and_seen_it_all() {
+        let mut achievements = Achievements::default();
+        let pet = Pet::quiet(LifeStage::Child);
+
+        assert_eq!(
+            achievements.record_pet(&pet, &[PetEvent::Happened(HappeningKind::ShootingStar)]),
+            vec![idx(Key::MakeAWish)]
+        );
+        assert!(achievements.progress.has_seen(HappeningKind::ShootingStar));
+
+        let others: Vec<_> = HAPPENINGS
+            .iter()
+            .map(|happening| happening.kind)
+            .filter(|kind| *kind != HappeningKind::ShootingStar)
+            .collect();
+        let (last, rest) = others.split_last().unwrap();
+        for kind in rest {
+            assert_eq!(
+                achievements.record_pet(&pet, &[PetEvent::Happened(*kind)]),
+                vec![]
+            );
+        }
+        assert_eq!(
+            achievements.record_pet(&pet, &[PetEvent::Happened(*last)]),
+            vec![idx(Key::SeenItAll)]
+        );
+    }
+
+    #[test]
+    fn spotless_week_counts_from_hatching() {
+        let mut achievements = Achievements::default();
+        let mut pet = Pet::quiet(LifeStage::Child);
+
+        pet.age = hatched() + 7 * DAY - Duration::from_secs(1);
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+        pet.age = hatched() + 7 * DAY;
+        assert_eq!(
+            achievements.record_pet(&pet, &[]),
+            vec![idx(Key::SpotlessWeek)]
+        );
+    }
+
+    #[test]
+    fn care_mistake_restarts_the_spotless_week() {
+        let mut achievements = Achievements::default();
+        let mut pet = Pet::quiet(LifeStage::Child);
+
+        pet.age = hatched() + 10 * DAY;
+        pet.care.log.record(CareMistake {
+            kind: CareMistakeKind::Waste,
+            age: pet.age,
+        });
+        assert_eq!(clean_for(&pet), Duration::ZERO);
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+
+        pet.age += 7 * DAY - Duration::from_secs(1);
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+        pet.age += Duration::from_secs(1);
+        assert_eq!(clean_for(&pet), 7 * DAY);
+        assert_eq!(
+            achievements.record_pet(&pet, &[]),
+            vec![idx(Key::SpotlessWeek)]
+        );
+    }
+
+    #[test]
+    fn eggs_dont_keep_clean() {
+        let mut achievements = Achievements::default();
+        let mut pet = Pet::quiet(LifeStage::Egg);
+
+        pet.age = hatched() + 7 * DAY;
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+    }
+
+    #[test]
+    fn unlocks_only_once() {
+        let mut achievements = Achievements::default();
+        let pet = senior();
+
+        assert_eq!(
+            achievements.record_pet(&pet, &[]),
+            vec![idx(Key::GoldenYears)]
+        );
+        assert_eq!(achievements.unlocked, 1 << idx(Key::GoldenYears));
+        assert_eq!(achievements.record_pet(&pet, &[]), vec![]);
+        assert_eq!(achievements.unlocked, 1 << idx(Key::GoldenYears));
+        assert!(achievements.is_unlocked(idx(Key::GoldenYears)));
+        assert!(!achievements.is_unlocked(idx(Key::FirstSteps)));
+        assert_eq!(achievements.unseen.len(), 1);
+    }
+
+    #[test]
+    fn unseen_come_out_in_unlock_order() {
+        let mut achievements = Achievements::default();
+        let mut pet = senior();
+
+        // unlocked together, they come in table order
+        achievements.record_pet(&pet, &[PetEvent::Evolved(LifeStage::Senior)]);
+        pet.discipline.level = STAT_MAX;
+        achievements.record_game(&pet, &GameResult::new(5, 5));
+
+        let shown: Vec<_> = std::iter::from_fn(|| achievements.take_unseen())
+            .map(|achievement| achievement.name)
+            .collect();
+        assert_eq!(
+            shown,
+            vec![
+                Key::FirstSteps,
+                Key::GoldenYears,
+                Key::Perfectionist,
+                Key::GoodManners
+            ]
+        );
+        assert!(achievements.take_unseen().is_none());
+    }
+}
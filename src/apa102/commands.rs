@@ -0,0 +1,50 @@
+use crate::apa102::pattern::{Colour, LedLayer, Pattern, PatternPlayer};
+use crate::console::Console;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub fn register_commands(console: &mut Console, leds: Arc<Mutex<PatternPlayer>>) {
+    {
+        let leds = leds.clone();
+        console.register("led fill", "<rrggbb>", "light every led", move |args| {
+            let colour = args
+                .first()
+                .and_then(|hex| Colour::from_hex(hex))
+                .ok_or("Expected a colour like ff0000")?;
+
+            leds.lock()
+                .map_err(|_| String::from("Failed to gain led lock"))?
+                .set(LedLayer::Base, Pattern::Solid(colour), Instant::now());
+            Ok(String::new())
+        });
+    }
+
+    {
+        let leds = leds.clone();
+        console.register("led chase", "<rrggbb>", "run a test chase", move |args| {
+            let colour = args
+                .first()
+                .and_then(|hex| Colour::from_hex(hex))
+                .unwrap_or(Colour::WHITE);
+
+            leds.lock()
+                .map_err(|_| String::from("Failed to gain led lock"))?
+                .set(
+                    LedLayer::Base,
+                    Pattern::Chase {
+                        colour,
+                        period: Duration::from_millis(100),
+                    },
+                    Instant::now(),
+                );
+            Ok(String::new())
+        });
+    }
+
+    console.register("led off", "", "clear what the console set", move |_| {
+        leds.lock()
+            .map_err(|_| String::from("Failed to gain led lock"))?
+            .clear(LedLayer::Base);
+        Ok(String::new())
+    });
+}
@@ -1,9 +1,10 @@
 use crate::apa102::{LEDState, APA102};
+use crate::supervisor::{Task, TaskSpec};
 use crate::{EventSet, Events};
 use esp_idf_svc::hal::delay::FreeRtos;
 use esp_idf_svc::hal::gpio::{AnyOutputPin, Output, PinDriver};
 use once_cell::sync::Lazy;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::RwLock;
 
 #[derive(Copy, Clone)]
@@ -70,6 +71,7 @@ impl Events<LEDEventSet> for LEDEvents {
 pub struct LEDInterface(Vec<LEDState>);
 
 static REQUESTED_LED_STATE: Lazy<RwLock<Vec<LEDState>>> = Lazy::new(|| RwLock::new(vec![]));
+static LEDS_BLANKED: AtomicBool = AtomicBool::new(false);
 
 impl LEDInterface {
     fn init(mut led: Vec<LEDState>) {
@@ -93,6 +95,12 @@ impl LEDInterface {
         }
     }
 
+    /// Turns every led off without forgetting the requested state, which comes back on unblanking.
+    pub fn set_blanked(blanked: bool) {
+        LEDS_BLANKED.store(blanked, Ordering::Relaxed);
+        LEDEvents::set(LEDEventSet::UpdateLed);
+    }
+
     pub fn set_led_vec(mut led: Vec<LEDState>) -> Result<(), String> {
         match REQUESTED_LED_STATE.write() {
             Ok(mut led_data) => {
@@ -112,28 +120,35 @@ impl LEDInterface {
     }
 }
 
-fn led_task(mut apa: APA102) -> ! {
+fn led_task(apa: &mut APA102) -> Result<(), String> {
     loop {
         let _ = LED_EVENTS.wait_for_any();
 
         let requested_led_state = REQUESTED_LED_STATE
             .read()
-            .expect("Main task failed to gain read access to requested led");
-        apa.set_led_array(requested_led_state.clone())
-            .expect("Failed to set led on apa");
+            .map_err(|err| format!("Failed to gain requested led read lock, {}", err))?;
+
+        let led_state = if LEDS_BLANKED.load(Ordering::Relaxed) {
+            vec![LEDState::OFF; requested_led_state.len()]
+        } else {
+            requested_led_state.clone()
+        };
+        drop(requested_led_state);
+
+        apa.set_led_array(led_state)
+            .map_err(|err| format!("Failed to set led on apa, {}", err))?;
     }
 }
 
 pub fn led_init(
+    num_led: u32,
     spi_clk: PinDriver<'static, AnyOutputPin, Output>,
     spi_do: PinDriver<'static, AnyOutputPin, Output>,
-) {
-    let apa = APA102::new(7, spi_clk, spi_do);
+) -> Task {
+    let mut apa = APA102::new(num_led, spi_clk, spi_do);
     LEDInterface::init(apa.led_states.clone());
 
-    std::thread::Builder::new()
-        .name("led_task".into())
-        .stack_size(32 * 100)
-        .spawn(move || led_task(apa))
-        .unwrap();
+    Task::new(TaskSpec::new("led_task", 32 * 100), move |_| {
+        led_task(&mut apa)
+    })
 }
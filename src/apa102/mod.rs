@@ -1,18 +1,24 @@
+pub mod commands;
+#[cfg(target_os = "espidf")]
 pub mod interface;
+pub mod pattern;
 
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::hal::gpio::{AnyOutputPin, Level, Output, PinDriver};
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::sys::EspError;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Brightness(u8);
 
+#[cfg(target_os = "espidf")]
 pub struct APA102 {
     pin_clk: PinDriver<'static, AnyOutputPin, Output>,
     pin_do: PinDriver<'static, AnyOutputPin, Output>,
     led_states: Vec<LEDState>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct LEDState {
     pub brightness: Brightness,
     pub red: u8,
@@ -20,6 +26,15 @@ pub struct LEDState {
     pub blue: u8,
 }
 
+impl LEDState {
+    pub const OFF: Self = Self {
+        brightness: Brightness::OFF,
+        red: 0,
+        green: 0,
+        blue: 0,
+    };
+}
+
 impl Brightness {
     pub const MAX: Self = Self(0b11111);
     pub const MIN: Self = Self(0b0);
@@ -38,6 +53,7 @@ impl Brightness {
     }
 }
 
+#[cfg(target_os = "espidf")]
 impl APA102 {
     pub fn new(
         num_led: u32,
@@ -0,0 +1,236 @@
+//! Animated patterns for the LED strip, computed from elapsed time so they can be checked off the
+//! device.
+
+use crate::apa102::{Brightness, LEDState};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const RED: Self = Self::new(255, 0, 0);
+    pub const GREEN: Self = Self::new(0, 255, 0);
+    pub const BLUE: Self = Self::new(0, 0, 255);
+    pub const AMBER: Self = Self::new(255, 120, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Parses `rrggbb`, with or without a leading `#`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if hex.len() != 6 {
+            return None;
+        }
+
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        Some(Self::new(
+            (value >> 16) as u8,
+            (value >> 8) as u8,
+            value as u8,
+        ))
+    }
+
+    fn scaled(&self, level: u8) -> Self {
+        let scale = |channel: u8| (channel as u16 * level as u16 / 255) as u8;
+        Self::new(scale(self.red), scale(self.green), scale(self.blue))
+    }
+
+    fn led(&self, brightness: &Brightness) -> LEDState {
+        LEDState {
+            brightness: brightness.clone(),
+            red: self.red,
+            green: self.green,
+            blue: self.blue,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Pattern {
+    Off,
+    Solid(Colour),
+    /// On for the first half of every period.
+    Blink {
+        colour: Colour,
+        period: Duration,
+    },
+    /// Fades in and out once every period.
+    Pulse {
+        colour: Colour,
+        period: Duration,
+    },
+    /// Single lit led running along the strip, one step per period.
+    Chase {
+        colour: Colour,
+        period: Duration,
+    },
+    /// Lights the first `filled` 255ths of the strip, the last lit led partially.
+    Gauge {
+        colour: Colour,
+        filled: u8,
+    },
+}
+
+impl Pattern {
+    pub fn frame(&self, elapsed: Duration, len: usize, brightness: &Brightness) -> Vec<LEDState> {
+        let mut frame = vec![LEDState::OFF; len];
+
+        match self {
+            Pattern::Off => {}
+            Pattern::Solid(colour) => frame.fill(colour.led(brightness)),
+            Pattern::Blink { colour, period } => {
+                if phase(elapsed, *period) < 128 {
+                    frame.fill(colour.led(brightness));
+                }
+            }
+            Pattern::Pulse { colour, period } => {
+                let phase = phase(elapsed, *period) as u16;
+                let level = if phase < 128 {
+                    phase * 2
+                } else {
+                    (255 - phase) * 2
+                };
+                frame.fill(colour.scaled(level.min(255) as u8).led(brightness));
+            }
+            Pattern::Chase { colour, period } => {
+                if len > 0 {
+                    let step = elapsed.as_millis() / period.as_millis().max(1);
+                    frame[step as usize % len] = colour.led(brightness);
+                }
+            }
+            Pattern::Gauge { colour, filled } => {
+                let lit = len as u32 * *filled as u32;
+                for (idx, led) in frame.iter_mut().enumerate() {
+                    let level = lit.saturating_sub(idx as u32 * 255).min(255);
+                    if level > 0 {
+                        *led = colour.scaled(level as u8).led(brightness);
+                    }
+                }
+            }
+        }
+
+        frame
+    }
+}
+
+/// Position within the current period, 0 to 255.
+fn phase(elapsed: Duration, period: Duration) -> u8 {
+    let period = period.as_millis().max(1);
+    ((elapsed.as_millis() % period) * 256 / period) as u8
+}
+
+/// Who asked for a pattern, the highest layer with a pattern is the one shown.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LedLayer {
+    Base,
+    /// Timer gauge, games and happenings show over it while they're on.
+    Timer,
+    Ambient,
+    Notification,
+    /// Alarm and the timer finishing, above the pet's calls but under the battery warning.
+    Alarm,
+    Warning,
+}
+
+struct Playing {
+    pattern: Pattern,
+    started: Instant,
+    until: Option<Instant>,
+}
+
+/// Mixes patterns from several layers into frames for the strip.
+pub struct PatternPlayer {
+    len: usize,
+    brightness: Brightness,
+    layers: BTreeMap<LedLayer, Playing>,
+    last_frame: Option<Vec<LEDState>>,
+}
+
+impl PatternPlayer {
+    pub fn new(len: usize, brightness: Brightness) -> Self {
+        Self {
+            len,
+            brightness,
+            layers: BTreeMap::new(),
+            last_frame: None,
+        }
+    }
+
+    pub fn set_brightness(&mut self, brightness: Brightness) {
+        self.brightness = brightness;
+        self.last_frame = None;
+    }
+
+    pub fn set(&mut self, layer: LedLayer, pattern: Pattern, now: Instant) {
+        self.play(layer, pattern, now, None);
+    }
+
+    /// Plays `pattern` on `layer` and clears it again after `duration`.
+    pub fn set_for(&mut self, layer: LedLayer, pattern: Pattern, now: Instant, duration: Duration) {
+        self.play(layer, pattern, now, Some(now + duration));
+    }
+
+    pub fn clear(&mut self, layer: LedLayer) {
+        self.layers.remove(&layer);
+    }
+
+    pub fn is_playing(&self, layer: LedLayer) -> bool {
+        self.layers.contains_key(&layer)
+    }
+
+    fn play(&mut self, layer: LedLayer, pattern: Pattern, now: Instant, until: Option<Instant>) {
+        // keep the animation running if the same pattern is set again
+        if let Some(playing) = self.layers.get_mut(&layer) {
+            if playing.pattern == pattern {
+                playing.until = until;
+                return;
+            }
+        }
+
+        self.layers.insert(
+            layer,
+            Playing {
+                pattern,
+                started: now,
+                until,
+            },
+        );
+    }
+
+    /// The frame to show at `now`, `None` if it's the same as last time.
+    ///
+    /// Once every layer has been cleared a single blank frame is returned, after that the strip is
+    /// left alone for whoever sets it directly.
+    pub fn frame(&mut self, now: Instant) -> Option<Vec<LEDState>> {
+        self.layers
+            .retain(|_, playing| !matches!(playing.until, Some(until) if now >= until));
+
+        let Some(playing) = self.layers.values().next_back() else {
+            return self
+                .last_frame
+                .take()
+                .map(|_| vec![LEDState::OFF; self.len]);
+        };
+
+        let frame = playing.pattern.frame(
+            now.duration_since(playing.started),
+            self.len,
+            &self.brightness,
+        );
+
+        if self.last_frame.as_ref() == Some(&frame) {
+            return None;
+        }
+
+        self.last_frame = Some(frame.clone());
+        Some(frame)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::battery::{BatteryConfig, BatteryEvent, BatteryMonitor, BatteryStatus};
+use crate::supervisor::{Task, TaskSpec};
+use esp_idf_svc::hal::adc::attenuation::DB_11;
+use esp_idf_svc::hal::adc::oneshot::config::AdcChannelConfig;
+use esp_idf_svc::hal::adc::oneshot::{AdcChannelDriver, AdcDriver};
+use esp_idf_svc::hal::adc::ADC1;
+use esp_idf_svc::hal::delay::FreeRtos;
+use esp_idf_svc::hal::gpio::Gpio4;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::RwLock;
+
+static BATTERY_STATUS: Lazy<RwLock<Option<BatteryStatus>>> = Lazy::new(|| RwLock::new(None));
+static BATTERY_EVENTS: Lazy<RwLock<VecDeque<BatteryEvent>>> =
+    Lazy::new(|| RwLock::new(VecDeque::new()));
+
+pub struct BatteryInterface;
+
+impl BatteryInterface {
+    /// Latest filtered reading, `None` until the first sample has been taken.
+    pub fn status() -> Option<BatteryStatus> {
+        match BATTERY_STATUS.read() {
+            Ok(status) => *status,
+            Err(err) => {
+                log::error!("Failed to gain battery status read lock, {}", err);
+                None
+            }
+        }
+    }
+
+    pub fn take_event() -> Option<BatteryEvent> {
+        match BATTERY_EVENTS.write() {
+            Ok(mut events) => events.pop_front(),
+            Err(err) => {
+                log::error!("Failed to gain battery events write lock, {}", err);
+                None
+            }
+        }
+    }
+}
+
+type BatteryChannel = AdcChannelDriver<'static, Gpio4, AdcDriver<'static, ADC1>>;
+
+fn battery_task(channel: &mut BatteryChannel, config: &BatteryConfig) -> ! {
+    let interval = config.sample_interval.as_millis() as u32;
+    let mut monitor = BatteryMonitor::new(config.clone());
+
+    loop {
+        match channel.read() {
+            Ok(millivolts) => {
+                let events = monitor.update(millivolts);
+
+                match BATTERY_STATUS.write() {
+                    Ok(mut status) => *status = monitor.status(),
+                    Err(err) => log::error!("Failed to gain battery status write lock, {}", err),
+                }
+                match BATTERY_EVENTS.write() {
+                    Ok(mut queue) => queue.extend(events),
+                    Err(err) => log::error!("Failed to gain battery events write lock, {}", err),
+                }
+            }
+            Err(err) => log::error!("Failed to read battery voltage, {}", err),
+        }
+
+        FreeRtos::delay_ms(interval);
+    }
+}
+
+pub fn battery_init(adc: ADC1, pin: Gpio4, config: BatteryConfig) -> Task {
+    let adc = AdcDriver::new(adc).expect("Failed to take battery adc");
+    let channel_config = AdcChannelConfig {
+        attenuation: DB_11,
+        calibration: true,
+        ..Default::default()
+    };
+    let mut channel =
+        AdcChannelDriver::new(adc, pin, &channel_config).expect("Failed to setup battery pin");
+
+    Task::new(TaskSpec::new("battery_task", 32 * 100), move |_| {
+        battery_task(&mut channel, &config)
+    })
+}
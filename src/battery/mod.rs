@@ -0,0 +1,410 @@
+//! Battery voltage filtering, charge estimation and low battery warnings.
+//!
+//! Everything apart from [`interface`] works on millivolt readings so it runs without the ADC.
+
+#[cfg(target_os = "espidf")]
+pub mod interface;
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Voltage to charge curve, points must be sorted by descending voltage.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DischargeCurve(Vec<(u16, u8)>);
+
+impl DischargeCurve {
+    pub fn new(points: Vec<(u16, u8)>) -> Self {
+        Self(points)
+    }
+
+    /// Typical single cell LiPo under a light load.
+    pub fn lipo() -> Self {
+        Self(vec![
+            (4200, 100),
+            (4110, 90),
+            (4020, 80),
+            (3950, 70),
+            (3870, 60),
+            (3840, 50),
+            (3800, 40),
+            (3770, 30),
+            (3730, 20),
+            (3690, 10),
+            (3610, 5),
+            (3270, 0),
+        ])
+    }
+
+    /// Charge in percent, interpolated between the two nearest points.
+    pub fn percent(&self, millivolts: u16) -> u8 {
+        let (Some(first), Some(last)) = (self.0.first(), self.0.last()) else {
+            return 0;
+        };
+
+        if millivolts >= first.0 {
+            return first.1;
+        }
+        if millivolts <= last.0 {
+            return last.1;
+        }
+
+        for pair in self.0.windows(2) {
+            let ((high_mv, high_pct), (low_mv, low_pct)) = (pair[0], pair[1]);
+
+            if millivolts >= low_mv {
+                let span_mv = (high_mv - low_mv) as u32;
+                let span_pct = (high_pct - low_pct) as u32;
+                let above = (millivolts - low_mv) as u32;
+                return low_pct + ((above * span_pct + span_mv / 2) / span_mv) as u8;
+            }
+        }
+
+        last.1
+    }
+}
+
+/// Samples the median is taken over, enough to drop a single spike.
+const MEDIAN_SAMPLES: usize = 3;
+
+/// Exponential moving average of the median of the last few samples, smooths out ADC noise and
+/// ignores single load spikes.
+#[derive(Clone, Debug)]
+pub struct VoltageFilter {
+    /// Weight of a new sample out of 256.
+    weight: u32,
+    recent: VecDeque<u16>,
+    /// Filtered value in 1/256 millivolts.
+    value: Option<u32>,
+}
+
+impl VoltageFilter {
+    pub fn new(weight: u8) -> Self {
+        Self {
+            weight: weight.max(1) as u32,
+            recent: VecDeque::with_capacity(MEDIAN_SAMPLES),
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, millivolts: u16) -> u16 {
+        if self.recent.len() == MEDIAN_SAMPLES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(millivolts);
+
+        // taken as is until there are enough samples for a median
+        let median = match self.recent.len() {
+            MEDIAN_SAMPLES => {
+                let mut sorted: Vec<u16> = self.recent.iter().copied().collect();
+                sorted.sort_unstable();
+                sorted[MEDIAN_SAMPLES / 2]
+            }
+            _ => millivolts,
+        };
+        let sample = (median as u32) << 8;
+
+        let value = match self.value {
+            Some(value) => (value * (256 - self.weight) + sample * self.weight) >> 8,
+            None => sample,
+        };
+        self.value = Some(value);
+
+        ((value + 128) >> 8) as u16
+    }
+
+    pub fn value(&self) -> Option<u16> {
+        self.value.map(|value| ((value + 128) >> 8) as u16)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ChargeState {
+    Discharging,
+    Charging,
+    /// Running from USB with no battery, or a full battery being topped up.
+    External,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum BatteryLevel {
+    Ok,
+    Low,
+    Critical,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BatteryEvent {
+    Level(BatteryLevel),
+    Charge(ChargeState),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BatteryStatus {
+    pub millivolts: u16,
+    pub percent: u8,
+    pub charge: ChargeState,
+    pub level: BatteryLevel,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct BatteryConfig {
+    /// Battery voltage over the voltage seen by the ADC.
+    pub divider: f32,
+    pub curve: DischargeCurve,
+    pub low_percent: u8,
+    pub critical_percent: u8,
+    /// Percent the charge has to rise above a threshold before the level recovers.
+    pub hysteresis: u8,
+    /// Readings above this can only come from external power.
+    pub external_millivolts: u16,
+    /// Rise over the trend window that counts as charging.
+    pub charging_rise_millivolts: u16,
+    pub sample_interval: Duration,
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        Self {
+            divider: 2.0,
+            curve: DischargeCurve::lipo(),
+            low_percent: 20,
+            critical_percent: 5,
+            hysteresis: 3,
+            external_millivolts: 4400,
+            charging_rise_millivolts: 30,
+            sample_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Samples kept to spot the voltage rising while charging.
+const TREND_SAMPLES: usize = 12;
+
+pub struct BatteryMonitor {
+    config: BatteryConfig,
+    filter: VoltageFilter,
+    trend: VecDeque<u16>,
+    status: Option<BatteryStatus>,
+}
+
+impl BatteryMonitor {
+    pub fn new(config: BatteryConfig) -> Self {
+        Self {
+            config,
+            filter: VoltageFilter::new(64),
+            trend: VecDeque::with_capacity(TREND_SAMPLES),
+            status: None,
+        }
+    }
+
+    pub fn config(&self) -> &BatteryConfig {
+        &self.config
+    }
+
+    pub fn status(&self) -> Option<BatteryStatus> {
+        self.status
+    }
+
+    /// Feeds a reading taken at the ADC pin, returns what changed.
+    pub fn update(&mut self, adc_millivolts: u16) -> Vec<BatteryEvent> {
+        let millivolts = self
+            .filter
+            .update((adc_millivolts as f32 * self.config.divider) as u16);
+
+        if self.trend.len() == TREND_SAMPLES {
+            self.trend.pop_front();
+        }
+        self.trend.push_back(millivolts);
+
+        let charge = self.charge_state(millivolts);
+        let percent = self.config.curve.percent(millivolts);
+        let previous = self.status;
+        let level = self.level(percent, charge, previous.map(|status| status.level));
+
+        let status = BatteryStatus {
+            millivolts,
+            percent,
+            charge,
+            level,
+        };
+        self.status = Some(status);
+
+        let mut events = vec![];
+        if previous.map(|status| status.charge) != Some(charge) {
+            events.push(BatteryEvent::Charge(charge));
+        }
+        if previous.map_or(level != BatteryLevel::Ok, |status| status.level != level) {
+            events.push(BatteryEvent::Level(level));
+        }
+
+        events
+    }
+
+    fn charge_state(&self, millivolts: u16) -> ChargeState {
+        if millivolts >= self.config.external_millivolts {
+            return ChargeState::External;
+        }
+
+        let oldest = *self.trend.front().unwrap_or(&millivolts);
+        if self.trend.len() == TREND_SAMPLES
+            && millivolts >= oldest + self.config.charging_rise_millivolts
+        {
+            return ChargeState::Charging;
+        }
+
+        // stay charging until the voltage starts dropping again
+        match self.status {
+            Some(status) if status.charge == ChargeState::Charging && millivolts >= oldest => {
+                ChargeState::Charging
+            }
+            _ => ChargeState::Discharging,
+        }
+    }
+
+    fn level(
+        &self,
+        percent: u8,
+        charge: ChargeState,
+        previous: Option<BatteryLevel>,
+    ) -> BatteryLevel {
+        if charge != ChargeState::Discharging {
+            return BatteryLevel::Ok;
+        }
+
+        let previous = previous.unwrap_or(BatteryLevel::Ok);
+        let critical = self.config.critical_percent;
+        let low = self.config.low_percent;
+        let hysteresis = self.config.hysteresis;
+
+        let level = if percent <= critical {
+            BatteryLevel::Critical
+        } else if percent <= low {
+            BatteryLevel::Low
+        } else {
+            BatteryLevel::Ok
+        };
+
+        // only get better once clear of the threshold
+        match (previous, level) {
+            (BatteryLevel::Critical, BatteryLevel::Low | BatteryLevel::Ok)
+                if percent <= critical + hysteresis =>
+            {
+                BatteryLevel::Critical
+            }
+            (BatteryLevel::Low, BatteryLevel::Ok) if percent <= low + hysteresis => {
+                BatteryLevel::Low
+            }
+            (BatteryLevel::Critical, BatteryLevel::Ok) if percent <= low + hysteresis => {
+                BatteryLevel::Low
+            }
+            _ => level,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_clamps_at_the_ends() {
+        let curve = DischargeCurve::lipo();
+        assert_eq!(curve.percent(4200), 100);
+        assert_eq!(curve.percent(5000), 100);
+        assert_eq!(curve.percent(3270), 0);
+        assert_eq!(curve.percent(2500), 0);
+        assert_eq!(DischargeCurve::new(vec![]).percent(3800), 0);
+    }
+
+    #[test]
+    fn curve_interpolates_between_points() {
+        let curve = DischargeCurve::lipo();
+        for (millivolts, percent) in curve.0.clone() {
+            assert_eq!(curve.percent(millivolts), percent);
+        }
+
+        // halfway between 3840 at 50% and 3800 at 40%, rounding to nearest
+        assert_eq!(curve.percent(3820), 45);
+        assert_eq!(curve.percent(3810), 43);
+        assert_eq!(curve.percent(3805), 41);
+        // halfway down the long tail from 5% to 0%
+        assert_eq!(curve.percent(3440), 3);
+
+        let mut last = 0;
+        for millivolts in 3000..4400 {
+            let percent = curve.percent(millivolts);
+            assert!(percent >= last, "{}mV", millivolts);
+            last = percent;
+        }
+    }
+
+    #[test]
+    fn filter_ignores_a_single_outlier() {
+        let mut filter = VoltageFilter::new(64);
+        assert_eq!(filter.value(), None);
+        assert_eq!(filter.update(3800), 3800);
+        assert_eq!(filter.update(3800), 3800);
+        assert_eq!(filter.update(3800), 3800);
+
+        assert_eq!(filter.update(4400), 3800);
+        assert_eq!(filter.update(3800), 3800);
+        assert_eq!(filter.update(3000), 3800);
+        assert_eq!(filter.update(3800), 3800);
+    }
+
+    #[test]
+    fn filter_follows_a_lasting_change() {
+        let mut filter = VoltageFilter::new(64);
+        for _ in 0..3 {
+            filter.update(3800);
+        }
+
+        // the median moves on the second sample, then a quarter of the way each time
+        assert_eq!(filter.update(4000), 3800);
+        assert_eq!(filter.update(4000), 3850);
+        assert_eq!(filter.update(4000), 3888);
+        for _ in 0..30 {
+            filter.update(4000);
+        }
+        assert_eq!(filter.value(), Some(4000));
+    }
+
+    #[test]
+    fn single_outlier_changes_nothing() {
+        let mut monitor = BatteryMonitor::new(BatteryConfig::default());
+        // 3800mV at the battery through the divider
+        assert_eq!(
+            monitor.update(1900),
+            [BatteryEvent::Charge(ChargeState::Discharging)]
+        );
+        for _ in 0..TREND_SAMPLES {
+            assert!(monitor.update(1900).is_empty());
+        }
+
+        // a spike that would read as external power on its own
+        assert!(monitor.update(2300).is_empty());
+        let status = monitor.status().unwrap();
+        assert_eq!(status.charge, ChargeState::Discharging);
+        assert_eq!(status.level, BatteryLevel::Ok);
+        assert!(status.millivolts < monitor.config().external_millivolts);
+    }
+
+    #[test]
+    fn level_recovers_only_past_the_hysteresis() {
+        let monitor = BatteryMonitor::new(BatteryConfig::default());
+        let level =
+            |percent, previous| monitor.level(percent, ChargeState::Discharging, Some(previous));
+
+        assert_eq!(level(20, BatteryLevel::Ok), BatteryLevel::Low);
+        assert_eq!(level(23, BatteryLevel::Low), BatteryLevel::Low);
+        assert_eq!(level(24, BatteryLevel::Low), BatteryLevel::Ok);
+        assert_eq!(level(5, BatteryLevel::Low), BatteryLevel::Critical);
+        assert_eq!(level(8, BatteryLevel::Critical), BatteryLevel::Critical);
+        assert_eq!(level(9, BatteryLevel::Critical), BatteryLevel::Low);
+        assert_eq!(level(30, BatteryLevel::Critical), BatteryLevel::Ok);
+        assert_eq!(
+            monitor.level(1, ChargeState::Charging, Some(BatteryLevel::Critical)),
+            BatteryLevel::Ok
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! ESP32-S3-DevKitC with the display, encoder and LEDs on a breadboard breakout.
+//!
+//! The boot button doubles as the pet button and there's no battery to monitor. GPIO35-37 are
+//! left alone for the octal PSRAM.
+
+use super::{BoardConfig, BoardPins, DisplayPins};
+use crate::battery::BatteryConfig;
+use crate::rotary_encoder::LatchMode;
+use esp_idf_svc::hal::gpio::{IOPin, InputPin, OutputPin};
+use esp_idf_svc::hal::peripherals::Peripherals;
+
+pub fn config() -> BoardConfig {
+    BoardConfig {
+        name: "ESP32-S3-DevKitC breakout",
+        led_count: 7,
+        has_battery: false,
+        encoder_latch: LatchMode::FOUR3,
+        button_gpio: 0,
+        encoder_gpio: vec![5, 6],
+        battery: BatteryConfig::default(),
+    }
+}
+
+pub fn pins(peripherals: Peripherals) -> BoardPins {
+    let pins = peripherals.pins;
+
+    BoardPins {
+        power: pins.gpio21.downgrade_output(),
+        button: pins.gpio0.downgrade(),
+        encoder: Some((pins.gpio6.downgrade_input(), pins.gpio5.downgrade_input())),
+        leds: Some((pins.gpio7.downgrade_output(), pins.gpio8.downgrade_output())),
+        battery: None,
+        display: Some(DisplayPins {
+            spi: peripherals.spi2,
+            clk: pins.gpio12.downgrade_output(),
+            sdo: pins.gpio11.downgrade_output(),
+            cs: pins.gpio10.downgrade_output(),
+            bl: pins.gpio14.downgrade_output(),
+            dc: pins.gpio13.downgrade_output(),
+            rst: pins.gpio9.downgrade_output(),
+            bl_timer: peripherals.ledc.timer0,
+            bl_channel: peripherals.ledc.channel0,
+        }),
+    }
+}
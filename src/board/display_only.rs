@@ -0,0 +1,19 @@
+//! LilyGO T-Display-S3 without the LED strip fitted.
+
+use super::{lilygo, BoardConfig, BoardPins};
+use esp_idf_svc::hal::peripherals::Peripherals;
+
+pub fn config() -> BoardConfig {
+    BoardConfig {
+        name: "LilyGO T-Display-S3, no LEDs",
+        led_count: 0,
+        ..lilygo::config()
+    }
+}
+
+pub fn pins(peripherals: Peripherals) -> BoardPins {
+    BoardPins {
+        leds: None,
+        ..lilygo::pins(peripherals)
+    }
+}
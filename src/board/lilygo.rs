@@ -0,0 +1,45 @@
+//! LilyGO T-Display-S3 with the encoder, button and LED strip on the side header.
+
+use super::{BoardConfig, BoardPins, DisplayPins};
+use crate::battery::BatteryConfig;
+use crate::rotary_encoder::LatchMode;
+use esp_idf_svc::hal::gpio::{IOPin, InputPin, OutputPin};
+use esp_idf_svc::hal::peripherals::Peripherals;
+
+pub fn config() -> BoardConfig {
+    BoardConfig {
+        name: "LilyGO T-Display-S3",
+        led_count: 7,
+        has_battery: true,
+        encoder_latch: LatchMode::TWO3,
+        button_gpio: 0,
+        encoder_gpio: vec![1, 2],
+        battery: BatteryConfig::default(),
+    }
+}
+
+pub fn pins(peripherals: Peripherals) -> BoardPins {
+    let pins = peripherals.pins;
+
+    BoardPins {
+        power: pins.gpio46.downgrade_output(),
+        button: pins.gpio0.downgrade(),
+        encoder: Some((pins.gpio2.downgrade_input(), pins.gpio1.downgrade_input())),
+        leds: Some((
+            pins.gpio45.downgrade_output(),
+            pins.gpio42.downgrade_output(),
+        )),
+        battery: Some((peripherals.adc1, pins.gpio4)),
+        display: Some(DisplayPins {
+            spi: peripherals.spi2,
+            clk: pins.gpio12.downgrade_output(),
+            sdo: pins.gpio11.downgrade_output(),
+            cs: pins.gpio10.downgrade_output(),
+            bl: pins.gpio15.downgrade_output(),
+            dc: pins.gpio13.downgrade_output(),
+            rst: pins.gpio9.downgrade_output(),
+            bl_timer: peripherals.ledc.timer0,
+            bl_channel: peripherals.ledc.channel0,
+        }),
+    }
+}
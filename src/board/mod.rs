@@ -0,0 +1,168 @@
+//! Pin mappings and optional hardware for each supported board.
+//!
+//! The profile is picked with one of the `board-*` cargo features, [`Board::init`] brings up
+//! everything the board has and hands back the tasks for the supervisor to start.
+
+#[cfg(feature = "board-devkit")]
+mod devkit;
+#[cfg(feature = "board-display-only")]
+mod display_only;
+#[cfg(any(feature = "board-lilygo", feature = "board-display-only"))]
+mod lilygo;
+
+#[cfg(feature = "board-devkit")]
+use devkit as profile;
+#[cfg(feature = "board-display-only")]
+use display_only as profile;
+#[cfg(feature = "board-lilygo")]
+use lilygo as profile;
+
+#[cfg(not(any(
+    feature = "board-lilygo",
+    feature = "board-devkit",
+    feature = "board-display-only"
+)))]
+compile_error!(
+    "Select a board with one of the board-lilygo, board-devkit or board-display-only features"
+);
+
+#[cfg(any(
+    all(feature = "board-lilygo", feature = "board-devkit"),
+    all(feature = "board-lilygo", feature = "board-display-only"),
+    all(feature = "board-devkit", feature = "board-display-only")
+))]
+compile_error!(
+    "Only one board feature can be enabled, disable the default features to change board"
+);
+
+use crate::apa102::interface::led_init;
+use crate::battery::interface::battery_init;
+use crate::battery::BatteryConfig;
+use crate::button_interface::button_init;
+use crate::device::power::WakeSources;
+use crate::device::{DevicePowerState, PowerToggle};
+use crate::rotary_encoder::interface::rotary_encoder_init;
+use crate::rotary_encoder::LatchMode;
+use crate::supervisor::Task;
+use crate::tft::{tft_init, AppSpawner};
+use esp_idf_svc::hal::adc::ADC1;
+use esp_idf_svc::hal::gpio::{AnyIOPin, AnyInputPin, AnyOutputPin, Gpio4, PinDriver};
+use esp_idf_svc::hal::ledc::{CHANNEL0, TIMER0};
+use esp_idf_svc::hal::peripherals::Peripherals;
+use esp_idf_svc::hal::spi::SPI2;
+use std::time::Duration;
+
+/// What a board has fitted, anything missing is simply not started.
+#[derive(Clone, Debug)]
+pub struct BoardConfig {
+    pub name: &'static str,
+    /// APA102 LEDs on the strip, 0 when there are none.
+    pub led_count: u32,
+    pub has_battery: bool,
+    pub encoder_latch: LatchMode,
+    /// GPIO numbers of the button and encoder, used to wake from sleep.
+    pub button_gpio: i32,
+    pub encoder_gpio: Vec<i32>,
+    pub battery: BatteryConfig,
+}
+
+impl BoardConfig {
+    pub fn wake_sources(&self, timer: Option<Duration>) -> WakeSources {
+        WakeSources {
+            button: self.button_gpio,
+            encoder: self.encoder_gpio.clone(),
+            timer,
+        }
+    }
+}
+
+pub struct DisplayPins {
+    pub spi: SPI2,
+    pub clk: AnyOutputPin,
+    pub sdo: AnyOutputPin,
+    pub cs: AnyOutputPin,
+    pub bl: AnyOutputPin,
+    pub dc: AnyOutputPin,
+    pub rst: AnyOutputPin,
+    pub bl_timer: TIMER0,
+    pub bl_channel: CHANNEL0,
+}
+
+/// Pins taken from the peripherals by the selected profile.
+pub struct BoardPins {
+    pub power: AnyOutputPin,
+    pub button: AnyIOPin,
+    pub encoder: Option<(AnyInputPin, AnyInputPin)>,
+    /// Clock and data.
+    pub leds: Option<(AnyOutputPin, AnyOutputPin)>,
+    pub battery: Option<(ADC1, Gpio4)>,
+    pub display: Option<DisplayPins>,
+}
+
+/// The display isn't started until the app to run on it is known.
+pub struct Display(DisplayPins);
+
+impl Display {
+    pub fn start(self, app_spawner: AppSpawner) -> Task {
+        let pins = self.0;
+        tft_init(
+            pins.spi,
+            pins.clk,
+            pins.sdo,
+            pins.cs,
+            pins.bl,
+            pins.dc,
+            pins.rst,
+            pins.bl_timer,
+            pins.bl_channel,
+            app_spawner,
+        )
+    }
+}
+
+pub struct Board {
+    pub config: BoardConfig,
+    /// Already switched on.
+    pub power: DevicePowerState<'static, AnyOutputPin>,
+    /// Input, LED and battery tasks ready to hand to the supervisor.
+    pub tasks: Vec<Task>,
+    pub display: Option<Display>,
+}
+
+impl Board {
+    pub fn init(peripherals: Peripherals) -> Self {
+        let config = profile::config();
+        let pins = profile::pins(peripherals);
+        log::info!("Starting on {}", config.name);
+
+        let mut power = DevicePowerState::new(PinDriver::output(pins.power).unwrap()).unwrap();
+        power.wake().unwrap();
+
+        let mut tasks = vec![];
+
+        if let Some((pin_a, pin_b)) = pins.encoder {
+            let pin_a = PinDriver::input(pin_a).unwrap();
+            let pin_b = PinDriver::input(pin_b).unwrap();
+            tasks.push(rotary_encoder_init(pin_a, pin_b, config.encoder_latch));
+        }
+
+        tasks.push(button_init(PinDriver::input(pins.button).unwrap()));
+
+        if let Some((clk, data)) = pins.leds {
+            let clk = PinDriver::output(clk).unwrap();
+            let data = PinDriver::output(data).unwrap();
+            tasks.push(led_init(config.led_count, clk, data));
+        }
+
+        if let Some((adc, pin)) = pins.battery.filter(|_| config.has_battery) {
+            tasks.push(battery_init(adc, pin, config.battery.clone()));
+        }
+
+        Self {
+            config,
+            power,
+            tasks,
+            display: pins.display.map(Display),
+        }
+    }
+}
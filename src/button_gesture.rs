@@ -0,0 +1,188 @@
+//! Click, double click and long press from the button's raw edges, kept off the driver so the
+//! timing can be checked on the host.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Presses held at least this long are a long press instead of a click.
+pub const LONG_PRESS_TIME: Duration = Duration::from_millis(600);
+/// A second click within this window of the first is a double click.
+pub const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(300);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ButtonGesture {
+    Click,
+    DoubleClick,
+    LongPress,
+}
+
+/// Turns button edges into gestures, a click is held back until the double click window
+/// has passed so a double click never also reports a click.
+#[derive(Default)]
+pub struct GestureDetector {
+    pressed_at: Option<Instant>,
+    pending_click: Option<Instant>,
+    gestures: VecDeque<ButtonGesture>,
+}
+
+impl GestureDetector {
+    pub fn update(&mut self, pressed: bool, now: Instant) {
+        self.flush_click(now);
+
+        if pressed {
+            if self.pressed_at.is_none() {
+                self.pressed_at = Some(now);
+            }
+            return;
+        }
+
+        let Some(pressed_at) = self.pressed_at.take() else {
+            return;
+        };
+
+        if now.duration_since(pressed_at) >= LONG_PRESS_TIME {
+            self.pending_click = None;
+            self.gestures.push_back(ButtonGesture::LongPress);
+        } else if self.pending_click.take().is_some() {
+            self.gestures.push_back(ButtonGesture::DoubleClick);
+        } else {
+            self.pending_click = Some(now);
+        }
+    }
+
+    pub fn poll(&mut self, now: Instant) -> Option<ButtonGesture> {
+        self.flush_click(now);
+        self.gestures.pop_front()
+    }
+
+    /// Time the button has currently been held down for.
+    pub fn held_for(&self, now: Instant) -> Option<Duration> {
+        self.pressed_at
+            .map(|pressed_at| now.duration_since(pressed_at))
+    }
+
+    fn flush_click(&mut self, now: Instant) {
+        if let Some(released_at) = self.pending_click {
+            if now.duration_since(released_at) > DOUBLE_CLICK_TIME {
+                self.pending_click = None;
+                self.gestures.push_back(ButtonGesture::Click);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `(ms, pressed)` edges in order and collects the gestures, polling up to `until` ms.
+    fn run(script: &[(u64, bool)], until: u64) -> Vec<ButtonGesture> {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut detector = GestureDetector::default();
+        let mut gestures = Vec::new();
+
+        for &(ms, pressed) in script {
+            detector.update(pressed, at(ms));
+            while let Some(gesture) = detector.poll(at(ms)) {
+                gestures.push(gesture);
+            }
+        }
+        while let Some(gesture) = detector.poll(at(until)) {
+            gestures.push(gesture);
+        }
+        gestures
+    }
+
+    #[test]
+    fn short_press_is_a_click_once_the_double_click_window_passed() {
+        let script = [(0, true), (100, false)];
+
+        assert_eq!(run(&script, 400), vec![]);
+        assert_eq!(run(&script, 401), vec![ButtonGesture::Click]);
+    }
+
+    #[test]
+    fn long_press_starts_at_its_threshold() {
+        assert_eq!(
+            run(&[(0, true), (599, false)], 1000),
+            vec![ButtonGesture::Click]
+        );
+        assert_eq!(
+            run(&[(0, true), (600, false)], 1000),
+            vec![ButtonGesture::LongPress]
+        );
+    }
+
+    #[test]
+    fn held_button_reports_how_long() {
+        let start = Instant::now();
+        let mut detector = GestureDetector::default();
+        assert_eq!(detector.held_for(start), None);
+
+        detector.update(true, start);
+        detector.update(true, start + Duration::from_millis(200));
+        assert_eq!(
+            detector.held_for(start + Duration::from_millis(700)),
+            Some(Duration::from_millis(700))
+        );
+
+        detector.update(false, start + Duration::from_millis(700));
+        assert_eq!(detector.held_for(start + Duration::from_millis(800)), None);
+        assert_eq!(
+            detector.poll(start + Duration::from_millis(800)),
+            Some(ButtonGesture::LongPress)
+        );
+    }
+
+    #[test]
+    fn two_clicks_within_the_window_are_one_double_click() {
+        assert_eq!(
+            run(&[(0, true), (50, false), (200, true), (350, false)], 2000),
+            vec![ButtonGesture::DoubleClick]
+        );
+    }
+
+    #[test]
+    fn second_release_on_the_window_edge_still_doubles() {
+        assert_eq!(
+            run(&[(0, true), (50, false), (250, true), (350, false)], 2000),
+            vec![ButtonGesture::DoubleClick]
+        );
+    }
+
+    #[test]
+    fn clicks_further_apart_stay_single() {
+        assert_eq!(
+            run(&[(0, true), (50, false), (351, true), (400, false)], 2000),
+            vec![ButtonGesture::Click, ButtonGesture::Click]
+        );
+    }
+
+    #[test]
+    fn long_press_right_after_a_click_keeps_both() {
+        // the click's window runs out while the button is still held
+        assert_eq!(
+            run(&[(0, true), (50, false), (100, true), (800, false)], 2000),
+            vec![ButtonGesture::Click, ButtonGesture::LongPress]
+        );
+    }
+
+    #[test]
+    fn third_click_starts_a_new_gesture() {
+        assert_eq!(
+            run(
+                &[
+                    (0, true),
+                    (50, false),
+                    (100, true),
+                    (150, false),
+                    (200, true),
+                    (250, false)
+                ],
+                2000
+            ),
+            vec![ButtonGesture::DoubleClick, ButtonGesture::Click]
+        );
+    }
+}
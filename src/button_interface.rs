@@ -1,9 +1,13 @@
+use crate::button_gesture::{ButtonGesture, GestureDetector};
+use crate::console::Console;
+use crate::supervisor::{Task, TaskSpec};
 use crate::{EventSet, Events};
 use esp_idf_svc::hal::delay::FreeRtos;
-use esp_idf_svc::hal::gpio::{Gpio0, Input, InterruptType, PinDriver, Pull};
+use esp_idf_svc::hal::gpio::{AnyIOPin, Input, InterruptType, PinDriver, Pull};
 use once_cell::sync::Lazy;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{RwLock, RwLockWriteGuard};
+use std::time::Instant;
 
 #[derive(Copy, Clone)]
 pub enum ButtonEventSet {
@@ -77,6 +81,7 @@ pub struct ButtonInterface {
     toggle_state: AtomicBool,
 
     _has_been_low: bool,
+    gestures: GestureDetector,
 }
 
 static BUTTON_INTERFACE: Lazy<RwLock<ButtonInterface>> =
@@ -88,17 +93,21 @@ impl ButtonInterface {
             button_state: AtomicBool::new(false),
             toggle_state: AtomicBool::new(false),
             _has_been_low: false,
+            gestures: GestureDetector::default(),
         }
     }
 
     fn update_button(button_state: bool) {
-        let interface = BUTTON_INTERFACE
+        let mut interface = BUTTON_INTERFACE
             .write()
             .expect("Failed to gain write lock on led update");
         interface
             .button_state
             .store(button_state, Ordering::Relaxed);
 
+        // button is pulled up, so pressed reads low
+        interface.gestures.update(!button_state, Instant::now());
+
         Self::update_toggle(interface);
     }
 
@@ -117,28 +126,30 @@ impl ButtonInterface {
             .expect("Failed to gain read for button interface");
         interface.toggle_state.load(Ordering::Relaxed)
     }
+
+    /// Takes the oldest gesture that hasn't been handled yet.
+    pub fn take_gesture() -> Option<ButtonGesture> {
+        let mut interface = BUTTON_INTERFACE
+            .write()
+            .expect("Failed to gain write lock for button interface");
+        interface.gestures.poll(Instant::now())
+    }
 }
 
-fn button_task(mut button: PinDriver<'static, Gpio0, Input>) -> ! {
+fn button_task(button: &mut PinDriver<'static, AnyIOPin, Input>) -> Result<(), String> {
     loop {
-        match button.enable_interrupt() {
-            Ok(_) => {}
-            Err(e) => loop {
-                log::error!("Error resetting isr for Button, {}", e);
-                FreeRtos::delay_ms(100);
-                if button.enable_interrupt().is_ok() {
-                    break;
-                }
-            },
-        }
+        button
+            .enable_interrupt()
+            .map_err(|err| format!("Error resetting isr for Button, {}", err))?;
 
         let _ = BUTTON_EVENTS.wait_for_any();
 
         ButtonInterface::update_button(button.is_high());
+        crate::device::mark_activity();
     }
 }
 
-pub fn button_init(mut button: PinDriver<'static, Gpio0, Input>) {
+pub fn button_init(mut button: PinDriver<'static, AnyIOPin, Input>) -> Task {
     button.set_pull(Pull::Up).unwrap();
     button
         .set_interrupt_type(InterruptType::AnyEdge)
@@ -148,13 +159,29 @@ pub fn button_init(mut button: PinDriver<'static, Gpio0, Input>) {
         button.subscribe(button_callback).unwrap();
     }
 
-    std::thread::Builder::new()
-        .name("button_task".into())
-        .stack_size(32 * 60)
-        .spawn(move || button_task(button))
-        .unwrap();
+    Task::new(TaskSpec::new("button_task", 32 * 60), move |_| {
+        button_task(&mut button)
+    })
 }
 
 fn button_callback() {
     ButtonEvents::set(ButtonEventSet::ButtonChange);
 }
+
+pub fn register_commands(console: &mut Console) {
+    console.register("btn", "", "show the button state", |_| {
+        let interface = BUTTON_INTERFACE
+            .read()
+            .map_err(|_| String::from("Failed to gain read for button interface"))?;
+
+        Ok(format!(
+            "level {} toggle {}",
+            if interface.button_state.load(Ordering::Relaxed) {
+                "high"
+            } else {
+                "low"
+            },
+            interface.toggle_state.load(Ordering::Relaxed)
+        ))
+    });
+}
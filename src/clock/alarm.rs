@@ -0,0 +1,291 @@
+//! Daily alarm. Everything is worked out from the clock passed in, so when it goes off can be
+//! checked on the host with a [`ManualClock`](super::ManualClock).
+
+use crate::clock::{Clock, TimeOfDay, Timestamp};
+use std::time::Duration;
+
+/// Rings for this long before giving up if nobody stops it.
+pub const RING_FOR: Duration = Duration::from_secs(5 * 60);
+pub const SNOOZE_FOR: Duration = Duration::from_secs(9 * 60);
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AlarmState {
+    Idle,
+    Ringing { since: Timestamp },
+    Snoozed { until: Timestamp },
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Alarm {
+    time: TimeOfDay,
+    enabled: bool,
+    state: AlarmState,
+    /// Time of the last update, the alarm goes off once the clock has passed `time` since.
+    checked: Option<Timestamp>,
+}
+
+impl Default for Alarm {
+    fn default() -> Self {
+        Self::new(TimeOfDay::new(7, 0), false)
+    }
+}
+
+impl Alarm {
+    pub fn new(time: TimeOfDay, enabled: bool) -> Self {
+        Self {
+            time,
+            enabled,
+            state: AlarmState::Idle,
+            checked: None,
+        }
+    }
+
+    pub fn time(&self) -> TimeOfDay {
+        self.time
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn state(&self) -> AlarmState {
+        self.state
+    }
+
+    pub fn is_ringing(&self) -> bool {
+        matches!(self.state, AlarmState::Ringing { .. })
+    }
+
+    /// Turns the alarm on at `time`.
+    pub fn set(&mut self, time: TimeOfDay) {
+        self.time = time;
+        self.enabled = true;
+    }
+
+    /// Turning it off also stops it ringing or snoozing.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.state = AlarmState::Idle;
+        }
+    }
+
+    /// Checks the clock, true if the alarm has just started ringing.
+    ///
+    /// The alarm goes off if `time` was passed since the last update, so it still rings after
+    /// waking late from sleep. Nothing rings on the first update, while the clock isn't set or
+    /// when it's been set backwards.
+    pub fn update(&mut self, clock: &dyn Clock, utc_offset: i16) -> bool {
+        let now = clock.now();
+        let checked = self.checked.replace(now);
+
+        match self.state {
+            AlarmState::Ringing { since } => {
+                // a clock set backwards stops it too
+                let rung_out = match now.since(since) {
+                    Some(rung) => rung >= RING_FOR,
+                    None => true,
+                };
+                if rung_out {
+                    self.state = AlarmState::Idle;
+                }
+                false
+            }
+            AlarmState::Snoozed { until } if now >= until => {
+                self.state = AlarmState::Ringing { since: now };
+                true
+            }
+            AlarmState::Snoozed { .. } => false,
+            AlarmState::Idle => {
+                let second = Duration::from_secs(1);
+                let passed = match (checked, now.local_time(utc_offset)) {
+                    (Some(checked), Some(local)) => match now.since(checked) {
+                        // the clock was set backwards
+                        None => false,
+                        Some(elapsed) if elapsed >= DAY => true,
+                        Some(_) => checked.local_time(utc_offset).is_some_and(|checked| {
+                            self.time.is_between(checked.add(second), local.add(second))
+                        }),
+                    },
+                    _ => false,
+                };
+
+                if self.enabled && passed {
+                    self.state = AlarmState::Ringing { since: now };
+                }
+                self.enabled && passed
+            }
+        }
+    }
+
+    /// Stops ringing and goes off again after [`SNOOZE_FOR`].
+    pub fn snooze(&mut self, clock: &dyn Clock) {
+        if self.is_ringing() {
+            self.state = AlarmState::Snoozed {
+                until: clock.now().add(SNOOZE_FOR),
+            };
+        }
+    }
+
+    /// Stops ringing or snoozing until tomorrow.
+    pub fn stop(&mut self) {
+        self.state = AlarmState::Idle;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{ManualClock, CLOCK_SET_AFTER};
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn clock_at(hour: u8, minute: u8) -> ManualClock {
+        let day = Timestamp(CLOCK_SET_AFTER);
+        ManualClock::new(day.with_local_time(TimeOfDay::new(hour, minute), 0))
+    }
+
+    /// Updates once a minute for `minutes`, counting how often the alarm went off.
+    fn run(alarm: &mut Alarm, clock: &ManualClock, minutes: u32) -> u32 {
+        (0..minutes)
+            .filter(|_| {
+                clock.advance(MINUTE);
+                alarm.update(clock, 0)
+            })
+            .count() as u32
+    }
+
+    #[test]
+    fn rings_once_when_the_time_is_passed() {
+        let clock = clock_at(6, 50);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        assert!(!alarm.update(&clock, 0));
+
+        assert_eq!(run(&mut alarm, &clock, 9), 0);
+        assert_eq!(run(&mut alarm, &clock, 1), 1);
+        assert!(alarm.is_ringing());
+        assert_eq!(run(&mut alarm, &clock, 60), 0);
+        assert!(!alarm.is_ringing());
+
+        // and again the next day
+        assert_eq!(run(&mut alarm, &clock, 23 * 60), 1);
+    }
+
+    #[test]
+    fn nothing_rings_on_the_first_update_or_without_a_set_clock() {
+        let clock = clock_at(7, 0);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        assert!(!alarm.update(&clock, 0));
+
+        let clock = ManualClock::new(Timestamp(0));
+        let mut alarm = Alarm::new(TimeOfDay::new(0, 5), true);
+        assert_eq!(run(&mut alarm, &clock, 24 * 60), 0);
+    }
+
+    #[test]
+    fn rings_across_midnight() {
+        let clock = clock_at(23, 55);
+        let mut alarm = Alarm::new(TimeOfDay::new(0, 0), true);
+        alarm.update(&clock, 0);
+        assert_eq!(run(&mut alarm, &clock, 10), 1);
+
+        // waking up late still rings
+        let clock = clock_at(23, 50);
+        let mut alarm = Alarm::new(TimeOfDay::new(0, 5), true);
+        alarm.update(&clock, 0);
+        clock.advance(20 * MINUTE);
+        assert!(alarm.update(&clock, 0));
+    }
+
+    #[test]
+    fn rings_after_sleeping_through_a_whole_day() {
+        let clock = clock_at(8, 0);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        alarm.update(&clock, 0);
+        clock.advance(Duration::from_secs(25 * 60 * 60));
+        assert!(alarm.update(&clock, 0));
+    }
+
+    #[test]
+    fn local_time_is_used() {
+        let clock = clock_at(5, 55);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        alarm.update(&clock, 60);
+        let mut rang = vec![];
+        for _ in 0..10 {
+            clock.advance(MINUTE);
+            if alarm.update(&clock, 60) {
+                rang.push(clock.now().local_time(60));
+            }
+        }
+        assert_eq!(rang, vec![Some(TimeOfDay::new(7, 0))]);
+    }
+
+    #[test]
+    fn silent_while_disabled() {
+        let clock = clock_at(6, 0);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), false);
+        alarm.update(&clock, 0);
+        assert_eq!(run(&mut alarm, &clock, 24 * 60), 0);
+
+        // turning it on after the time doesn't ring for the time already passed
+        clock.advance(61 * MINUTE);
+        alarm.update(&clock, 0);
+        alarm.set_enabled(true);
+        assert_eq!(run(&mut alarm, &clock, 60), 0);
+
+        alarm.set(TimeOfDay::new(8, 30));
+        assert_eq!(run(&mut alarm, &clock, 60), 1);
+        alarm.set_enabled(false);
+        assert_eq!(alarm.state(), AlarmState::Idle);
+    }
+
+    #[test]
+    fn silent_when_the_clock_steps_back() {
+        let clock = clock_at(8, 0);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        alarm.update(&clock, 0);
+
+        clock.set(clock_at(7, 30).now());
+        assert!(!alarm.update(&clock, 0));
+        clock.set(clock_at(6, 0).now());
+        assert!(!alarm.update(&clock, 0));
+
+        // then rings when the time comes round again
+        assert_eq!(run(&mut alarm, &clock, 61), 1);
+    }
+
+    #[test]
+    fn snooze_rings_again_and_stop_waits_for_tomorrow() {
+        let clock = clock_at(6, 59);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        alarm.update(&clock, 0);
+        assert_eq!(run(&mut alarm, &clock, 1), 1);
+
+        alarm.snooze(&clock);
+        assert!(!alarm.is_ringing());
+        assert_eq!(run(&mut alarm, &clock, 8), 0);
+        assert_eq!(run(&mut alarm, &clock, 1), 1);
+
+        alarm.stop();
+        assert_eq!(run(&mut alarm, &clock, 23 * 60), 0);
+        assert_eq!(run(&mut alarm, &clock, 60), 1);
+    }
+
+    #[test]
+    fn rings_out_on_its_own() {
+        let clock = clock_at(6, 59);
+        let mut alarm = Alarm::new(TimeOfDay::new(7, 0), true);
+        alarm.update(&clock, 0);
+        run(&mut alarm, &clock, 1);
+
+        clock.advance(RING_FOR - MINUTE);
+        alarm.update(&clock, 0);
+        assert!(alarm.is_ringing());
+        clock.advance(MINUTE);
+        alarm.update(&clock, 0);
+        assert!(!alarm.is_ringing());
+    }
+}
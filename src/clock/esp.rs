@@ -0,0 +1,17 @@
+use super::{SystemClock, Timestamp};
+use esp_idf_svc::sys;
+
+impl SystemClock {
+    /// Sets the system time, which the RTC timer then keeps through sleep and resets.
+    pub(super) fn set_time(&self, now: Timestamp) -> Result<(), String> {
+        let time = sys::timeval {
+            tv_sec: now.as_secs() as _,
+            tv_usec: 0,
+        };
+
+        match unsafe { sys::settimeofday(&time, std::ptr::null()) } {
+            0 => Ok(()),
+            err => Err(format!("Failed to set the time, {}", err)),
+        }
+    }
+}
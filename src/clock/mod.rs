@@ -0,0 +1,239 @@
+//! Wall clock time, injected everywhere it's needed so the host can run the pet on a fake clock.
+
+pub mod alarm;
+#[cfg(target_os = "espidf")]
+mod esp;
+pub mod timer;
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Times before this are taken to mean the clock was never set, 2024-01-01.
+const CLOCK_SET_AFTER: u64 = 1_704_067_200;
+
+const DAY: u32 = 24 * 60 * 60;
+
+/// Seconds since the unix epoch.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    pub fn from_secs(secs: u64) -> Self {
+        Self(secs)
+    }
+
+    pub fn as_secs(&self) -> u64 {
+        self.0
+    }
+
+    /// Time passed since `earlier`, `None` if `earlier` is actually later, e.g. after the clock
+    /// was lost.
+    pub fn since(&self, earlier: Timestamp) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration::from_secs)
+    }
+
+    pub fn add(&self, duration: Duration) -> Timestamp {
+        Timestamp(self.0 + duration.as_secs())
+    }
+
+    /// Time of day with `utc_offset` minutes added, `None` while the clock isn't set.
+    pub fn local_time(&self, utc_offset: i16) -> Option<TimeOfDay> {
+        if self.0 < CLOCK_SET_AFTER {
+            return None;
+        }
+
+        let local = self.0 as i64 + utc_offset as i64 * 60;
+        Some(TimeOfDay(local.rem_euclid(DAY as i64) as u32))
+    }
+
+    /// The same local day at `time`. A clock that was never set is put on the day after it's
+    /// taken to be set from, so the result always counts as set.
+    pub fn with_local_time(&self, time: TimeOfDay, utc_offset: i16) -> Timestamp {
+        let day = match self.0 < CLOCK_SET_AFTER {
+            true => CLOCK_SET_AFTER + DAY as u64,
+            false => self.0,
+        };
+        let offset = utc_offset as i64 * 60;
+        let local = day as i64 + offset;
+        let midnight = local - local.rem_euclid(DAY as i64);
+        Timestamp((midnight + time.0 as i64 - offset) as u64)
+    }
+}
+
+/// Seconds since local midnight.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct TimeOfDay(u32);
+
+impl TimeOfDay {
+    pub const fn new(hour: u8, minute: u8) -> Self {
+        Self((hour as u32 * 60 + minute as u32) * 60)
+    }
+
+    /// Wraps around midnight.
+    pub fn from_secs(secs: u32) -> Self {
+        Self(secs % DAY)
+    }
+
+    pub fn hour(&self) -> u8 {
+        (self.0 / 3600) as u8
+    }
+
+    pub fn minute(&self) -> u8 {
+        (self.0 / 60 % 60) as u8
+    }
+
+    pub fn second(&self) -> u8 {
+        (self.0 % 60) as u8
+    }
+
+    pub fn as_secs(&self) -> u32 {
+        self.0
+    }
+
+    /// Wraps around midnight.
+    pub fn add(&self, duration: Duration) -> Self {
+        Self(((self.0 as u64 + duration.as_secs()) % DAY as u64) as u32)
+    }
+
+    /// Wraps around midnight.
+    pub fn sub(&self, duration: Duration) -> Self {
+        let back = (duration.as_secs() % DAY as u64) as u32;
+        Self((self.0 + DAY - back) % DAY)
+    }
+
+    /// True if this falls in `start..end`, which may run over midnight.
+    pub fn is_between(&self, start: TimeOfDay, end: TimeOfDay) -> bool {
+        if start <= end {
+            start <= *self && *self < end
+        } else {
+            start <= *self || *self < end
+        }
+    }
+
+    /// Hours and minutes, a 12 hour clock drops the leading zero and adds am or pm.
+    pub fn format(&self, format: ClockFormat) -> String {
+        match format {
+            ClockFormat::TwentyFourHour => self.to_string(),
+            ClockFormat::TwelveHour => {
+                let suffix = if self.hour() < 12 { "am" } else { "pm" };
+                let hour = match self.hour() % 12 {
+                    0 => 12,
+                    hour => hour,
+                };
+                format!("{}:{:02}{}", hour, self.minute(), suffix)
+            }
+        }
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ClockFormat {
+    #[default]
+    TwentyFourHour,
+    TwelveHour,
+}
+
+impl ClockFormat {
+    pub const ALL: [ClockFormat; 2] = [ClockFormat::TwentyFourHour, ClockFormat::TwelveHour];
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        Self::ALL.get(val as usize).copied()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ClockFace {
+    #[default]
+    Digital,
+    Analogue,
+}
+
+impl ClockFace {
+    pub const ALL: [ClockFace; 2] = [ClockFace::Digital, ClockFace::Analogue];
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        Self::ALL.get(val as usize).copied()
+    }
+}
+
+pub trait Clock {
+    fn now(&self) -> Timestamp;
+
+    /// Moves the clock to `now`, e.g. once the owner has set the time by hand.
+    fn set(&self, now: Timestamp) -> Result<(), String>;
+}
+
+/// System time, on the ESP32-S3 this is kept by the RTC timer so it carries on through deep
+/// sleep and resets but starts again from zero after the power has been removed.
+#[derive(Copy, Clone, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => Timestamp(duration.as_secs()),
+            Err(err) => {
+                log::error!("System time is before the epoch, {}", err);
+                Timestamp(0)
+            }
+        }
+    }
+
+    fn set(&self, now: Timestamp) -> Result<(), String> {
+        self.set_time(now)
+    }
+}
+
+#[cfg(not(target_os = "espidf"))]
+impl SystemClock {
+    /// The host's clock belongs to the OS, use a [`ManualClock`] there instead.
+    fn set_time(&self, _now: Timestamp) -> Result<(), String> {
+        Err(String::from(
+            "The system clock can only be set on the device",
+        ))
+    }
+}
+
+/// Clock that only moves when told to, for driving the pet from tests and the console.
+#[derive(Default)]
+pub struct ManualClock(AtomicU64);
+
+impl ManualClock {
+    pub fn new(now: Timestamp) -> Self {
+        Self(AtomicU64::new(now.0))
+    }
+
+    pub fn set(&self, now: Timestamp) {
+        self.0.store(now.0, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.0.fetch_add(duration.as_secs(), Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        Timestamp(self.0.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, now: Timestamp) -> Result<(), String> {
+        ManualClock::set(self, now);
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+//! Countdown and pomodoro timer, which like the alarm only reads the clock it's given.
+
+use crate::clock::{Clock, Timestamp};
+use std::time::Duration;
+
+pub const POMODORO_WORK: Duration = Duration::from_secs(25 * 60);
+pub const POMODORO_BREAK: Duration = Duration::from_secs(5 * 60);
+/// Countdowns are set in whole minutes up to this.
+pub const MAX_LENGTH: Duration = Duration::from_secs(99 * 60);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TimerMode {
+    Countdown,
+    /// Work and break phases one after the other until it's reset.
+    Pomodoro,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Phase {
+    Work,
+    Break,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Run {
+    Stopped,
+    Running { ends: Timestamp },
+    Paused { left: Duration },
+    Finished,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TimerEvent {
+    /// A countdown reached zero.
+    Finished,
+    /// A pomodoro moved on to the next phase, which has already started.
+    Phase(Phase),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Timer {
+    mode: TimerMode,
+    length: Duration,
+    phase: Phase,
+    run: Run,
+    /// Work phases finished since the last reset.
+    pomodoros: u32,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5 * 60))
+    }
+}
+
+impl Timer {
+    pub fn new(length: Duration) -> Self {
+        Self {
+            mode: TimerMode::Countdown,
+            length: length.clamp(Duration::from_secs(60), MAX_LENGTH),
+            phase: Phase::Work,
+            run: Run::Stopped,
+            pomodoros: 0,
+        }
+    }
+
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    /// Switching mode resets the timer.
+    pub fn set_mode(&mut self, mode: TimerMode) {
+        self.mode = mode;
+        self.reset();
+    }
+
+    /// Length of a countdown.
+    pub fn length(&self) -> Duration {
+        self.length
+    }
+
+    /// Changes the countdown by whole minutes, only while it's stopped.
+    pub fn adjust_length(&mut self, minutes: i32) {
+        if self.run != Run::Stopped {
+            return;
+        }
+
+        let length = self.length.as_secs() as i64 / 60 + minutes as i64;
+        self.length =
+            Duration::from_secs(length.clamp(1, MAX_LENGTH.as_secs() as i64 / 60) as u64 * 60);
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn pomodoros(&self) -> u32 {
+        self.pomodoros
+    }
+
+    /// Length of the phase being timed, the whole countdown outside of a pomodoro.
+    pub fn phase_length(&self) -> Duration {
+        match (self.mode, self.phase) {
+            (TimerMode::Countdown, _) => self.length,
+            (TimerMode::Pomodoro, Phase::Work) => POMODORO_WORK,
+            (TimerMode::Pomodoro, Phase::Break) => POMODORO_BREAK,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.run, Run::Running { .. })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self.run, Run::Paused { .. })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.run == Run::Finished
+    }
+
+    pub fn left(&self, clock: &dyn Clock) -> Duration {
+        match self.run {
+            Run::Stopped => self.phase_length(),
+            Run::Running { ends } => ends.since(clock.now()).unwrap_or_default(),
+            Run::Paused { left } => left,
+            Run::Finished => Duration::ZERO,
+        }
+    }
+
+    /// How much of the phase is still to go, 0 to 255 and only 0 once there's nothing left.
+    pub fn remaining(&self, clock: &dyn Clock) -> u8 {
+        let length = self.phase_length().as_secs().max(1);
+        match self.left(clock).as_secs().min(length) {
+            0 => 0,
+            left => (left * 254 / length + 1) as u8,
+        }
+    }
+
+    /// Starts from the top or carries on after a pause.
+    pub fn start(&mut self, clock: &dyn Clock) {
+        let now = clock.now();
+
+        match self.run {
+            Run::Stopped | Run::Finished => {
+                self.phase = Phase::Work;
+                self.run = Run::Running {
+                    ends: now.add(self.phase_length()),
+                };
+            }
+            Run::Paused { left } => {
+                self.run = Run::Running {
+                    ends: now.add(left),
+                }
+            }
+            Run::Running { .. } => {}
+        }
+    }
+
+    pub fn pause(&mut self, clock: &dyn Clock) {
+        if self.is_running() {
+            self.run = Run::Paused {
+                left: self.left(clock),
+            };
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.phase = Phase::Work;
+        self.run = Run::Stopped;
+        self.pomodoros = 0;
+    }
+
+    /// Checks the clock, reporting the end of a countdown or phase once.
+    pub fn update(&mut self, clock: &dyn Clock) -> Option<TimerEvent> {
+        let Run::Running { ends } = self.run else {
+            return None;
+        };
+        if clock.now() < ends {
+            return None;
+        }
+
+        match self.mode {
+            TimerMode::Countdown => {
+                self.run = Run::Finished;
+                Some(TimerEvent::Finished)
+            }
+            TimerMode::Pomodoro => {
+                self.phase = match self.phase {
+                    Phase::Work => {
+                        self.pomodoros += 1;
+                        Phase::Break
+                    }
+                    Phase::Break => Phase::Work,
+                };
+                // timed from when the last phase should have ended so a late update doesn't drift
+                self.run = Run::Running {
+                    ends: ends.add(self.phase_length()),
+                };
+                Some(TimerEvent::Phase(self.phase))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    /// Updates once a second for `duration`, collecting what the timer reported.
+    fn run(timer: &mut Timer, clock: &ManualClock, duration: Duration) -> Vec<TimerEvent> {
+        (0..duration.as_secs())
+            .filter_map(|_| {
+                clock.advance(Duration::from_secs(1));
+                timer.update(clock)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn countdown_finishes_once() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::new(3 * MINUTE);
+        assert_eq!(timer.left(&clock), 3 * MINUTE);
+        assert_eq!(timer.remaining(&clock), 255);
+
+        timer.start(&clock);
+        assert!(run(&mut timer, &clock, 3 * MINUTE - Duration::from_secs(1)).is_empty());
+        assert_eq!(timer.left(&clock), Duration::from_secs(1));
+        assert!(timer.remaining(&clock) > 0);
+
+        assert_eq!(run(&mut timer, &clock, MINUTE), vec![TimerEvent::Finished]);
+        assert!(timer.is_finished());
+        assert_eq!(timer.remaining(&clock), 0);
+    }
+
+    #[test]
+    fn pausing_stops_the_countdown() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::new(2 * MINUTE);
+        timer.start(&clock);
+        run(&mut timer, &clock, MINUTE);
+
+        timer.pause(&clock);
+        assert!(timer.is_paused());
+        assert!(run(&mut timer, &clock, 10 * MINUTE).is_empty());
+        assert_eq!(timer.left(&clock), MINUTE);
+
+        timer.start(&clock);
+        assert_eq!(run(&mut timer, &clock, MINUTE), vec![TimerEvent::Finished]);
+    }
+
+    #[test]
+    fn a_late_update_still_finishes() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::new(MINUTE);
+        timer.start(&clock);
+        clock.advance(MAX_LENGTH);
+        assert_eq!(timer.update(&clock), Some(TimerEvent::Finished));
+        assert_eq!(timer.update(&clock), None);
+    }
+
+    #[test]
+    fn length_is_whole_minutes_within_bounds_and_fixed_while_running() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::new(Duration::ZERO);
+        assert_eq!(timer.length(), MINUTE);
+        timer.adjust_length(-5);
+        assert_eq!(timer.length(), MINUTE);
+        timer.adjust_length(500);
+        assert_eq!(timer.length(), MAX_LENGTH);
+
+        timer.adjust_length(-97);
+        timer.start(&clock);
+        timer.adjust_length(10);
+        assert_eq!(timer.length(), 2 * MINUTE);
+
+        timer.reset();
+        timer.adjust_length(10);
+        assert_eq!(timer.length(), 12 * MINUTE);
+    }
+
+    #[test]
+    fn pomodoro_alternates_work_and_breaks() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::default();
+        timer.set_mode(TimerMode::Pomodoro);
+        timer.start(&clock);
+        assert_eq!(timer.phase(), Phase::Work);
+        assert_eq!(timer.left(&clock), POMODORO_WORK);
+
+        let cycle = POMODORO_WORK + POMODORO_BREAK;
+        assert_eq!(
+            run(&mut timer, &clock, 2 * cycle),
+            vec![
+                TimerEvent::Phase(Phase::Break),
+                TimerEvent::Phase(Phase::Work),
+                TimerEvent::Phase(Phase::Break),
+                TimerEvent::Phase(Phase::Work),
+            ]
+        );
+        assert_eq!(timer.pomodoros(), 2);
+        assert_eq!(timer.phase(), Phase::Work);
+        assert_eq!(timer.left(&clock), POMODORO_WORK);
+        assert!(!timer.is_finished());
+
+        timer.reset();
+        assert_eq!(timer.pomodoros(), 0);
+        assert!(!timer.is_running());
+    }
+
+    #[test]
+    fn pomodoro_phases_dont_drift_with_late_updates() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::default();
+        timer.set_mode(TimerMode::Pomodoro);
+        timer.start(&clock);
+
+        clock.advance(POMODORO_WORK + MINUTE);
+        assert_eq!(timer.update(&clock), Some(TimerEvent::Phase(Phase::Break)));
+        assert_eq!(timer.left(&clock), POMODORO_BREAK - MINUTE);
+        assert_eq!(timer.phase_length(), POMODORO_BREAK);
+    }
+
+    #[test]
+    fn switching_mode_resets() {
+        let clock = ManualClock::new(Timestamp(1000));
+        let mut timer = Timer::default();
+        timer.set_mode(TimerMode::Pomodoro);
+        timer.start(&clock);
+        run(&mut timer, &clock, POMODORO_WORK);
+        assert_eq!(timer.pomodoros(), 1);
+
+        timer.set_mode(TimerMode::Countdown);
+        assert!(!timer.is_running());
+        assert_eq!(timer.pomodoros(), 0);
+        assert_eq!(timer.left(&clock), timer.length());
+    }
+}
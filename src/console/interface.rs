@@ -0,0 +1,75 @@
+use crate::console::{write_result, Console};
+use crate::supervisor::{Heartbeat, Task, TaskSpec};
+use esp_idf_svc::hal::delay::FreeRtos;
+use std::io::{ErrorKind, Read, Write};
+use std::time::Duration;
+
+const PROMPT: &str = "> ";
+const MAX_LINE: usize = 128;
+
+/// Reads characters from the console port, echoing them back and running each finished line.
+///
+/// Stdin on the ESP32 doesn't block, so an empty read just waits and tries again.
+fn console_task(console: &mut Console, heartbeat: &Heartbeat) -> ! {
+    let mut stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    let mut line = String::new();
+    let mut buf = [0u8; 16];
+    let mut last_byte = 0u8;
+
+    let _ = write!(stdout, "{}", PROMPT);
+    let _ = stdout.flush();
+
+    loop {
+        heartbeat.beat();
+
+        let len = match stdin.read(&mut buf) {
+            Ok(0) => 0,
+            Ok(len) => len,
+            Err(err) if err.kind() == ErrorKind::WouldBlock => 0,
+            Err(err) => {
+                log::error!("Failed to read console, {}", err);
+                0
+            }
+        };
+
+        if len == 0 {
+            FreeRtos::delay_ms(20);
+            continue;
+        }
+
+        for byte in &buf[..len] {
+            match byte {
+                // terminals send either or both
+                b'\n' if last_byte == b'\r' => {}
+                b'\r' | b'\n' => {
+                    let _ = writeln!(stdout);
+                    let result = console.execute(&line);
+                    let _ = write_result(&mut stdout, &result);
+                    line.clear();
+                    let _ = write!(stdout, "{}", PROMPT);
+                }
+                // backspace and delete
+                0x08 | 0x7f => {
+                    if line.pop().is_some() {
+                        let _ = write!(stdout, "\x08 \x08");
+                    }
+                }
+                byte if byte.is_ascii() && !byte.is_ascii_control() && line.len() < MAX_LINE => {
+                    line.push(*byte as char);
+                    let _ = write!(stdout, "{}", *byte as char);
+                }
+                _ => {}
+            }
+            last_byte = *byte;
+        }
+        let _ = stdout.flush();
+    }
+}
+
+pub fn console_init(mut console: Console) -> Task {
+    Task::new(
+        TaskSpec::new("console_task", 32 * 200).stall_timeout(Duration::from_secs(2)),
+        move |heartbeat| console_task(&mut console, heartbeat),
+    )
+}
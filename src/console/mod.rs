@@ -0,0 +1,391 @@
+//! Line based debug console.
+//!
+//! Commands are registered by the subsystems they belong to as space separated paths such as
+//! `pet set`, the handler gets the remaining words. Nothing here knows about the serial port so
+//! a console can be driven from any reader, see [`interface`] for the device side.
+
+#[cfg(target_os = "espidf")]
+pub mod interface;
+
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub type CommandResult = Result<String, String>;
+pub type Handler = Box<dyn FnMut(&[&str]) -> CommandResult + Send>;
+
+struct Command {
+    path: Vec<&'static str>,
+    usage: &'static str,
+    help: &'static str,
+    handler: Handler,
+}
+
+#[derive(Default)]
+pub struct Console {
+    commands: Vec<Command>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a command, `usage` describes the arguments following `path`.
+    pub fn register<F>(
+        &mut self,
+        path: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        handler: F,
+    ) where
+        F: FnMut(&[&str]) -> CommandResult + Send + 'static,
+    {
+        self.commands.push(Command {
+            path: path.split_whitespace().collect(),
+            usage,
+            help,
+            handler: Box::new(handler),
+        });
+    }
+
+    pub fn execute(&mut self, line: &str) -> CommandResult {
+        let words = tokenize(line);
+
+        match words.first().map(String::as_str) {
+            None => return Ok(String::new()),
+            Some("help") => return Ok(self.help(&words[1..])),
+            _ => {}
+        }
+
+        let command = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter(|(_, command)| starts_with(&words, &command.path))
+            .max_by_key(|(_, command)| command.path.len())
+            .map(|(idx, _)| idx);
+
+        match command {
+            Some(idx) => {
+                let command = &mut self.commands[idx];
+                let args: Vec<&str> = words[command.path.len()..]
+                    .iter()
+                    .map(String::as_str)
+                    .collect();
+                (command.handler)(&args)
+            }
+            None if self
+                .commands
+                .iter()
+                .any(|command| command.path[0] == words[0]) =>
+            {
+                Err(self.help(&words[..1]))
+            }
+            None => Err(format!("Unknown command {}, try help", words[0])),
+        }
+    }
+
+    /// Lists the commands starting with `prefix`, or all of them.
+    pub fn help(&self, prefix: &[String]) -> String {
+        let mut lines: Vec<String> = self
+            .commands
+            .iter()
+            .filter(|command| starts_with_path(&command.path, prefix))
+            .map(|command| {
+                let usage = format!("{} {}", command.path.join(" "), command.usage);
+                format!("{:<28} {}", usage.trim_end(), command.help)
+            })
+            .collect();
+        lines.sort();
+
+        lines.join("\n")
+    }
+
+    /// Runs commands read from `input` until it ends, writing the results to `output`.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> std::io::Result<()> {
+        for line in input.lines() {
+            let result = self.execute(&line?);
+            write_result(&mut output, &result)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn write_result<W: Write>(output: &mut W, result: &CommandResult) -> std::io::Result<()> {
+    match result {
+        Ok(text) if text.is_empty() => {}
+        Ok(text) => writeln!(output, "{}", text)?,
+        Err(err) => writeln!(output, "error: {}", err)?,
+    }
+    output.flush()
+}
+
+fn starts_with(words: &[String], path: &[&str]) -> bool {
+    words.len() >= path.len() && path.iter().zip(words).all(|(part, word)| part == word)
+}
+
+fn starts_with_path(path: &[&str], prefix: &[String]) -> bool {
+    prefix.len() <= path.len() && prefix.iter().zip(path).all(|(word, part)| part == word)
+}
+
+/// Splits on whitespace, double quotes group words together.
+pub fn tokenize(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+
+    for ch in line.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            ch => {
+                word.push(ch);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Parses the argument at `idx`, naming it in the error.
+pub fn arg<T: FromStr>(args: &[&str], idx: usize, name: &str) -> Result<T, String> {
+    let value = args.get(idx).ok_or(format!("Missing {}", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {} {}", name, value))
+}
+
+/// Parses durations like `90s`, `15m`, `3h`, `2d` or combinations such as `1h30m`, a bare number
+/// is seconds.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let mut total = 0u64;
+    let mut number = String::new();
+
+    for ch in text.chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+
+        let unit = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            _ => return None,
+        };
+        total += number.parse::<u64>().ok()? * unit;
+        number.clear();
+    }
+
+    if !number.is_empty() {
+        total += number.parse::<u64>().ok()?;
+    }
+
+    if text.is_empty() {
+        return None;
+    }
+
+    Some(Duration::from_secs(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|word| word.to_string()).collect()
+    }
+
+    /// Console with `pet`, `pet set` and `led` commands, each recording the arguments it got.
+    fn console() -> (Console, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut console = Console::new();
+
+        for (path, usage) in [
+            ("pet", ""),
+            ("pet set", "<stat> <value>"),
+            ("led", "<on|off>"),
+        ] {
+            let calls = calls.clone();
+            console.register(path, usage, "test command", move |args| {
+                calls
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}: {}", path, args.join(",")));
+                Ok(format!("ran {}", path))
+            });
+        }
+
+        (console, calls)
+    }
+
+    #[test]
+    fn tokenize_splits_on_whitespace() {
+        assert_eq!(
+            tokenize("pet set  hunger\t3 "),
+            words(&["pet", "set", "hunger", "3"])
+        );
+        assert!(tokenize("").is_empty());
+        assert!(tokenize("   ").is_empty());
+    }
+
+    #[test]
+    fn tokenize_groups_quoted_words() {
+        assert_eq!(
+            tokenize(r#"pet name "Mister Blob""#),
+            words(&["pet", "name", "Mister Blob"])
+        );
+        assert_eq!(tokenize(r#"a"b c"d"#), words(&["ab cd"]));
+        assert_eq!(tokenize(r#"name """#), words(&["name", ""]));
+        // an unterminated quote runs to the end of the line
+        assert_eq!(
+            tokenize(r#"say "hello  there"#),
+            words(&["say", "hello  there"])
+        );
+    }
+
+    #[test]
+    fn arg_names_what_is_wrong() {
+        let args = ["3", "lots"];
+        assert_eq!(arg::<u8>(&args, 0, "value"), Ok(3));
+        assert_eq!(
+            arg::<u8>(&args, 1, "count"),
+            Err(String::from("Invalid count lots"))
+        );
+        assert_eq!(
+            arg::<u8>(&args, 2, "stat"),
+            Err(String::from("Missing stat"))
+        );
+    }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("15m"), Some(Duration::from_secs(15 * 60)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(90 * 60)));
+        assert_eq!(
+            parse_duration("2d5"),
+            Some(Duration::from_secs(2 * 24 * 60 * 60 + 5))
+        );
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("m"), None);
+    }
+
+    #[test]
+    fn dispatches_to_the_longest_matching_path() {
+        let (mut console, calls) = console();
+
+        assert_eq!(console.execute("pet"), Ok(String::from("ran pet")));
+        assert_eq!(
+            console.execute(r#"pet set name "Mister Blob""#),
+            Ok(String::from("ran pet set"))
+        );
+        assert_eq!(console.execute("pet feed"), Ok(String::from("ran pet")));
+        assert_eq!(console.execute("led on"), Ok(String::from("ran led")));
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                String::from("pet: "),
+                String::from("pet set: name,Mister Blob"),
+                String::from("pet: feed"),
+                String::from("led: on"),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_lines_do_nothing() {
+        let (mut console, calls) = console();
+        assert_eq!(console.execute(""), Ok(String::new()));
+        assert_eq!(console.execute("  "), Ok(String::new()));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unknown_commands_are_errors() {
+        let (mut console, calls) = console();
+        assert_eq!(
+            console.execute("reboot now"),
+            Err(String::from("Unknown command reboot, try help"))
+        );
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unknown_subcommands_list_the_group() {
+        let mut console = Console::new();
+        console.register(
+            "clock set",
+            "<hh:mm>",
+            "set the time",
+            |_| Ok(String::new()),
+        );
+        console.register("clock show", "", "print the time", |_| Ok(String::new()));
+        console.register("led", "<on|off>", "switch the strip", |_| Ok(String::new()));
+
+        let err = console.execute("clock").unwrap_err();
+        assert_eq!(err, console.help(&words(&["clock"])));
+        assert!(err.contains("clock set <hh:mm>"));
+        assert!(err.contains("clock show"));
+        assert!(!err.contains("led"));
+    }
+
+    #[test]
+    fn help_lists_commands_sorted() {
+        let (mut console, _) = console();
+
+        let help = console.execute("help").unwrap();
+        let paths: Vec<&str> = help
+            .lines()
+            .map(|line| line.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(paths, vec!["led", "pet", "pet"]);
+        assert!(help.contains("pet set <stat> <value>"));
+
+        let help = console.execute("help pet set").unwrap();
+        assert_eq!(help.lines().count(), 1);
+        assert!(help.starts_with("pet set <stat> <value>"));
+    }
+
+    #[test]
+    fn handler_errors_are_passed_on() {
+        let mut console = Console::new();
+        console.register("fail", "", "always fails", |_| Err(String::from("nope")));
+        assert_eq!(console.execute("fail"), Err(String::from("nope")));
+    }
+
+    #[test]
+    fn run_writes_results_and_errors() {
+        let (mut console, _) = console();
+        let mut output = vec![];
+
+        console
+            .run("pet\n\nbogus\nled off\n".as_bytes(), &mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "ran pet\nerror: Unknown command bogus, try help\nran led\n"
+        );
+    }
+}
@@ -1,5 +1,29 @@
+#[cfg(target_os = "espidf")]
+pub mod power;
+pub mod power_plan;
+
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::hal::gpio::{Level, Output, Pin, PinDriver};
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::sys::EspError;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static START: Lazy<Instant> = Lazy::new(Instant::now);
+static LAST_ACTIVITY_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Records user input, called by the input drivers so the power manager knows when to sleep.
+pub fn mark_activity() {
+    LAST_ACTIVITY_MS.store(START.elapsed().as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Time since the last input.
+pub fn idle_for() -> Duration {
+    START.elapsed().saturating_sub(Duration::from_millis(
+        LAST_ACTIVITY_MS.load(Ordering::Relaxed),
+    ))
+}
 
 #[derive(Copy, Clone, PartialEq)]
 pub enum State {
@@ -7,6 +31,7 @@ pub enum State {
     On,
 }
 
+#[cfg(target_os = "espidf")]
 pub trait PowerToggle {
     fn get_state(&self) -> State;
     fn wake(&mut self) -> Result<(), EspError>;
@@ -22,11 +47,13 @@ pub trait PowerToggle {
     }
 }
 
+#[cfg(target_os = "espidf")]
 pub struct DevicePowerState<'d, P1: Pin> {
     peripheral_power: State,
     peripheral_power_pin: PinDriver<'d, P1, Output>,
 }
 
+#[cfg(target_os = "espidf")]
 impl<'d, P1: Pin> DevicePowerState<'d, P1> {
     pub fn new(mut peripheral_power_pin: PinDriver<'d, P1, Output>) -> Result<Self, EspError> {
         peripheral_power_pin.set_level(Level::Low)?;
@@ -38,6 +65,7 @@ impl<'d, P1: Pin> DevicePowerState<'d, P1> {
     }
 }
 
+#[cfg(target_os = "espidf")]
 impl<'d, P1: Pin> PowerToggle for DevicePowerState<'d, P1> {
     fn get_state(&self) -> State {
         self.peripheral_power
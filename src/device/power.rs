@@ -0,0 +1,184 @@
+//! Moves the device between power modes, switching everything off in order on the way down and
+//! back on in reverse on the way up.
+//!
+//! The steps come from [`plan`], [`PowerManager`] carries them out.
+
+use crate::apa102::interface::LEDInterface;
+use crate::device::power_plan::{plan, PowerAction, PowerMode, PowerNotice};
+use crate::device::{self, DevicePowerState, PowerToggle};
+use crate::tft::{self, DisplayPower};
+use esp_idf_svc::hal::delay::FreeRtos;
+use esp_idf_svc::hal::gpio::Pin;
+use esp_idf_svc::sys::{self, esp, EspError};
+use std::time::Duration;
+
+const DISPLAY_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Pins and timer that bring the device back out of sleep.
+#[derive(Clone, PartialEq, Debug)]
+pub struct WakeSources {
+    /// Active low button, pulled up.
+    pub button: i32,
+    /// Encoder pins, these wake on moving away from the level they rest at.
+    pub encoder: Vec<i32>,
+    /// Light sleep also wakes after this long so the pet can be ticked, the screen stays off.
+    pub timer: Option<Duration>,
+}
+
+pub type PowerSubscriber = Box<dyn FnMut(PowerNotice) + Send>;
+
+pub struct PowerManager<'d, P: Pin> {
+    rail: DevicePowerState<'d, P>,
+    mode: PowerMode,
+    wake: WakeSources,
+    subscribers: Vec<PowerSubscriber>,
+}
+
+impl<'d, P: Pin> PowerManager<'d, P> {
+    /// Takes over the peripheral rail, which must already be on.
+    pub fn new(rail: DevicePowerState<'d, P>, wake: WakeSources) -> Self {
+        Self {
+            rail,
+            mode: PowerMode::Active,
+            wake,
+            subscribers: vec![],
+        }
+    }
+
+    pub fn mode(&self) -> PowerMode {
+        self.mode
+    }
+
+    pub fn subscribe(&mut self, subscriber: PowerSubscriber) {
+        self.subscribers.push(subscriber);
+    }
+
+    pub fn set_wake_timer(&mut self, timer: Option<Duration>) {
+        self.wake.timer = timer;
+    }
+
+    /// Moves to `to`, for light sleep this returns once the device has woken back up.
+    pub fn set_mode(&mut self, to: PowerMode) -> Result<(), EspError> {
+        let from = self.mode;
+
+        for action in plan(from, to) {
+            self.apply(action)?;
+        }
+        self.mode = to;
+
+        if to == PowerMode::LightSleep {
+            // woken by the timer only to tick the pet, keep the screen off
+            let cause = unsafe { sys::esp_sleep_get_wakeup_cause() };
+            let woke_to = if cause == sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_TIMER {
+                PowerMode::ScreenOff
+            } else {
+                // the edge that woke us may have been missed by the input drivers
+                device::mark_activity();
+                PowerMode::Active
+            };
+            self.set_mode(woke_to)?;
+        }
+
+        Ok(())
+    }
+
+    fn apply(&mut self, action: PowerAction) -> Result<(), EspError> {
+        match action {
+            PowerAction::Notify(notice) => {
+                for subscriber in self.subscribers.iter_mut() {
+                    subscriber(notice);
+                }
+            }
+            PowerAction::DisplayOn => Self::set_display(DisplayPower::On),
+            PowerAction::DisplayOff => Self::set_display(DisplayPower::Off),
+            PowerAction::LedsOn => LEDInterface::set_blanked(false),
+            PowerAction::LedsOff => {
+                LEDInterface::set_blanked(true);
+                // give the led task time to push out the blank frame before anything stops
+                FreeRtos::delay_ms(10);
+            }
+            PowerAction::RailOn => self.rail.wake()?,
+            PowerAction::RailOff => self.rail.sleep()?,
+            PowerAction::EnterLightSleep => self.light_sleep()?,
+            PowerAction::EnterDeepSleep => self.deep_sleep()?,
+        }
+
+        Ok(())
+    }
+
+    fn set_display(power: DisplayPower) {
+        tft::set_display_power(power);
+
+        if !tft::wait_for_display_power(power, DISPLAY_TIMEOUT) {
+            log::warn!("Display didn't switch power in time");
+        }
+    }
+
+    fn light_sleep(&mut self) -> Result<(), EspError> {
+        let pins = self.wake_pins();
+
+        unsafe {
+            esp!(sys::esp_sleep_disable_wakeup_source(
+                sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_ALL
+            ))?;
+
+            for (pin, level) in &pins {
+                let intr_type = if *level == 0 {
+                    sys::gpio_int_type_t_GPIO_INTR_HIGH_LEVEL
+                } else {
+                    sys::gpio_int_type_t_GPIO_INTR_LOW_LEVEL
+                };
+                esp!(sys::gpio_wakeup_enable(*pin, intr_type))?;
+            }
+            esp!(sys::esp_sleep_enable_gpio_wakeup())?;
+
+            if let Some(timer) = self.wake.timer {
+                esp!(sys::esp_sleep_enable_timer_wakeup(timer.as_micros() as u64))?;
+            }
+
+            esp!(sys::esp_light_sleep_start())?;
+
+            // wake up leaves the pins on level interrupts, put back what the drivers expect
+            for (pin, _) in &pins {
+                esp!(sys::gpio_wakeup_disable(*pin))?;
+                esp!(sys::gpio_set_intr_type(
+                    *pin,
+                    sys::gpio_int_type_t_GPIO_INTR_ANYEDGE
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn deep_sleep(&mut self) -> Result<(), EspError> {
+        // ext1 can only wake on any pin going low, so skip encoder pins resting low
+        let mask = self
+            .wake_pins()
+            .iter()
+            .filter(|(_, level)| *level != 0)
+            .fold(0u64, |mask, (pin, _)| mask | 1 << pin);
+
+        unsafe {
+            esp!(sys::esp_sleep_disable_wakeup_source(
+                sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_ALL
+            ))?;
+            esp!(sys::rtc_gpio_pullup_en(self.wake.button))?;
+            esp!(sys::rtc_gpio_pulldown_dis(self.wake.button))?;
+            esp!(sys::esp_sleep_enable_ext1_wakeup(
+                mask,
+                sys::esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_LOW
+            ))?;
+
+            sys::esp_deep_sleep_start()
+        }
+    }
+
+    /// Wake pins along with the level they currently read.
+    fn wake_pins(&self) -> Vec<(i32, i32)> {
+        std::iter::once(self.wake.button)
+            .chain(self.wake.encoder.iter().copied())
+            .map(|pin| (pin, unsafe { sys::gpio_get_level(pin) }))
+            .collect()
+    }
+}
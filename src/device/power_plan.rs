@@ -0,0 +1,225 @@
+//! Which steps move the device between power modes and which mode it should be in after a while
+//! without input. Kept apart from [`PowerManager`](super::power::PowerManager) so the ordering can
+//! be checked without the hardware.
+
+use std::time::Duration;
+
+/// Ordered from most to least awake.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum PowerMode {
+    Active,
+    /// Display and LEDs off, the pet keeps running.
+    ScreenOff,
+    /// SoC paused until a wake source fires, RAM is kept.
+    LightSleep,
+    /// Everything off, waking up restarts the firmware.
+    DeepSleep,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PowerNotice {
+    /// About to move to the mode, this is the last chance to save anything.
+    Before(PowerMode),
+    /// The mode is now in effect. Not sent for the sleep modes, waking up sends its own notices.
+    After(PowerMode),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PowerAction {
+    Notify(PowerNotice),
+    DisplayOn,
+    DisplayOff,
+    LedsOn,
+    LedsOff,
+    RailOn,
+    RailOff,
+    EnterLightSleep,
+    EnterDeepSleep,
+}
+
+/// Steps needed to get from `from` to `to`.
+pub fn plan(from: PowerMode, to: PowerMode) -> Vec<PowerAction> {
+    if from == to {
+        return vec![];
+    }
+
+    let mut actions = vec![PowerAction::Notify(PowerNotice::Before(to))];
+
+    if to > from {
+        if from == PowerMode::Active {
+            actions.push(PowerAction::LedsOff);
+            actions.push(PowerAction::DisplayOff);
+        }
+
+        match to {
+            PowerMode::Active => unreachable!(),
+            PowerMode::ScreenOff => actions.push(PowerAction::Notify(PowerNotice::After(to))),
+            PowerMode::LightSleep => actions.push(PowerAction::EnterLightSleep),
+            PowerMode::DeepSleep => {
+                actions.push(PowerAction::RailOff);
+                actions.push(PowerAction::EnterDeepSleep);
+            }
+        }
+    } else {
+        if from == PowerMode::DeepSleep {
+            actions.push(PowerAction::RailOn);
+        }
+
+        if to == PowerMode::Active {
+            actions.push(PowerAction::DisplayOn);
+            actions.push(PowerAction::LedsOn);
+        }
+
+        actions.push(PowerAction::Notify(PowerNotice::After(to)));
+    }
+
+    actions
+}
+
+/// Picks the power mode from how long there has been no input.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct IdlePolicy {
+    pub screen_off_after: Duration,
+    /// `None` keeps the SoC awake with only the screen off.
+    pub sleep_after: Option<Duration>,
+}
+
+impl Default for IdlePolicy {
+    fn default() -> Self {
+        Self {
+            screen_off_after: Duration::from_secs(30),
+            sleep_after: Some(Duration::from_secs(2 * 60)),
+        }
+    }
+}
+
+impl IdlePolicy {
+    pub fn mode_for(&self, idle: Duration) -> PowerMode {
+        match self.sleep_after {
+            Some(sleep_after) if idle >= sleep_after => PowerMode::LightSleep,
+            _ if idle >= self.screen_off_after => PowerMode::ScreenOff,
+            _ => PowerMode::Active,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PowerAction::*;
+    use PowerMode::*;
+
+    const MODES: [PowerMode; 4] = [Active, ScreenOff, LightSleep, DeepSleep];
+
+    fn before(mode: PowerMode) -> PowerAction {
+        Notify(PowerNotice::Before(mode))
+    }
+
+    fn after(mode: PowerMode) -> PowerAction {
+        Notify(PowerNotice::After(mode))
+    }
+
+    #[test]
+    fn going_down_switches_off_in_order() {
+        let cases = [
+            (
+                Active,
+                ScreenOff,
+                vec![LedsOff, DisplayOff, after(ScreenOff)],
+            ),
+            (
+                Active,
+                LightSleep,
+                vec![LedsOff, DisplayOff, EnterLightSleep],
+            ),
+            (
+                Active,
+                DeepSleep,
+                vec![LedsOff, DisplayOff, RailOff, EnterDeepSleep],
+            ),
+            (ScreenOff, LightSleep, vec![EnterLightSleep]),
+            (ScreenOff, DeepSleep, vec![RailOff, EnterDeepSleep]),
+            (LightSleep, DeepSleep, vec![RailOff, EnterDeepSleep]),
+        ];
+
+        for (from, to, steps) in cases {
+            let mut expected = vec![before(to)];
+            expected.extend(steps);
+            assert_eq!(plan(from, to), expected, "{:?} to {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn coming_up_switches_on_in_reverse() {
+        let cases = [
+            (ScreenOff, Active, vec![DisplayOn, LedsOn]),
+            (LightSleep, Active, vec![DisplayOn, LedsOn]),
+            (LightSleep, ScreenOff, vec![]),
+            (DeepSleep, Active, vec![RailOn, DisplayOn, LedsOn]),
+            (DeepSleep, ScreenOff, vec![RailOn]),
+            (DeepSleep, LightSleep, vec![RailOn]),
+        ];
+
+        for (from, to, steps) in cases {
+            let mut expected = vec![before(to)];
+            expected.extend(steps);
+            expected.push(after(to));
+            assert_eq!(plan(from, to), expected, "{:?} to {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn notices_bracket_every_change() {
+        for from in MODES {
+            for to in MODES {
+                let actions = plan(from, to);
+                if from == to {
+                    assert!(actions.is_empty());
+                    continue;
+                }
+
+                let notices: Vec<_> = actions
+                    .iter()
+                    .filter(|action| matches!(action, Notify(_)))
+                    .collect();
+                assert_eq!(actions.first(), Some(&before(to)));
+                // going to sleep never comes back to send an after
+                match to > from && to >= LightSleep {
+                    true => assert_eq!(notices, [&before(to)]),
+                    false => {
+                        assert_eq!(notices, [&before(to), &after(to)]);
+                        assert_eq!(actions.last(), Some(&after(to)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn idle_policy_thresholds() {
+        let policy = IdlePolicy::default();
+        let secs = Duration::from_secs;
+        assert_eq!(policy.mode_for(Duration::ZERO), Active);
+        assert_eq!(policy.mode_for(secs(30) - Duration::from_millis(1)), Active);
+        assert_eq!(policy.mode_for(secs(30)), ScreenOff);
+        assert_eq!(
+            policy.mode_for(secs(120) - Duration::from_millis(1)),
+            ScreenOff
+        );
+        assert_eq!(policy.mode_for(secs(120)), LightSleep);
+        assert_eq!(policy.mode_for(secs(24 * 60 * 60)), LightSleep);
+
+        let screen_only = IdlePolicy {
+            screen_off_after: secs(15),
+            sleep_after: None,
+        };
+        assert_eq!(screen_only.mode_for(secs(14)), Active);
+        assert_eq!(screen_only.mode_for(secs(24 * 60 * 60)), ScreenOff);
+
+        let never = IdlePolicy {
+            screen_off_after: Duration::MAX,
+            sleep_after: None,
+        };
+        assert_eq!(never.mode_for(secs(24 * 60 * 60)), Active);
+    }
+}
@@ -0,0 +1,227 @@
+use super::{stack_size, Diagnostics, HeapSample, Report, Snapshot, TaskSample};
+use crate::console::Console;
+use crate::supervisor::{Task, TaskSpec};
+use crate::tft::{App, ST7789};
+use crate::ui::diagnostics::DiagnosticsOverlay;
+use crate::ui::{Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use esp_idf_svc::sys;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+const MAX_TASKS: usize = 32;
+const MAX_HEAP_BLOCKS: usize = 64;
+
+static REPORT: Lazy<RwLock<Option<Report>>> = Lazy::new(|| RwLock::new(None));
+static OVERLAY: AtomicBool = AtomicBool::new(false);
+
+pub struct DiagnosticsInterface;
+
+impl DiagnosticsInterface {
+    /// Most recent report from the diagnostics task.
+    pub fn report() -> Option<Report> {
+        match REPORT.read() {
+            Ok(report) => report.clone(),
+            Err(err) => {
+                log::error!("Failed to gain diagnostics read lock, {}", err);
+                None
+            }
+        }
+    }
+
+    pub fn set_overlay(shown: bool) {
+        OVERLAY.store(shown, Ordering::Relaxed);
+    }
+
+    pub fn overlay() -> bool {
+        OVERLAY.load(Ordering::Relaxed)
+    }
+}
+
+/// Draws the diagnostics overlay over the bottom of another app while it is switched on.
+pub struct OverlayApp<A: App> {
+    app: A,
+    overlay: Option<DiagnosticsOverlay>,
+    theme: Theme,
+}
+
+impl<A: App> OverlayApp<A> {
+    pub fn new(app: A) -> Self {
+        Self {
+            app,
+            overlay: None,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl<A: App> App for OverlayApp<A> {
+    fn update(&mut self, display: &mut ST7789) {
+        self.app.update(display);
+
+        if !DiagnosticsInterface::overlay() {
+            if let Some(overlay) = self.overlay.take() {
+                // leave nothing behind for the app to draw around
+                let _ = display.fill_solid(&overlay.bounds(), self.theme.background);
+            }
+            return;
+        }
+
+        let overlay = self.overlay.get_or_insert_with(|| {
+            let screen = display.bounding_box();
+            let height = screen.size.height / 2;
+            DiagnosticsOverlay::new(Rectangle::new(
+                screen.top_left + Point::new(0, (screen.size.height - height) as i32),
+                Size::new(screen.size.width, height),
+            ))
+        });
+        overlay.set_report(DiagnosticsInterface::report());
+        let _ = overlay.redraw(display, &self.theme);
+    }
+}
+
+pub fn sample() -> Snapshot {
+    let heap = sample_task_heap();
+
+    let mut status: Vec<sys::TaskStatus_t> = Vec::with_capacity(MAX_TASKS);
+    let mut total_runtime = 0;
+    let count = unsafe {
+        sys::uxTaskGetSystemState(status.as_mut_ptr(), MAX_TASKS as _, &mut total_runtime)
+    };
+    unsafe { status.set_len(count as usize) };
+
+    let tasks = status
+        .iter()
+        .map(|task| {
+            let name = unsafe { CStr::from_ptr(task.pcTaskName) }
+                .to_string_lossy()
+                .into_owned();
+
+            TaskSample {
+                heap: heap.get(&(task.xHandle as usize)).copied(),
+                name,
+                core: u8::try_from(task.xCoreID).ok().filter(|core| *core < 2),
+                priority: task.uxCurrentPriority,
+                stack_free_min: task.usStackHighWaterMark as u32,
+                runtime: task.ulRunTimeCounter,
+            }
+        })
+        .collect();
+
+    let heap = unsafe {
+        HeapSample {
+            free: sys::esp_get_free_heap_size(),
+            min_free: sys::esp_get_minimum_free_heap_size(),
+            free_internal: sys::heap_caps_get_free_size(sys::MALLOC_CAP_INTERNAL) as u32,
+            free_psram: sys::heap_caps_get_free_size(sys::MALLOC_CAP_SPIRAM) as u32,
+        }
+    };
+
+    Snapshot {
+        tasks,
+        total_runtime,
+        heap,
+    }
+}
+
+/// Heap held by each task as (internal, psram) bytes, keyed by task handle.
+fn sample_task_heap() -> HashMap<usize, (u32, u32)> {
+    let mut totals: Vec<sys::heap_task_totals_t> = Vec::with_capacity(MAX_TASKS);
+    let mut blocks: Vec<sys::heap_task_block_t> = Vec::with_capacity(MAX_HEAP_BLOCKS);
+    let mut num_totals = 0;
+
+    let mut params: sys::heap_task_info_params_t = unsafe { core::mem::zeroed() };
+    params.caps[0] = sys::MALLOC_CAP_INTERNAL;
+    params.mask[0] = sys::MALLOC_CAP_INTERNAL;
+    params.caps[1] = sys::MALLOC_CAP_SPIRAM;
+    params.mask[1] = sys::MALLOC_CAP_SPIRAM;
+    params.totals = totals.as_mut_ptr();
+    params.num_totals = &mut num_totals;
+    params.max_totals = MAX_TASKS;
+    // only the totals are wanted, the blocks just need somewhere to go
+    params.blocks = blocks.as_mut_ptr();
+    params.max_blocks = MAX_HEAP_BLOCKS;
+
+    unsafe {
+        sys::heap_caps_get_per_task_info(&mut params);
+        totals.set_len(num_totals);
+    }
+
+    totals
+        .iter()
+        .map(|total| {
+            (
+                total.task as usize,
+                (total.size[0] as u32, total.size[1] as u32),
+            )
+        })
+        .collect()
+}
+
+fn diagnostics_task(interval: Duration) -> ! {
+    let mut diagnostics = Diagnostics::new();
+
+    loop {
+        let report = diagnostics.analyse(sample(), stack_size);
+
+        for task in report.near_overflow() {
+            log::warn!(
+                "{} is close to overflowing its stack, {} bytes never used",
+                task.name,
+                task.stack_free_min
+            );
+        }
+        log::debug!("\n{}", report);
+
+        match REPORT.write() {
+            Ok(mut last) => *last = Some(report),
+            Err(err) => log::error!("Failed to gain diagnostics write lock, {}", err),
+        }
+
+        std::thread::sleep(interval);
+    }
+}
+
+pub fn diagnostics_init(interval: Duration) -> Task {
+    Task::new(TaskSpec::new("diag_task", 32 * 150), move |_| {
+        diagnostics_task(interval)
+    })
+}
+
+pub fn register_commands(console: &mut Console) {
+    console.register("heap", "", "show free heap", |_| {
+        let heap = sample().heap;
+        Ok(format!(
+            "free {} min free {}\ninternal {} psram {}",
+            heap.free, heap.min_free, heap.free_internal, heap.free_psram
+        ))
+    });
+
+    console.register("tasks", "", "show stack, cpu and heap use per task", |_| {
+        match DiagnosticsInterface::report() {
+            Some(report) => Ok(report.to_string()),
+            None => Err(String::from(
+                "No report yet, is the diagnostics task running?",
+            )),
+        }
+    });
+
+    console.register(
+        "tasks overlay",
+        "<on|off>",
+        "show diagnostics on screen",
+        |args| {
+            match args.first().copied() {
+                Some("on") => DiagnosticsInterface::set_overlay(true),
+                Some("off") => DiagnosticsInterface::set_overlay(false),
+                _ => return Err(String::from("Expected on or off")),
+            }
+            Ok(String::new())
+        },
+    );
+}
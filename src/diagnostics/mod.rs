@@ -0,0 +1,219 @@
+//! Stack, heap and CPU usage per task.
+//!
+//! [`interface`] takes snapshots from FreeRTOS and the heap task tracking, [`Diagnostics`] turns
+//! them into a [`Report`] and flags tasks close to running out of stack.
+
+#[cfg(target_os = "espidf")]
+pub mod interface;
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::RwLock;
+
+static STACK_SIZES: Lazy<RwLock<HashMap<String, u32>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Records the stack a task was spawned with, FreeRTOS only reports what is left.
+pub fn register_stack(name: &str, size: u32) {
+    match STACK_SIZES.write() {
+        Ok(mut sizes) => {
+            sizes.insert(name.into(), size);
+        }
+        Err(err) => log::error!("Failed to gain stack sizes write lock, {}", err),
+    }
+}
+
+pub fn stack_size(name: &str) -> Option<u32> {
+    STACK_SIZES.read().ok()?.get(name).copied()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TaskSample {
+    pub name: String,
+    pub core: Option<u8>,
+    pub priority: u32,
+    /// Smallest amount of stack that has ever been free, in bytes.
+    pub stack_free_min: u32,
+    /// Run time counter, wraps.
+    pub runtime: u32,
+    /// Heap currently allocated by the task in (internal, psram) bytes.
+    pub heap: Option<(u32, u32)>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct HeapSample {
+    pub free: u32,
+    pub min_free: u32,
+    pub free_internal: u32,
+    pub free_psram: u32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    pub tasks: Vec<TaskSample>,
+    /// Run time counter all task counters are relative to, wraps.
+    pub total_runtime: u32,
+    pub heap: HeapSample,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TaskReport {
+    pub name: String,
+    pub core: Option<u8>,
+    pub priority: u32,
+    pub stack_size: Option<u32>,
+    pub stack_free_min: u32,
+    /// Share of one core since the previous snapshot.
+    pub cpu_percent: Option<f32>,
+    pub heap: Option<(u32, u32)>,
+    pub near_overflow: bool,
+}
+
+impl TaskReport {
+    pub fn stack_used_percent(&self) -> Option<u32> {
+        self.stack_size
+            .filter(|size| *size > 0)
+            .map(|size| size.saturating_sub(self.stack_free_min) * 100 / size)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Report {
+    pub tasks: Vec<TaskReport>,
+    pub heap: HeapSample,
+}
+
+impl Report {
+    pub fn near_overflow(&self) -> impl Iterator<Item = &TaskReport> {
+        self.tasks.iter().filter(|task| task.near_overflow)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:>4} {:>3} {:>6} {:>6} {:>4} {:>6} {:>7}",
+            "task", "core", "pri", "stack", "free", "used", "cpu", "heap"
+        )?;
+
+        for task in &self.tasks {
+            let core = task.core.map_or(String::from("-"), |core| core.to_string());
+            let size = task
+                .stack_size
+                .map_or(String::from("?"), |size| size.to_string());
+            let used = task
+                .stack_used_percent()
+                .map_or(String::from("?"), |used| format!("{}%", used));
+            let cpu = task
+                .cpu_percent
+                .map_or(String::from("-"), |cpu| format!("{:.1}%", cpu));
+            let heap = task.heap.map_or(String::from("-"), |(internal, psram)| {
+                (internal + psram).to_string()
+            });
+
+            writeln!(
+                f,
+                "{:<16} {:>4} {:>3} {:>6} {:>6} {:>4} {:>6} {:>7}{}",
+                task.name,
+                core,
+                task.priority,
+                size,
+                task.stack_free_min,
+                used,
+                cpu,
+                heap,
+                if task.near_overflow { " !" } else { "" }
+            )?;
+        }
+
+        write!(
+            f,
+            "heap free {} (min {}) internal {} psram {}",
+            self.heap.free, self.heap.min_free, self.heap.free_internal, self.heap.free_psram
+        )
+    }
+}
+
+pub struct Diagnostics {
+    /// Warn once less than this many bytes of stack have ever been free.
+    pub warn_free_bytes: u32,
+    /// Warn once more than this share of a known stack size has been used.
+    pub warn_used_percent: u32,
+
+    last_runtime: HashMap<String, u32>,
+    last_total: Option<u32>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self {
+            warn_free_bytes: 512,
+            warn_used_percent: 85,
+            last_runtime: HashMap::new(),
+            last_total: None,
+        }
+    }
+
+    pub fn analyse(
+        &mut self,
+        snapshot: Snapshot,
+        stack_size: impl Fn(&str) -> Option<u32>,
+    ) -> Report {
+        let total_delta = self
+            .last_total
+            .map(|last| snapshot.total_runtime.wrapping_sub(last))
+            .filter(|delta| *delta > 0);
+
+        let mut tasks: Vec<TaskReport> = snapshot
+            .tasks
+            .iter()
+            .map(|task| {
+                let cpu_percent = match (total_delta, self.last_runtime.get(&task.name)) {
+                    // the total is wall time, so this is the share of the core the task ran on
+                    (Some(total), Some(last)) => {
+                        let delta = task.runtime.wrapping_sub(*last);
+                        Some(delta as f32 * 100.0 / total as f32)
+                    }
+                    _ => None,
+                };
+
+                let mut report = TaskReport {
+                    name: task.name.clone(),
+                    core: task.core,
+                    priority: task.priority,
+                    stack_size: stack_size(&task.name),
+                    stack_free_min: task.stack_free_min,
+                    cpu_percent,
+                    heap: task.heap,
+                    near_overflow: false,
+                };
+                report.near_overflow = task.stack_free_min < self.warn_free_bytes
+                    || report
+                        .stack_used_percent()
+                        .is_some_and(|used| used >= self.warn_used_percent);
+
+                report
+            })
+            .collect();
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.last_runtime = snapshot
+            .tasks
+            .iter()
+            .map(|task| (task.name.clone(), task.runtime))
+            .collect();
+        self.last_total = Some(snapshot.total_runtime);
+
+        Report {
+            tasks,
+            heap: snapshot.heap,
+        }
+    }
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,411 @@
+//! The pet's stats sampled on a fixed interval, the last week of them kept for the charts.
+
+use crate::i18n::{self, Key};
+use crate::pet::{Stats, STAT_MAX};
+use std::time::Duration;
+
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// A week of samples.
+pub const CAPACITY: usize = 7 * 24 * 2;
+
+/// Fixed size buffer that drops the oldest item to make room for a new one.
+#[derive(Clone, Debug)]
+pub struct RingBuffer<T> {
+    items: Vec<T>,
+    capacity: usize,
+    /// Index of the oldest item once the buffer is full.
+    head: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            capacity: capacity.max(1),
+            head: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+        } else {
+            self.items[self.head] = item;
+            self.head = (self.head + 1) % self.capacity;
+        }
+    }
+
+    /// Oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        let (newer, older) = self.items.split_at(self.head);
+        older.iter().chain(newer.iter())
+    }
+
+    /// Item `ago` places back from the newest.
+    pub fn get_back(&self, ago: usize) -> Option<&T> {
+        if ago >= self.items.len() {
+            return None;
+        }
+
+        // the newest item sits just before the head, which is 0 until the buffer is full
+        let len = self.items.len();
+        self.items.get((self.head + len - 1 - ago) % len)
+    }
+}
+
+/// Equal when they hold the same items in the same order, wherever the head is.
+impl<T: PartialEq> PartialEq for RingBuffer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.capacity == other.capacity && self.iter().eq(other.iter())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Sample {
+    pub hunger: u8,
+    pub happiness: u8,
+    pub health: u8,
+    pub weight: u16,
+}
+
+impl From<&Stats> for Sample {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            hunger: stats.hunger,
+            happiness: stats.happiness,
+            health: stats.health,
+            weight: stats.weight,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Stat {
+    Hunger,
+    Happiness,
+    Health,
+    Weight,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 4] = [Stat::Hunger, Stat::Happiness, Stat::Health, Stat::Weight];
+
+    pub fn name(&self) -> &'static str {
+        i18n::text(match self {
+            Stat::Hunger => Key::StatHunger,
+            Stat::Happiness => Key::StatHappiness,
+            Stat::Health => Key::StatHealth,
+            Stat::Weight => Key::StatWeight,
+        })
+    }
+
+    pub fn get(&self, sample: &Sample) -> u16 {
+        match self {
+            Stat::Hunger => sample.hunger as u16,
+            Stat::Happiness => sample.happiness as u16,
+            Stat::Health => sample.health as u16,
+            Stat::Weight => sample.weight,
+        }
+    }
+
+    /// Top of the chart, weight has no fixed limit so it's scaled to the heaviest sample.
+    pub fn max<'a>(&self, samples: impl Iterator<Item = &'a Option<Sample>>) -> u16 {
+        match self {
+            Stat::Weight => samples
+                .flatten()
+                .map(|sample| sample.weight)
+                .max()
+                .unwrap_or(1)
+                .max(1),
+            _ => STAT_MAX as u16,
+        }
+    }
+}
+
+/// Averages `samples` into `buckets` evenly sized groups, oldest first. A group with no samples
+/// in it, e.g. while the device was off, is `None`.
+pub fn downsample(samples: &[Option<Sample>], buckets: usize, stat: Stat) -> Vec<Option<u16>> {
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * samples.len() / buckets;
+            let end = (bucket + 1) * samples.len() / buckets;
+            let values: Vec<u32> = samples[start..end]
+                .iter()
+                .flatten()
+                .map(|sample| stat.get(sample) as u32)
+                .collect();
+
+            match values.len() {
+                0 => None,
+                len => Some((values.iter().sum::<u32>() / len as u32) as u16),
+            }
+        })
+        .collect()
+}
+
+/// Samples every [`SAMPLE_INTERVAL`], gaps while the device was off are kept as `None` so the
+/// charts stay to scale.
+#[derive(Clone, PartialEq, Debug)]
+pub struct History {
+    pub samples: RingBuffer<Option<Sample>>,
+    /// Time since the last sample.
+    pub(crate) since_last: Duration,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            samples: RingBuffer::new(CAPACITY),
+            since_last: Duration::ZERO,
+        }
+    }
+}
+
+impl History {
+    /// Moves on by `elapsed`, returning true if a sample was taken.
+    pub fn record(&mut self, elapsed: Duration, stats: &Stats) -> bool {
+        self.since_last += elapsed;
+        if self.since_last < SAMPLE_INTERVAL {
+            return false;
+        }
+
+        // whole intervals past the first were missed
+        self.since_last -= SAMPLE_INTERVAL;
+        self.skip(Duration::ZERO);
+        self.samples.push(Some(Sample::from(stats)));
+        true
+    }
+
+    /// Leaves a gap for time nothing was recorded, e.g. while the device was off.
+    pub fn skip(&mut self, elapsed: Duration) {
+        self.since_last += elapsed;
+        let missed = self.since_last.as_secs() / SAMPLE_INTERVAL.as_secs();
+        for _ in 0..missed.min(CAPACITY as u64) {
+            self.samples.push(None);
+        }
+        self.since_last -= SAMPLE_INTERVAL * missed as u32;
+    }
+
+    /// The `count` samples ending `ago` samples before the newest, oldest first. Padded with
+    /// `None` at the start if there isn't that much history yet.
+    pub fn window(&self, count: usize, ago: usize) -> Vec<Option<Sample>> {
+        let mut window: Vec<_> = (ago..ago + count)
+            .map(|back| self.samples.get_back(back).copied().flatten())
+            .collect();
+        window.reverse();
+        window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(capacity: usize, items: impl IntoIterator<Item = u32>) -> RingBuffer<u32> {
+        let mut ring = RingBuffer::new(capacity);
+        for item in items {
+            ring.push(item);
+        }
+        ring
+    }
+
+    fn sample(value: u8) -> Option<Sample> {
+        Some(Sample {
+            hunger: value,
+            happiness: value,
+            health: value,
+            weight: value as u16,
+        })
+    }
+
+    fn stats(value: u8) -> Stats {
+        Stats {
+            hunger: value,
+            ..Stats::default()
+        }
+    }
+
+    fn sample_of(hunger: u8) -> Option<Sample> {
+        Some(Sample::from(&stats(hunger)))
+    }
+
+    #[test]
+    fn ring_drops_the_oldest() {
+        let partial = ring(3, [1, 2]);
+        assert_eq!(partial.len(), 2);
+        assert_eq!(partial.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        let wrapped = ring(3, 1..=5);
+        assert_eq!(wrapped.len(), 3);
+        assert_eq!(wrapped.capacity(), 3);
+        assert_eq!(wrapped.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(
+            wrapped.iter().rev().copied().collect::<Vec<_>>(),
+            vec![5, 4, 3]
+        );
+
+        assert_eq!(RingBuffer::<u32>::new(0).capacity(), 1);
+        assert!(RingBuffer::<u32>::new(4).is_empty());
+    }
+
+    #[test]
+    fn get_back_across_the_head() {
+        let partial = ring(4, [1, 2]);
+        assert_eq!(partial.get_back(0), Some(&2));
+        assert_eq!(partial.get_back(1), Some(&1));
+        assert_eq!(partial.get_back(2), None);
+
+        // every position of the head
+        for pushed in 4..12 {
+            let wrapped = ring(4, 1..=pushed);
+            let back: Vec<_> = (0..5).map(|ago| wrapped.get_back(ago).copied()).collect();
+            assert_eq!(
+                back,
+                vec![
+                    Some(pushed),
+                    Some(pushed - 1),
+                    Some(pushed - 2),
+                    Some(pushed - 3),
+                    None
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn equal_wherever_the_head_is() {
+        let unwrapped = ring(3, [1, 2, 3]);
+        let wrapped = ring(3, [0, 1, 2, 3]);
+        let twice = ring(3, [7, 8, 9, 0, 1, 2, 3]);
+        assert_eq!(unwrapped, wrapped);
+        assert_eq!(wrapped, twice);
+
+        assert_ne!(unwrapped, ring(3, [3, 2, 1]));
+        assert_ne!(unwrapped, ring(4, [1, 2, 3]));
+        assert_ne!(unwrapped, ring(3, [1, 2]));
+    }
+
+    #[test]
+    fn record_every_interval() {
+        let mut history = History::default();
+        assert!(!history.record(SAMPLE_INTERVAL - Duration::from_secs(1), &stats(10)));
+        assert!(history.record(Duration::from_secs(1), &stats(20)));
+        assert!(!history.record(SAMPLE_INTERVAL / 2, &stats(30)));
+        assert!(history.record(SAMPLE_INTERVAL / 2, &stats(40)));
+
+        assert_eq!(history.window(2, 0), vec![sample_of(20), sample_of(40)]);
+    }
+
+    #[test]
+    fn skip_leaves_gaps() {
+        let mut history = History::default();
+        history.record(SAMPLE_INTERVAL, &stats(10));
+
+        history.skip(SAMPLE_INTERVAL * 3 + Duration::from_secs(600));
+        assert_eq!(history.samples.len(), 4);
+        assert_eq!(history.since_last, Duration::from_secs(600));
+
+        // the part interval left over counts towards the next sample
+        assert!(history.record(SAMPLE_INTERVAL - Duration::from_secs(600), &stats(20)));
+        assert_eq!(
+            history.window(5, 0),
+            vec![sample_of(10), None, None, None, sample_of(20)]
+        );
+    }
+
+    #[test]
+    fn late_record_pads_the_missed_intervals() {
+        let mut history = History::default();
+        history.record(SAMPLE_INTERVAL, &stats(10));
+        assert!(history.record(SAMPLE_INTERVAL * 3, &stats(20)));
+        assert_eq!(
+            history.window(4, 0),
+            vec![sample_of(10), None, None, sample_of(20)]
+        );
+    }
+
+    #[test]
+    fn long_gaps_are_capped_at_capacity() {
+        let mut history = History::default();
+        history.record(SAMPLE_INTERVAL, &stats(10));
+        history.skip(SAMPLE_INTERVAL * (CAPACITY as u32 * 3));
+
+        assert_eq!(history.samples.len(), CAPACITY);
+        assert!(history.samples.iter().all(Option::is_none));
+        assert_eq!(history.since_last, Duration::ZERO);
+    }
+
+    #[test]
+    fn downsample_buckets() {
+        let samples: Vec<_> = [10, 20, 30, 40, 50, 60].into_iter().map(sample).collect();
+        assert_eq!(
+            downsample(&samples, 3, Stat::Hunger),
+            vec![Some(15), Some(35), Some(55)]
+        );
+        assert_eq!(
+            downsample(&samples, 6, Stat::Weight),
+            vec![Some(10), Some(20), Some(30), Some(40), Some(50), Some(60)]
+        );
+        assert_eq!(downsample(&samples, 1, Stat::Health), vec![Some(35)]);
+
+        // uneven buckets split at len * n / buckets
+        let samples: Vec<_> = [10, 20, 30, 40, 50].into_iter().map(sample).collect();
+        assert_eq!(
+            downsample(&samples, 2, Stat::Hunger),
+            vec![Some(15), Some(40)]
+        );
+    }
+
+    #[test]
+    fn downsample_gaps() {
+        let samples = vec![sample(10), None, None, None, sample(30), None];
+        assert_eq!(
+            downsample(&samples, 3, Stat::Happiness),
+            vec![Some(10), None, Some(30)]
+        );
+
+        // more buckets than samples leaves the in between ones empty
+        assert_eq!(
+            downsample(&[sample(10), sample(20)], 4, Stat::Hunger),
+            vec![None, Some(10), None, Some(20)]
+        );
+        assert_eq!(downsample(&[None, None], 2, Stat::Hunger), vec![None, None]);
+        assert_eq!(downsample(&[], 2, Stat::Hunger), vec![None, None]);
+    }
+
+    #[test]
+    fn window_pads_the_start() {
+        let mut history = History::default();
+        for value in [10, 20, 30] {
+            history.record(SAMPLE_INTERVAL, &stats(value));
+        }
+
+        assert_eq!(
+            history.window(5, 0),
+            vec![None, None, sample_of(10), sample_of(20), sample_of(30)]
+        );
+        assert_eq!(history.window(2, 1), vec![sample_of(10), sample_of(20)]);
+        assert_eq!(history.window(2, 3), vec![None, None]);
+        assert!(history.window(0, 0).is_empty());
+    }
+
+    #[test]
+    fn weight_chart_scales_to_the_heaviest() {
+        let samples = [sample(10), None, sample(40)];
+        assert_eq!(Stat::Weight.max(samples.iter()), 40);
+        assert_eq!(Stat::Weight.max([None].iter()), 1);
+        assert_eq!(Stat::Hunger.max(samples.iter()), STAT_MAX as u16);
+    }
+}
@@ -0,0 +1,238 @@
+//! English, the text every other language is checked against.
+
+use crate::i18n::Key;
+use crate::i18n::Text::{self, Plain, Plural};
+
+pub(super) const fn text(key: Key) -> Text {
+    match key {
+        // life stages, forms and traits
+        Key::StageEgg => Plain("Egg"),
+        Key::StageBaby => Plain("Baby"),
+        Key::StageChild => Plain("Child"),
+        Key::StageTeen => Plain("Teen"),
+        Key::StageAdult => Plain("Adult"),
+        Key::StageSenior => Plain("Senior"),
+        Key::FormGood => Plain("Good"),
+        Key::FormAverage => Plain("Average"),
+        Key::FormPoor => Plain("Poor"),
+        Key::TraitPlayful => Plain("Playful"),
+        Key::TraitLazy => Plain("Lazy"),
+        Key::TraitGreedy => Plain("Greedy"),
+        Key::TraitShy => Plain("Shy"),
+        Key::TraitPicky => Plain("Picky"),
+        Key::PersonalityUnknown => Plain("unknown"),
+
+        // generations
+        Key::PaletteWhite => Plain("White"),
+        Key::PaletteSunny => Plain("Sunny"),
+        Key::PaletteMint => Plain("Mint"),
+        Key::PaletteSky => Plain("Sky"),
+        Key::PaletteRose => Plain("Rose"),
+        Key::PaletteLilac => Plain("Lilac"),
+        Key::FarewellSickness => Plain("{name} died of sickness"),
+        Key::FarewellStarvation => Plain("{name} starved"),
+        Key::FarewellOldAge => Plain("{name} left to see the world"),
+
+        // care
+        Key::MistakeHunger => Plain("left hungry"),
+        Key::MistakeSickness => Plain("left sick"),
+        Key::MistakeLightsOn => Plain("lights left on"),
+        Key::MistakeWaste => Plain("left dirty"),
+        Key::MistakeLonely => Plain("left lonely"),
+        Key::CallSick => Plain("{name} feels sick"),
+        Key::CallHungry => Plain("{name} is hungry"),
+        Key::CallWaste => Plain("{name} needs cleaning up"),
+        Key::CallBored => Plain("{name} wants to play"),
+        Key::CallMisbehaving => Plain("{name} is calling for you"),
+        Key::CallEvolving => Plain("{name} is growing up"),
+        Key::DisciplineCorrected => Plain("{name} stopped misbehaving"),
+        Key::DisciplineUnfair => Plain("{name} didn't deserve that"),
+        Key::DisciplinePraised => Plain("{name} is proud of itself"),
+        Key::DisciplineSpoiled => Plain("{name} got away with it"),
+        Key::DisciplinePleased => Plain("{name} is pleased"),
+
+        // food
+        Key::FoodMeal => Plain("Meal"),
+        Key::FoodSnack => Plain("Snack"),
+        Key::FoodTreat => Plain("Treat"),
+        Key::FoodMedicine => Plain("Medicine"),
+        Key::FedLoved => Plain("{name} loved the {food}!"),
+        Key::FedLiked => Plain("{name} ate the {food}"),
+        Key::FedDisliked => Plain("{name} didn't enjoy the {food}"),
+        Key::Overfed => Plain("{name} is too full"),
+        Key::OutOfStock => Plain("No {food} left"),
+        Key::Refused => Plain("{name} refuses the {food}"),
+        Key::Cured => Plain("{name} feels better"),
+        Key::DosesNeeded => Plural {
+            one: "One more dose needed",
+            other: "{count} more doses needed",
+        },
+        Key::CantEat => Plain("{name} can't eat"),
+        Key::FeedTitle => Plain("Feed"),
+
+        // happenings
+        Key::HappeningVisitor => Plain("A visitor!"),
+        Key::HappeningPresent => Plain("A present!"),
+        Key::HappeningRain => Plain("Rain"),
+        Key::HappeningShootingStar => Plain("A shooting star"),
+        Key::HappeningButterfly => Plain("A butterfly"),
+        Key::SummaryVisitor => Plain("{name} had a visitor"),
+        Key::SummaryPresent => Plain("{name} got a present"),
+        Key::SummaryRain => Plain("{name} was kept in by the rain"),
+        Key::SummaryShootingStar => Plain("{name} saw a shooting star"),
+        Key::SummaryButterfly => Plain("{name} chased a butterfly"),
+        Key::PresentWrapped => Plain("[ # ]  click to open"),
+        Key::PresentEmpty => Plain("Nothing!"),
+
+        // catching up
+        Key::CaughtUpHungry => Plain("{name} got hungry"),
+        Key::CaughtUpLonely => Plain("{name} got lonely"),
+        Key::CaughtUpEvolved => Plain("{name} grew into a {stage}"),
+        Key::CaughtUpSick => Plain("{name} fell sick"),
+        Key::CaughtUpDied => Plain("{name} passed away"),
+        Key::CaughtUpPooped => Plural {
+            one: "{name} pooped",
+            other: "{name} pooped {count} times",
+        },
+        Key::CaughtUpMistakes => Plural {
+            one: "A care mistake was made",
+            other: "{count} care mistakes were made",
+        },
+
+        // home
+        Key::MeterHunger => Plain("Hunger"),
+        Key::MeterHappy => Plain("Happy"),
+        Key::MeterHealth => Plain("Health"),
+        Key::HomeTitle => Plain("{name} the {stage}"),
+        Key::HomeTitleRaining => Plain("{name} the {stage}, rain"),
+        Key::NewEggHint => Plain("Click for a new egg"),
+        Key::NewEgg => Plain("A new egg, generation {generation}"),
+        Key::Unlocked => Plain("Unlocked {achievement}"),
+        Key::Cleaned => Plain("{name} is squeaky clean"),
+        Key::AlreadyClean => Plain("{name} is already clean"),
+        Key::LightsOn => Plain("Lights on"),
+        Key::LightsOff => Plain("Lights off"),
+
+        // stats
+        Key::StatsTitle => Plain("Stats"),
+        Key::StatsHint => Plain("Click: awards  x2: charts"),
+        Key::StatsSummary => Plain("Gen {generation} {stage}, raised {form}, {hours}h old"),
+        Key::StatsPersonality => Plain("Personality: {traits}  Colour: {colour}"),
+        Key::StatsBody => Plain("Weight {weight}g  Hygiene {hygiene}  Poops {poops}"),
+        Key::StatsPassedAway => Plain("Passed away"),
+        Key::StatsHealthy => Plain("Healthy"),
+        Key::StatsSick => Plural {
+            one: "Sick, needs {count} medicine",
+            other: "Sick, needs {count} medicines",
+        },
+        Key::StatsDiscipline => Plain("Discipline {level}"),
+        Key::StatsMisbehaving => Plain("Discipline {level}, misbehaving"),
+        Key::StatsMistakes => Plain("Care mistakes {total} ({stage} this stage)"),
+        Key::StatsMistakeAt => Plain("  at {hours}h {mistake}"),
+        Key::StatHunger => Plain("Hunger"),
+        Key::StatHappiness => Plain("Happiness"),
+        Key::StatHealth => Plain("Health"),
+        Key::StatWeight => Plain("Weight"),
+        Key::ChartNow => Plain("now"),
+
+        // achievements
+        Key::AchievementsTitle => Plain("Achievements"),
+        Key::FirstSteps => Plain("First steps"),
+        Key::FirstStepsInfo => Plain("Evolve for the first time"),
+        Key::SpotlessWeek => Plain("Spotless week"),
+        Key::SpotlessWeekInfo => Plain("Go 7 days without a care mistake"),
+        Key::Perfectionist => Plain("Perfectionist"),
+        Key::PerfectionistInfo => Plain("Get a perfect score in a game"),
+        Key::FullHouse => Plain("Full house"),
+        Key::FullHouseInfo => Plain("Raise an adult of every form"),
+        Key::AllGrownUp => Plain("All grown up"),
+        Key::AllGrownUpInfo => Plain("Raise a pet into an adult"),
+        Key::GoldenYears => Plain("Golden years"),
+        Key::GoldenYearsInfo => Plain("Keep a pet until it's a senior"),
+        Key::GameOn => Plain("Game on"),
+        Key::GameOnInfo => Plain("Play 50 games"),
+        Key::GoodManners => Plain("Good manners"),
+        Key::GoodMannersInfo => Plain("Reach full discipline"),
+        Key::MakeAWish => Plain("Make a wish"),
+        Key::MakeAWishInfo => Plain("See a shooting star"),
+        Key::SeenItAll => Plain("Seen it all"),
+        Key::SeenItAllInfo => Plain("See every kind of happening"),
+
+        // games
+        Key::PlayTitle => Plain("Play"),
+        Key::GameGuess => Plain("Left or right"),
+        Key::GameNeedle => Plain("Stop the needle"),
+        Key::GameReflex => Plain("Quick paws"),
+        Key::Round => Plain("Round {round}/{rounds}"),
+        Key::Score => Plain("Score {score}"),
+        Key::FinalScore => Plain("Score {score}/{max}"),
+        Key::Reward => Plain("+{happiness} happiness"),
+        Key::RewardFood => Plain("+{happiness} happiness, won a {food}"),
+        Key::Missed => Plain("Missed"),
+        Key::WaitForIt => Plain("Wait for it..."),
+        Key::Now => Plain("NOW!"),
+        Key::TooEarly => Plain("Too early!"),
+        Key::TooSlow => Plain("Too slow!"),
+        Key::FoundMe => Plain("Found me!"),
+        Key::NotThere => Plain("Not there..."),
+
+        // memorial, naming and more
+        Key::MemorialTitle => Plain("In memory of"),
+        Key::MemorialEmpty => Plain("Nobody yet"),
+        Key::MemorialRow => Plain("{name}, gen {generation}, {days}d {form} {stage}"),
+        Key::MoreTitle => Plain("More"),
+        Key::MoreRename => Plain("Rename {name}"),
+        Key::MoreOwner => Plain("Owner: {name}"),
+        Key::MoreOwnerUnset => Plain("Owner: not set"),
+        Key::MoreMemorial => Plain("Memorial"),
+        Key::MoreSettings => Plain("Settings"),
+        Key::NamePet => Plain("Name your pet"),
+        Key::NameOwner => Plain("Your name"),
+        Key::TextEntryHint => Plain("Click: add  Hold: delete  x2: done"),
+        Key::Yes => Plain("Yes"),
+        Key::No => Plain("No"),
+        Key::SettingsTitle => Plain("Settings"),
+        Key::SettingsBacklight => Plain("Backlight"),
+        Key::SettingsLeds => Plain("LED limit"),
+        Key::SettingsScreenOff => Plain("Screen off"),
+        Key::SettingsReverseDial => Plain("Reverse dial"),
+        Key::SettingsClock => Plain("Clock"),
+        Key::SettingsTimeZone => Plain("Time zone"),
+        Key::SettingsLanguage => Plain("Language"),
+        Key::SettingsHint => Plain("Click: change  Hold: back"),
+        Key::SettingsEditHint => Plain("Turn: adjust  Click: done"),
+        Key::SettingsOff => Plain("Off"),
+        Key::SettingsNever => Plain("Never"),
+        Key::Clock24 => Plain("24 hour"),
+        Key::Clock12 => Plain("12 hour"),
+        Key::MoreClock => Plain("Clock"),
+        Key::MoreAlarm => Plain("Alarm: {time}"),
+        Key::MoreAlarmOff => Plain("Alarm: off"),
+        Key::MoreTimer => Plain("Timer"),
+        Key::SettingsFace => Plain("Clock face"),
+        Key::FaceDigital => Plain("Digital"),
+        Key::FaceAnalogue => Plain("Analogue"),
+        Key::ClockUnset => Plain("Clock not set"),
+        Key::ClockHint => Plain("Turn: face  Click: set time  Hold: back"),
+        Key::SetClockTitle => Plain("Set the time"),
+        Key::SetAlarmTitle => Plain("Alarm time"),
+        Key::TimeEntryHint => Plain("Turn: change  Click: next  Hold: back"),
+        Key::AlarmTitle => Plain("Alarm"),
+        Key::AlarmEnabled => Plain("On: {value}"),
+        Key::AlarmTime => Plain("Time: {time}"),
+        Key::RingingTitle => Plain("Alarm {time}"),
+        Key::RingingHint => Plain("Click: snooze  Hold: stop"),
+        Key::TimerTitle => Plain("Timer"),
+        Key::PomodoroTitle => Plain("Pomodoro"),
+        Key::TimerWork => Plain("Work"),
+        Key::TimerBreak => Plain("Break"),
+        Key::TimerPaused => Plain("Paused"),
+        Key::TimerDone => Plain("Done!"),
+        Key::TimerHintStopped => Plain("Turn: minutes  Click: start  x2: mode  Hold: back"),
+        Key::TimerHintRunning => Plain("Click: pause  Hold: reset"),
+        Key::TimerPomodoros => Plural {
+            one: "{count} pomodoro done",
+            other: "{count} pomodoros done",
+        },
+    }
+}
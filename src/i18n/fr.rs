@@ -0,0 +1,238 @@
+//! French, counting zero as singular.
+
+use crate::i18n::Key;
+use crate::i18n::Text::{self, Plain, Plural};
+
+pub(super) const fn text(key: Key) -> Text {
+    match key {
+        // life stages, forms and traits
+        Key::StageEgg => Plain("Oeuf"),
+        Key::StageBaby => Plain("Bébé"),
+        Key::StageChild => Plain("Enfant"),
+        Key::StageTeen => Plain("Ado"),
+        Key::StageAdult => Plain("Adulte"),
+        Key::StageSenior => Plain("Aîné"),
+        Key::FormGood => Plain("Sage"),
+        Key::FormAverage => Plain("Moyen"),
+        Key::FormPoor => Plain("Turbulent"),
+        Key::TraitPlayful => Plain("Joueur"),
+        Key::TraitLazy => Plain("Paresseux"),
+        Key::TraitGreedy => Plain("Gourmand"),
+        Key::TraitShy => Plain("Timide"),
+        Key::TraitPicky => Plain("Difficile"),
+        Key::PersonalityUnknown => Plain("inconnue"),
+
+        // generations
+        Key::PaletteWhite => Plain("Blanc"),
+        Key::PaletteSunny => Plain("Soleil"),
+        Key::PaletteMint => Plain("Menthe"),
+        Key::PaletteSky => Plain("Ciel"),
+        Key::PaletteRose => Plain("Rose"),
+        Key::PaletteLilac => Plain("Lilas"),
+        Key::FarewellSickness => Plain("{name} est mort de maladie"),
+        Key::FarewellStarvation => Plain("{name} est mort de faim"),
+        Key::FarewellOldAge => Plain("{name} est parti voir le monde"),
+
+        // care
+        Key::MistakeHunger => Plain("laissé affamé"),
+        Key::MistakeSickness => Plain("laissé malade"),
+        Key::MistakeLightsOn => Plain("lumière laissée allumée"),
+        Key::MistakeWaste => Plain("laissé sale"),
+        Key::MistakeLonely => Plain("laissé seul"),
+        Key::CallSick => Plain("{name} est malade"),
+        Key::CallHungry => Plain("{name} a faim"),
+        Key::CallWaste => Plain("{name} a besoin d'un bain"),
+        Key::CallBored => Plain("{name} veut jouer"),
+        Key::CallMisbehaving => Plain("{name} vous appelle"),
+        Key::CallEvolving => Plain("{name} grandit"),
+        Key::DisciplineCorrected => Plain("{name} a arrêté ses bêtises"),
+        Key::DisciplineUnfair => Plain("{name} ne méritait pas ça"),
+        Key::DisciplinePraised => Plain("{name} est fier de lui"),
+        Key::DisciplineSpoiled => Plain("{name} s'en est tiré"),
+        Key::DisciplinePleased => Plain("{name} est content"),
+
+        // food
+        Key::FoodMeal => Plain("Repas"),
+        Key::FoodSnack => Plain("Goûter"),
+        Key::FoodTreat => Plain("Friandise"),
+        Key::FoodMedicine => Plain("Médicament"),
+        Key::FedLoved => Plain("{name} a adoré: {food}!"),
+        Key::FedLiked => Plain("{name} a mangé: {food}"),
+        Key::FedDisliked => Plain("{name} n'a pas aimé: {food}"),
+        Key::Overfed => Plain("{name} n'a plus faim"),
+        Key::OutOfStock => Plain("Plus de stock: {food}"),
+        Key::Refused => Plain("{name} refuse: {food}"),
+        Key::Cured => Plain("{name} va mieux"),
+        Key::DosesNeeded => Plural {
+            one: "Encore {count} dose",
+            other: "Encore {count} doses",
+        },
+        Key::CantEat => Plain("{name} ne peut pas manger"),
+        Key::FeedTitle => Plain("Manger"),
+
+        // happenings
+        Key::HappeningVisitor => Plain("Une visite!"),
+        Key::HappeningPresent => Plain("Un cadeau!"),
+        Key::HappeningRain => Plain("Pluie"),
+        Key::HappeningShootingStar => Plain("Une étoile filante"),
+        Key::HappeningButterfly => Plain("Un papillon"),
+        Key::SummaryVisitor => Plain("{name} a eu de la visite"),
+        Key::SummaryPresent => Plain("{name} a reçu un cadeau"),
+        Key::SummaryRain => Plain("{name} est resté dedans à cause de la pluie"),
+        Key::SummaryShootingStar => Plain("{name} a vu une étoile filante"),
+        Key::SummaryButterfly => Plain("{name} a chassé un papillon"),
+        Key::PresentWrapped => Plain("[ # ]  cliquez pour ouvrir"),
+        Key::PresentEmpty => Plain("Rien!"),
+
+        // catching up
+        Key::CaughtUpHungry => Plain("{name} a eu faim"),
+        Key::CaughtUpLonely => Plain("{name} s'est senti seul"),
+        Key::CaughtUpEvolved => Plain("{name} est devenu {stage}"),
+        Key::CaughtUpSick => Plain("{name} est tombé malade"),
+        Key::CaughtUpDied => Plain("{name} nous a quittés"),
+        Key::CaughtUpPooped => Plural {
+            one: "{name} a fait caca",
+            other: "{name} a fait caca {count} fois",
+        },
+        Key::CaughtUpMistakes => Plural {
+            one: "{count} erreur de soin",
+            other: "{count} erreurs de soin",
+        },
+
+        // home
+        Key::MeterHunger => Plain("Faim"),
+        Key::MeterHappy => Plain("Joie"),
+        Key::MeterHealth => Plain("Santé"),
+        Key::HomeTitle => Plain("{name} ({stage})"),
+        Key::HomeTitleRaining => Plain("{name} ({stage}), pluie"),
+        Key::NewEggHint => Plain("Cliquez: nouvel oeuf"),
+        Key::NewEgg => Plain("Un nouvel oeuf, génération {generation}"),
+        Key::Unlocked => Plain("Débloqué: {achievement}"),
+        Key::Cleaned => Plain("{name} est tout propre"),
+        Key::AlreadyClean => Plain("{name} est déjà propre"),
+        Key::LightsOn => Plain("Lumière allumée"),
+        Key::LightsOff => Plain("Lumière éteinte"),
+
+        // stats
+        Key::StatsTitle => Plain("Stats"),
+        Key::StatsHint => Plain("Clic: trophées  x2: courbes"),
+        Key::StatsSummary => Plain("Gén {generation} {stage}, {form}, {hours}h"),
+        Key::StatsPersonality => Plain("Caractère: {traits}  Couleur: {colour}"),
+        Key::StatsBody => Plain("Poids {weight}g  Hygiène {hygiene}  Cacas {poops}"),
+        Key::StatsPassedAway => Plain("Disparu"),
+        Key::StatsHealthy => Plain("En forme"),
+        Key::StatsSick => Plural {
+            one: "Malade, {count} médicament requis",
+            other: "Malade, {count} médicaments requis",
+        },
+        Key::StatsDiscipline => Plain("Discipline {level}"),
+        Key::StatsMisbehaving => Plain("Discipline {level}, fait des bêtises"),
+        Key::StatsMistakes => Plain("Erreurs de soin {total} ({stage} ce stade)"),
+        Key::StatsMistakeAt => Plain("  à {hours}h {mistake}"),
+        Key::StatHunger => Plain("Faim"),
+        Key::StatHappiness => Plain("Bonheur"),
+        Key::StatHealth => Plain("Santé"),
+        Key::StatWeight => Plain("Poids"),
+        Key::ChartNow => Plain("maint."),
+
+        // achievements
+        Key::AchievementsTitle => Plain("Trophées"),
+        Key::FirstSteps => Plain("Premiers pas"),
+        Key::FirstStepsInfo => Plain("Évoluer pour la première fois"),
+        Key::SpotlessWeek => Plain("Semaine parfaite"),
+        Key::SpotlessWeekInfo => Plain("7 jours sans erreur de soin"),
+        Key::Perfectionist => Plain("Perfectionniste"),
+        Key::PerfectionistInfo => Plain("Faire un score parfait à un jeu"),
+        Key::FullHouse => Plain("Grand chelem"),
+        Key::FullHouseInfo => Plain("Élever un adulte de chaque forme"),
+        Key::AllGrownUp => Plain("Bien grandi"),
+        Key::AllGrownUpInfo => Plain("Élever un animal jusqu'à l'âge adulte"),
+        Key::GoldenYears => Plain("Âge d'or"),
+        Key::GoldenYearsInfo => Plain("Garder un animal jusqu'à ses vieux jours"),
+        Key::GameOn => Plain("À vous de jouer"),
+        Key::GameOnInfo => Plain("Jouer 50 parties"),
+        Key::GoodManners => Plain("Bonnes manières"),
+        Key::GoodMannersInfo => Plain("Atteindre la discipline maximale"),
+        Key::MakeAWish => Plain("Faites un voeu"),
+        Key::MakeAWishInfo => Plain("Voir une étoile filante"),
+        Key::SeenItAll => Plain("Rien ne l'étonne"),
+        Key::SeenItAllInfo => Plain("Voir chaque sorte d'événement"),
+
+        // games
+        Key::PlayTitle => Plain("Jouer"),
+        Key::GameGuess => Plain("Gauche ou droite"),
+        Key::GameNeedle => Plain("Stoppe l'aiguille"),
+        Key::GameReflex => Plain("Pattes rapides"),
+        Key::Round => Plain("Manche {round}/{rounds}"),
+        Key::Score => Plain("Score {score}"),
+        Key::FinalScore => Plain("Score {score}/{max}"),
+        Key::Reward => Plain("+{happiness} bonheur"),
+        Key::RewardFood => Plain("+{happiness} bonheur, gagné: {food}"),
+        Key::Missed => Plain("Raté"),
+        Key::WaitForIt => Plain("Attention..."),
+        Key::Now => Plain("VAS-Y!"),
+        Key::TooEarly => Plain("Trop tôt!"),
+        Key::TooSlow => Plain("Trop lent!"),
+        Key::FoundMe => Plain("Trouvé!"),
+        Key::NotThere => Plain("Pas ici..."),
+
+        // memorial, naming and more
+        Key::MemorialTitle => Plain("En mémoire de"),
+        Key::MemorialEmpty => Plain("Personne encore"),
+        Key::MemorialRow => Plain("{name}, gén {generation}, {days}j {form} {stage}"),
+        Key::MoreTitle => Plain("Plus"),
+        Key::MoreRename => Plain("Renommer {name}"),
+        Key::MoreOwner => Plain("Maître: {name}"),
+        Key::MoreOwnerUnset => Plain("Maître: inconnu"),
+        Key::MoreMemorial => Plain("Souvenirs"),
+        Key::MoreSettings => Plain("Réglages"),
+        Key::NamePet => Plain("Nommez votre animal"),
+        Key::NameOwner => Plain("Votre nom"),
+        Key::TextEntryHint => Plain("Clic: ajouter  Appui: effacer  x2: ok"),
+        Key::Yes => Plain("Oui"),
+        Key::No => Plain("Non"),
+        Key::SettingsTitle => Plain("Réglages"),
+        Key::SettingsBacklight => Plain("Écran"),
+        Key::SettingsLeds => Plain("Limite LED"),
+        Key::SettingsScreenOff => Plain("Veille"),
+        Key::SettingsReverseDial => Plain("Molette inversée"),
+        Key::SettingsClock => Plain("Horloge"),
+        Key::SettingsTimeZone => Plain("Fuseau"),
+        Key::SettingsLanguage => Plain("Langue"),
+        Key::SettingsHint => Plain("Clic: modifier  Long: retour"),
+        Key::SettingsEditHint => Plain("Tourner: régler  Clic: fini"),
+        Key::SettingsOff => Plain("Éteint"),
+        Key::SettingsNever => Plain("Jamais"),
+        Key::Clock24 => Plain("24 h"),
+        Key::Clock12 => Plain("12 h"),
+        Key::MoreClock => Plain("Horloge"),
+        Key::MoreAlarm => Plain("Réveil: {time}"),
+        Key::MoreAlarmOff => Plain("Réveil: éteint"),
+        Key::MoreTimer => Plain("Minuteur"),
+        Key::SettingsFace => Plain("Cadran"),
+        Key::FaceDigital => Plain("Numérique"),
+        Key::FaceAnalogue => Plain("Aiguilles"),
+        Key::ClockUnset => Plain("Heure non réglée"),
+        Key::ClockHint => Plain("Tourner: cadran  Clic: régler  Long: retour"),
+        Key::SetClockTitle => Plain("Régler l'heure"),
+        Key::SetAlarmTitle => Plain("Heure du réveil"),
+        Key::TimeEntryHint => Plain("Tourner: changer  Clic: suivant  Long: retour"),
+        Key::AlarmTitle => Plain("Réveil"),
+        Key::AlarmEnabled => Plain("Activé: {value}"),
+        Key::AlarmTime => Plain("Heure: {time}"),
+        Key::RingingTitle => Plain("Réveil {time}"),
+        Key::RingingHint => Plain("Clic: répéter  Long: arrêter"),
+        Key::TimerTitle => Plain("Minuteur"),
+        Key::PomodoroTitle => Plain("Pomodoro"),
+        Key::TimerWork => Plain("Travail"),
+        Key::TimerBreak => Plain("Pause"),
+        Key::TimerPaused => Plain("En pause"),
+        Key::TimerDone => Plain("Terminé!"),
+        Key::TimerHintStopped => Plain("Tourner: minutes  Clic: lancer  x2: mode  Long: retour"),
+        Key::TimerHintRunning => Plain("Clic: pause  Long: remettre à zéro"),
+        Key::TimerPomodoros => Plural {
+            one: "{count} pomodoro fini",
+            other: "{count} pomodoros finis",
+        },
+    }
+}
@@ -0,0 +1,667 @@
+//! Every piece of text shown on the screen, looked up by [`Key`] in the current language.
+//!
+//! Each language is a `const fn` matching on every key, so a language missing a key doesn't
+//! compile. The tables are also checked while compiling for glyphs the theme's Latin-1 fonts can't
+//! draw and for `{placeholders}` that don't match the English text.
+
+mod en;
+mod fr;
+
+use once_cell::sync::Lazy;
+use std::fmt::Display;
+use std::sync::RwLock;
+
+static LANGUAGE: Lazy<RwLock<Language>> = Lazy::new(|| RwLock::new(Language::English));
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::French];
+
+    /// Written in the language itself so it can be found whichever one is set.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        Self::ALL.get(val as usize).copied()
+    }
+
+    /// Which form of a [`Text::Plural`] goes with `count`.
+    pub fn plural(&self, count: u32) -> PluralForm {
+        let one = match self {
+            Language::English => count == 1,
+            Language::French => count <= 1,
+        };
+
+        match one {
+            true => PluralForm::One,
+            false => PluralForm::Other,
+        }
+    }
+
+    const fn table(&self, key: Key) -> Text {
+        match self {
+            Language::English => en::text(key),
+            Language::French => fr::text(key),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PluralForm {
+    One,
+    Other,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Text {
+    Plain(&'static str),
+    /// Picked by [`Language::plural`], `{count}` is filled in with the number.
+    Plural {
+        one: &'static str,
+        other: &'static str,
+    },
+}
+
+impl Text {
+    const fn forms(&self) -> [&'static str; 2] {
+        match *self {
+            Text::Plain(text) => [text, text],
+            Text::Plural { one, other } => [one, other],
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Key {
+    // life stages, forms and traits
+    StageEgg,
+    StageBaby,
+    StageChild,
+    StageTeen,
+    StageAdult,
+    StageSenior,
+    FormGood,
+    FormAverage,
+    FormPoor,
+    TraitPlayful,
+    TraitLazy,
+    TraitGreedy,
+    TraitShy,
+    TraitPicky,
+    PersonalityUnknown,
+
+    // generations
+    PaletteWhite,
+    PaletteSunny,
+    PaletteMint,
+    PaletteSky,
+    PaletteRose,
+    PaletteLilac,
+    FarewellSickness,
+    FarewellStarvation,
+    FarewellOldAge,
+
+    // care
+    MistakeHunger,
+    MistakeSickness,
+    MistakeLightsOn,
+    MistakeWaste,
+    MistakeLonely,
+    CallSick,
+    CallHungry,
+    CallWaste,
+    CallBored,
+    CallMisbehaving,
+    CallEvolving,
+    DisciplineCorrected,
+    DisciplineUnfair,
+    DisciplinePraised,
+    DisciplineSpoiled,
+    DisciplinePleased,
+
+    // food
+    FoodMeal,
+    FoodSnack,
+    FoodTreat,
+    FoodMedicine,
+    FedLoved,
+    FedLiked,
+    FedDisliked,
+    Overfed,
+    OutOfStock,
+    Refused,
+    Cured,
+    DosesNeeded,
+    CantEat,
+    FeedTitle,
+
+    // happenings
+    HappeningVisitor,
+    HappeningPresent,
+    HappeningRain,
+    HappeningShootingStar,
+    HappeningButterfly,
+    SummaryVisitor,
+    SummaryPresent,
+    SummaryRain,
+    SummaryShootingStar,
+    SummaryButterfly,
+    PresentWrapped,
+    PresentEmpty,
+
+    // catching up
+    CaughtUpHungry,
+    CaughtUpLonely,
+    CaughtUpEvolved,
+    CaughtUpSick,
+    CaughtUpDied,
+    CaughtUpPooped,
+    CaughtUpMistakes,
+
+    // home
+    MeterHunger,
+    MeterHappy,
+    MeterHealth,
+    HomeTitle,
+    HomeTitleRaining,
+    NewEggHint,
+    NewEgg,
+    Unlocked,
+    Cleaned,
+    AlreadyClean,
+    LightsOn,
+    LightsOff,
+
+    // stats
+    StatsTitle,
+    StatsHint,
+    StatsSummary,
+    StatsPersonality,
+    StatsBody,
+    StatsPassedAway,
+    StatsHealthy,
+    StatsSick,
+    StatsDiscipline,
+    StatsMisbehaving,
+    StatsMistakes,
+    StatsMistakeAt,
+    StatHunger,
+    StatHappiness,
+    StatHealth,
+    StatWeight,
+    ChartNow,
+
+    // achievements
+    AchievementsTitle,
+    FirstSteps,
+    FirstStepsInfo,
+    SpotlessWeek,
+    SpotlessWeekInfo,
+    Perfectionist,
+    PerfectionistInfo,
+    FullHouse,
+    FullHouseInfo,
+    AllGrownUp,
+    AllGrownUpInfo,
+    GoldenYears,
+    GoldenYearsInfo,
+    GameOn,
+    GameOnInfo,
+    GoodManners,
+    GoodMannersInfo,
+    MakeAWish,
+    MakeAWishInfo,
+    SeenItAll,
+    SeenItAllInfo,
+
+    // games
+    PlayTitle,
+    GameGuess,
+    GameNeedle,
+    GameReflex,
+    Round,
+    Score,
+    FinalScore,
+    Reward,
+    RewardFood,
+    Missed,
+    WaitForIt,
+    Now,
+    TooEarly,
+    TooSlow,
+    FoundMe,
+    NotThere,
+
+    // memorial, naming and more
+    MemorialTitle,
+    MemorialEmpty,
+    MemorialRow,
+    MoreTitle,
+    MoreRename,
+    MoreOwner,
+    MoreOwnerUnset,
+    MoreMemorial,
+    MoreSettings,
+    NamePet,
+    NameOwner,
+    TextEntryHint,
+    Yes,
+    No,
+
+    // settings
+    SettingsTitle,
+    SettingsBacklight,
+    SettingsLeds,
+    SettingsScreenOff,
+    SettingsReverseDial,
+    SettingsClock,
+    SettingsTimeZone,
+    SettingsLanguage,
+    SettingsHint,
+    SettingsEditHint,
+    SettingsOff,
+    SettingsNever,
+    Clock24,
+    Clock12,
+
+    // clock, alarm and timer
+    MoreClock,
+    MoreAlarm,
+    MoreAlarmOff,
+    MoreTimer,
+    SettingsFace,
+    FaceDigital,
+    FaceAnalogue,
+    ClockUnset,
+    ClockHint,
+    SetClockTitle,
+    SetAlarmTitle,
+    TimeEntryHint,
+    AlarmTitle,
+    AlarmEnabled,
+    AlarmTime,
+    RingingTitle,
+    RingingHint,
+    TimerTitle,
+    PomodoroTitle,
+    TimerWork,
+    TimerBreak,
+    TimerPaused,
+    TimerDone,
+    TimerHintStopped,
+    TimerHintRunning,
+    TimerPomodoros,
+}
+
+impl Key {
+    pub const ALL: [Key; 193] = [
+        Key::StageEgg,
+        Key::StageBaby,
+        Key::StageChild,
+        Key::StageTeen,
+        Key::StageAdult,
+        Key::StageSenior,
+        Key::FormGood,
+        Key::FormAverage,
+        Key::FormPoor,
+        Key::TraitPlayful,
+        Key::TraitLazy,
+        Key::TraitGreedy,
+        Key::TraitShy,
+        Key::TraitPicky,
+        Key::PersonalityUnknown,
+        Key::PaletteWhite,
+        Key::PaletteSunny,
+        Key::PaletteMint,
+        Key::PaletteSky,
+        Key::PaletteRose,
+        Key::PaletteLilac,
+        Key::FarewellSickness,
+        Key::FarewellStarvation,
+        Key::FarewellOldAge,
+        Key::MistakeHunger,
+        Key::MistakeSickness,
+        Key::MistakeLightsOn,
+        Key::MistakeWaste,
+        Key::MistakeLonely,
+        Key::CallSick,
+        Key::CallHungry,
+        Key::CallWaste,
+        Key::CallBored,
+        Key::CallMisbehaving,
+        Key::CallEvolving,
+        Key::DisciplineCorrected,
+        Key::DisciplineUnfair,
+        Key::DisciplinePraised,
+        Key::DisciplineSpoiled,
+        Key::DisciplinePleased,
+        Key::FoodMeal,
+        Key::FoodSnack,
+        Key::FoodTreat,
+        Key::FoodMedicine,
+        Key::FedLoved,
+        Key::FedLiked,
+        Key::FedDisliked,
+        Key::Overfed,
+        Key::OutOfStock,
+        Key::Refused,
+        Key::Cured,
+        Key::DosesNeeded,
+        Key::CantEat,
+        Key::FeedTitle,
+        Key::HappeningVisitor,
+        Key::HappeningPresent,
+        Key::HappeningRain,
+        Key::HappeningShootingStar,
+        Key::HappeningButterfly,
+        Key::SummaryVisitor,
+        Key::SummaryPresent,
+        Key::SummaryRain,
+        Key::SummaryShootingStar,
+        Key::SummaryButterfly,
+        Key::PresentWrapped,
+        Key::PresentEmpty,
+        Key::CaughtUpHungry,
+        Key::CaughtUpLonely,
+        Key::CaughtUpEvolved,
+        Key::CaughtUpSick,
+        Key::CaughtUpDied,
+        Key::CaughtUpPooped,
+        Key::CaughtUpMistakes,
+        Key::MeterHunger,
+        Key::MeterHappy,
+        Key::MeterHealth,
+        Key::HomeTitle,
+        Key::HomeTitleRaining,
+        Key::NewEggHint,
+        Key::NewEgg,
+        Key::Unlocked,
+        Key::Cleaned,
+        Key::AlreadyClean,
+        Key::LightsOn,
+        Key::LightsOff,
+        Key::StatsTitle,
+        Key::StatsHint,
+        Key::StatsSummary,
+        Key::StatsPersonality,
+        Key::StatsBody,
+        Key::StatsPassedAway,
+        Key::StatsHealthy,
+        Key::StatsSick,
+        Key::StatsDiscipline,
+        Key::StatsMisbehaving,
+        Key::StatsMistakes,
+        Key::StatsMistakeAt,
+        Key::StatHunger,
+        Key::StatHappiness,
+        Key::StatHealth,
+        Key::StatWeight,
+        Key::ChartNow,
+        Key::AchievementsTitle,
+        Key::FirstSteps,
+        Key::FirstStepsInfo,
+        Key::SpotlessWeek,
+        Key::SpotlessWeekInfo,
+        Key::Perfectionist,
+        Key::PerfectionistInfo,
+        Key::FullHouse,
+        Key::FullHouseInfo,
+        Key::AllGrownUp,
+        Key::AllGrownUpInfo,
+        Key::GoldenYears,
+        Key::GoldenYearsInfo,
+        Key::GameOn,
+        Key::GameOnInfo,
+        Key::GoodManners,
+        Key::GoodMannersInfo,
+        Key::MakeAWish,
+        Key::MakeAWishInfo,
+        Key::SeenItAll,
+        Key::SeenItAllInfo,
+        Key::PlayTitle,
+        Key::GameGuess,
+        Key::GameNeedle,
+        Key::GameReflex,
+        Key::Round,
+        Key::Score,
+        Key::FinalScore,
+        Key::Reward,
+        Key::RewardFood,
+        Key::Missed,
+        Key::WaitForIt,
+        Key::Now,
+        Key::TooEarly,
+        Key::TooSlow,
+        Key::FoundMe,
+        Key::NotThere,
+        Key::MemorialTitle,
+        Key::MemorialEmpty,
+        Key::MemorialRow,
+        Key::MoreTitle,
+        Key::MoreRename,
+        Key::MoreOwner,
+        Key::MoreOwnerUnset,
+        Key::MoreMemorial,
+        Key::MoreSettings,
+        Key::NamePet,
+        Key::NameOwner,
+        Key::TextEntryHint,
+        Key::Yes,
+        Key::No,
+        Key::SettingsTitle,
+        Key::SettingsBacklight,
+        Key::SettingsLeds,
+        Key::SettingsScreenOff,
+        Key::SettingsReverseDial,
+        Key::SettingsClock,
+        Key::SettingsTimeZone,
+        Key::SettingsLanguage,
+        Key::SettingsHint,
+        Key::SettingsEditHint,
+        Key::SettingsOff,
+        Key::SettingsNever,
+        Key::Clock24,
+        Key::Clock12,
+        Key::MoreClock,
+        Key::MoreAlarm,
+        Key::MoreAlarmOff,
+        Key::MoreTimer,
+        Key::SettingsFace,
+        Key::FaceDigital,
+        Key::FaceAnalogue,
+        Key::ClockUnset,
+        Key::ClockHint,
+        Key::SetClockTitle,
+        Key::SetAlarmTitle,
+        Key::TimeEntryHint,
+        Key::AlarmTitle,
+        Key::AlarmEnabled,
+        Key::AlarmTime,
+        Key::RingingTitle,
+        Key::RingingHint,
+        Key::TimerTitle,
+        Key::PomodoroTitle,
+        Key::TimerWork,
+        Key::TimerBreak,
+        Key::TimerPaused,
+        Key::TimerDone,
+        Key::TimerHintStopped,
+        Key::TimerHintRunning,
+        Key::TimerPomodoros,
+    ];
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.read() {
+        Ok(language) => *language,
+        Err(err) => {
+            log::error!("Failed to gain language read lock, {}", err);
+            Language::default()
+        }
+    }
+}
+
+pub fn set_language(language: Language) {
+    match LANGUAGE.write() {
+        Ok(mut current) => *current = language,
+        Err(err) => log::error!("Failed to gain language write lock, {}", err),
+    }
+}
+
+/// `key` in the current language as written, the `other` form of a plural.
+pub fn text(key: Key) -> &'static str {
+    language().table(key).forms()[1]
+}
+
+/// `key` in the current language with its `{placeholders}` filled in from `args`.
+pub fn format(key: Key, args: &[(&str, &dyn Display)]) -> String {
+    interpolate(text(key), args)
+}
+
+/// Like [`format`] for text that depends on `count`, which also fills in `{count}`.
+pub fn format_count(key: Key, count: u32, args: &[(&str, &dyn Display)]) -> String {
+    let language = language();
+    let forms = language.table(key).forms();
+    let template = match language.plural(count) {
+        PluralForm::One => forms[0],
+        PluralForm::Other => forms[1],
+    };
+
+    let mut args = args.to_vec();
+    args.push(("count", &count));
+    interpolate(template, &args)
+}
+
+/// Replaces each `{name}` with the matching argument, unknown ones are left as they are.
+fn interpolate(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+
+        let placeholder = &rest[start..start + len + 1];
+        match args
+            .iter()
+            .find(|(name, _)| *name == &placeholder[1..placeholder.len() - 1])
+        {
+            Some((_, value)) => text.push_str(&value.to_string()),
+            None => text.push_str(placeholder),
+        }
+        rest = &rest[start + len + 1..];
+    }
+
+    text.push_str(rest);
+    text
+}
+
+/// Whether every character of `text` is in the Latin-1 fonts, leaving out the control characters.
+pub(crate) const fn covered(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        idx += match bytes[idx] {
+            0x20..=0x7e => 1,
+            // U+00A0 to U+00FF
+            0xc2 if idx + 1 < bytes.len() && bytes[idx + 1] >= 0xa0 => 2,
+            0xc3 => 2,
+            _ => return false,
+        };
+    }
+
+    true
+}
+
+/// Whether `haystack` contains `needle[from..to]`.
+const fn contains(haystack: &[u8], needle: &[u8], from: usize, to: usize) -> bool {
+    let len = to - from;
+    let mut start = 0;
+
+    while start + len <= haystack.len() {
+        let mut matched = 0;
+        while matched < len && haystack[start + matched] == needle[from + matched] {
+            matched += 1;
+        }
+        if matched == len {
+            return true;
+        }
+        start += 1;
+    }
+
+    false
+}
+
+/// Whether every `{placeholder}` in `text` is also in one of `forms`.
+const fn placeholders_in(text: &str, forms: [&str; 2]) -> bool {
+    let bytes = text.as_bytes();
+    let mut start = 0;
+
+    while start < bytes.len() {
+        if bytes[start] == b'{' {
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b'}' {
+                end += 1;
+            }
+            if end == bytes.len() {
+                return false;
+            }
+
+            if !contains(forms[0].as_bytes(), bytes, start, end + 1)
+                && !contains(forms[1].as_bytes(), bytes, start, end + 1)
+            {
+                return false;
+            }
+            start = end;
+        }
+        start += 1;
+    }
+
+    true
+}
+
+const _: () = {
+    let mut idx = 0;
+    while idx < Key::ALL.len() {
+        let key = Key::ALL[idx];
+        assert!(key as usize == idx, "Key::ALL is out of order");
+
+        let english = Language::English.table(key).forms();
+        let mut language = 0;
+        while language < Language::ALL.len() {
+            let forms = Language::ALL[language].table(key).forms();
+            let mut form = 0;
+            while form < forms.len() {
+                assert!(covered(forms[form]), "Text has a glyph the fonts don't");
+                assert!(
+                    placeholders_in(forms[form], english),
+                    "Translation has a placeholder the English text doesn't"
+                );
+                assert!(
+                    placeholders_in(english[form], forms),
+                    "Translation is missing a placeholder"
+                );
+                form += 1;
+            }
+            language += 1;
+        }
+        idx += 1;
+    }
+};
@@ -1,11 +1,35 @@
+#[cfg(target_os = "espidf")]
 use esp_idf_svc::hal::gpio::Level;
 
+pub mod achievements;
 pub mod apa102;
+pub mod battery;
+#[cfg(target_os = "espidf")]
+pub mod board;
+pub mod button_gesture;
+#[cfg(target_os = "espidf")]
 pub mod button_interface;
+pub mod clock;
+pub mod console;
 pub mod device;
+pub mod diagnostics;
+pub mod history;
+pub mod i18n;
+pub mod minigame;
+pub mod notifications;
+pub mod persistence;
+pub mod pet;
+pub mod random;
+#[cfg(target_os = "espidf")]
 pub mod rotary_encoder;
+#[cfg(target_os = "espidf")]
+pub mod scenes;
+pub mod settings;
+pub mod supervisor;
 pub mod tft;
+pub mod ui;
 
+#[cfg(target_os = "espidf")]
 pub fn level_into_u8(level: Level) -> u8 {
     if level == Level::High {
         return 1u8;
@@ -14,6 +38,7 @@ pub fn level_into_u8(level: Level) -> u8 {
     0u8
 }
 
+#[cfg(target_os = "espidf")]
 pub fn level_to_bool(level: Level) -> bool {
     level == Level::High
 }
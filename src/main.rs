@@ -1,22 +1,114 @@
-use embedded_graphics::draw_target::DrawTarget;
-use embedded_graphics::geometry::Point;
-use embedded_graphics::pixelcolor::Rgb565;
-use embedded_graphics::prelude::{Primitive, RgbColor};
-use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
-use embedded_graphics::Drawable;
-use esp_idf_svc::hal::gpio::InputPin;
-use esp_idf_svc::hal::gpio::OutputPin;
-use esp_idf_svc::hal::gpio::PinDriver;
 use esp_idf_svc::hal::{delay::FreeRtos, peripherals::Peripherals};
 use esp_idf_svc::log::EspLogger;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
 use esp_idf_svc::sys;
-use jazagotchi::apa102::interface::{led_init, LEDInterface};
-use jazagotchi::apa102::{Brightness, LEDState};
-use jazagotchi::button_interface::{button_init, ButtonInterface};
-use jazagotchi::device::{DevicePowerState, PowerToggle};
-use jazagotchi::rotary_encoder::interface::{rotary_encoder_init, rotary_interface};
-use jazagotchi::tft::{tft_init, App, ST7789};
-use std::cmp::{max, min};
+use jazagotchi::achievements::{Achievements, ACHIEVEMENTS};
+use jazagotchi::apa102::interface::LEDInterface;
+use jazagotchi::apa102::pattern::{Colour, LedLayer, Pattern, PatternPlayer};
+use jazagotchi::apa102::Brightness;
+use jazagotchi::battery::interface::BatteryInterface;
+use jazagotchi::battery::{BatteryEvent, BatteryLevel, ChargeState};
+use jazagotchi::board::Board;
+use jazagotchi::clock::alarm::Alarm;
+use jazagotchi::clock::timer::{Phase, Timer, TimerMode};
+use jazagotchi::clock::{Clock, SystemClock};
+use jazagotchi::console::interface::console_init;
+use jazagotchi::console::Console;
+use jazagotchi::device;
+use jazagotchi::device::power::PowerManager;
+use jazagotchi::device::power_plan::{IdlePolicy, PowerMode, PowerNotice};
+use jazagotchi::diagnostics::interface::{diagnostics_init, OverlayApp};
+use jazagotchi::history::History;
+use jazagotchi::notifications::Notifier;
+use jazagotchi::persistence::nvs::NvsStorage;
+use jazagotchi::persistence::{AutoSave, Store};
+use jazagotchi::pet::catch_up::catch_up;
+use jazagotchi::pet::generations::Graveyard;
+use jazagotchi::pet::Pet;
+use jazagotchi::random::esp::HardwareSeed;
+use jazagotchi::random::Rng;
+use jazagotchi::rotary_encoder::interface::rotary_interface;
+use jazagotchi::scenes::home::HomeScene;
+use jazagotchi::scenes::{Context, SceneManager};
+use jazagotchi::settings::Settings;
+use jazagotchi::supervisor::esp::EspBackend;
+use jazagotchi::supervisor::{RestartCounts, RestartPolicy, Supervisor, SupervisorEvent};
+use jazagotchi::ui::Theme;
+use jazagotchi::{
+    achievements, apa102, button_interface, diagnostics, i18n, persistence, pet, rotary_encoder,
+    tft,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const BATTERY_WARNING_TIME: Duration = Duration::from_secs(5);
+const DIAGNOSTICS_INTERVAL: Duration = Duration::from_secs(5);
+/// Settings are written once they've stopped changing so turning the encoder doesn't wear the
+/// flash.
+const SETTINGS_SAVE_DELAY: Duration = Duration::from_secs(2);
+/// How much longer than the screen the SoC stays awake before light sleeping.
+const SLEEP_AFTER_SCREEN_OFF: Duration = Duration::from_secs(90);
+/// Backlight level in percent and strip pattern while the lights are off.
+const NIGHT_BACKLIGHT: u8 = 5;
+const NIGHT_LIGHT: Pattern = Pattern::Pulse {
+    colour: Colour::new(40, 12, 0),
+    period: Duration::from_secs(8),
+};
+const ALARM_FLASH: Pattern = Pattern::Blink {
+    colour: Colour::WHITE,
+    period: Duration::from_millis(500),
+};
+/// Flashed when a countdown finishes or a pomodoro changes phase.
+const TIMER_FLASH: Pattern = Pattern::Blink {
+    colour: Colour::BLUE,
+    period: Duration::from_millis(300),
+};
+const TIMER_FLASH_TIME: Duration = Duration::from_secs(5);
+
+/// Colour of the timer's gauge on the strip.
+fn timer_colour(timer: &Timer) -> Colour {
+    match (timer.mode(), timer.phase()) {
+        (TimerMode::Countdown, _) => Colour::AMBER,
+        (TimerMode::Pomodoro, Phase::Work) => Colour::RED,
+        (TimerMode::Pomodoro, Phase::Break) => Colour::GREEN,
+    }
+}
+
+/// Dims the display and puts the strip on a night-light while the lights are off.
+fn apply_lights(on: bool, settings: &Settings, leds: &mut PatternPlayer, now: Instant) {
+    if on {
+        tft::set_backlight(settings.backlight);
+        leds.clear(LedLayer::Base);
+    } else {
+        tft::set_backlight(NIGHT_BACKLIGHT);
+        leds.set(LedLayer::Base, NIGHT_LIGHT, now);
+    }
+}
+
+fn idle_policy_for(settings: &Settings) -> IdlePolicy {
+    match settings.sleep_timeout {
+        Some(timeout) => IdlePolicy {
+            screen_off_after: timeout,
+            sleep_after: Some(timeout + SLEEP_AFTER_SCREEN_OFF),
+        },
+        None => IdlePolicy {
+            screen_off_after: Duration::MAX,
+            sleep_after: None,
+        },
+    }
+}
+
+/// Pushes everything except the time settings out to the display, strip and input.
+fn apply_settings(settings: &Settings, leds: &mut PatternPlayer, lit: bool) {
+    i18n::set_language(settings.language);
+    rotary_interface::set_reversed(settings.encoder_reversed);
+    leds.set_brightness(Brightness::new(settings.led_brightness).unwrap_or(Brightness::MAX));
+    // the night light keeps its own backlight level
+    if lit {
+        tft::set_backlight(settings.backlight);
+    }
+}
 
 fn main() -> anyhow::Result<()> {
     sys::link_patches();
@@ -24,123 +116,407 @@ fn main() -> anyhow::Result<()> {
 
     let peripherals = Peripherals::take().unwrap();
 
-    let pwr_pin = PinDriver::output(peripherals.pins.gpio46).unwrap();
-    let mut power_controller = DevicePowerState::new(pwr_pin).unwrap();
-    power_controller.wake().unwrap();
+    let storage = NvsStorage::new(EspDefaultNvsPartition::take()?, "jazagotchi")
+        .map_err(anyhow::Error::msg)?;
+    let store = Arc::new(Mutex::new(Store::new(storage)));
 
-    {
-        let encoder_pin_a = PinDriver::input(peripherals.pins.gpio2.downgrade_input()).unwrap();
-        let encoder_pin_b = PinDriver::input(peripherals.pins.gpio1.downgrade_input()).unwrap();
-        rotary_encoder_init(encoder_pin_a, encoder_pin_b);
+    let restart_counts = store
+        .lock()
+        .unwrap()
+        .load::<RestartCounts>()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to load restart counts, {}", err);
+            None
+        })
+        .unwrap_or_default();
+    let mut supervisor = Supervisor::new(
+        EspBackend::default(),
+        RestartPolicy::default(),
+        restart_counts,
+    );
+
+    let board = Board::init(peripherals);
+    let config = board.config;
+    let mut power = PowerManager::new(
+        board.power,
+        config.wake_sources(Some(Duration::from_secs(60))),
+    );
+
+    for task in board.tasks {
+        supervisor
+            .spawn(task, Instant::now())
+            .map_err(anyhow::Error::msg)?;
     }
 
-    {
-        let button = PinDriver::input(peripherals.pins.gpio0).unwrap();
-        button_init(button);
+    supervisor
+        .spawn(diagnostics_init(DIAGNOSTICS_INTERVAL), Instant::now())
+        .map_err(anyhow::Error::msg)?;
+
+    let mut store_guard = store.lock().unwrap();
+
+    let clock = Arc::new(SystemClock);
+
+    let settings = store_guard
+        .load::<Settings>()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to load settings, {}", err);
+            None
+        })
+        .unwrap_or_default();
+    i18n::set_language(settings.language);
+
+    let mut pet = match store_guard.load::<Pet>() {
+        Ok(Some(pet)) => pet,
+        Ok(None) => Pet::new(String::from("Jaza"), Rng::from_source(&HardwareSeed)),
+        Err(err) => {
+            log::error!("Failed to load pet, starting a new one, {}", err);
+            Pet::new(String::from("Jaza"), Rng::from_source(&HardwareSeed))
+        }
+    };
+
+    let mut achievements = store_guard
+        .load::<Achievements>()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to load achievements, {}", err);
+            None
+        })
+        .unwrap_or_default();
+
+    let mut history = store_guard
+        .load::<History>()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to load history, {}", err);
+            None
+        })
+        .unwrap_or_default();
+
+    let graveyard = store_guard
+        .load::<Graveyard>()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to load graveyard, {}", err);
+            None
+        })
+        .unwrap_or_default();
+
+    let alarm = store_guard
+        .load::<Alarm>()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to load alarm, {}", err);
+            None
+        })
+        .unwrap_or_default();
+
+    let mut caught_up = vec![];
+    if let Some(last_seen) = pet.last_seen {
+        match clock.now().since(last_seen) {
+            Some(elapsed) => {
+                let now = clock.now().local_time(settings.utc_offset);
+                let summary = catch_up(&mut pet, elapsed, now);
+                caught_up = summary.messages(&pet.name);
+                for message in &caught_up {
+                    log::info!("{}", message);
+                }
+                achievements.record_pet(&pet, &summary.events);
+                history.skip(elapsed);
+            }
+            None => log::warn!("Clock is behind the last save, skipping catch up"),
+        }
     }
+    pet.last_seen = Some(clock.now());
+
+    let pet = Arc::new(Mutex::new(pet));
+    let achievements = Arc::new(Mutex::new(achievements));
+    let history = Arc::new(Mutex::new(history));
+    let graveyard = Arc::new(Mutex::new(graveyard));
+    let mut saved_alarm = (alarm.time(), alarm.enabled());
+    let alarm = Arc::new(Mutex::new(alarm));
+    let timer = Arc::new(Mutex::new(Timer::default()));
+    let mut saved_settings = settings.clone();
+    let settings = Arc::new(Mutex::new(settings));
+
+    drop(store_guard);
 
     {
-        let led_clk = PinDriver::output(peripherals.pins.gpio45.downgrade_output()).unwrap();
-        let led_do = PinDriver::output(peripherals.pins.gpio42.downgrade_output()).unwrap();
-        led_init(led_clk, led_do);
+        let pet = pet.clone();
+        let achievements = achievements.clone();
+        let graveyard = graveyard.clone();
+        let history = history.clone();
+        let settings = settings.clone();
+        let alarm = alarm.clone();
+        let store = store.clone();
+        power.subscribe(Box::new(move |notice| {
+            if let PowerNotice::Before(PowerMode::LightSleep | PowerMode::DeepSleep) = notice {
+                let pet = pet.lock().unwrap();
+                let mut store = store.lock().unwrap();
+                if let Err(err) = store.save(&*pet) {
+                    log::error!("Failed to save before sleeping, {}", err);
+                }
+                if let Err(err) = store.save(&*achievements.lock().unwrap()) {
+                    log::error!("Failed to save achievements before sleeping, {}", err);
+                }
+                if let Err(err) = store.save(&*graveyard.lock().unwrap()) {
+                    log::error!("Failed to save graveyard before sleeping, {}", err);
+                }
+                if let Err(err) = store.save(&*history.lock().unwrap()) {
+                    log::error!("Failed to save history before sleeping, {}", err);
+                }
+                // settings are otherwise only saved once they've settled
+                if let Err(err) = store.save(&*settings.lock().unwrap()) {
+                    log::error!("Failed to save settings before sleeping, {}", err);
+                }
+                if let Err(err) = store.save(&*alarm.lock().unwrap()) {
+                    log::error!("Failed to save alarm before sleeping, {}", err);
+                }
+            }
+        }));
+    }
+
+    let mut idle_policy = idle_policy_for(&saved_settings);
+    let leds = Arc::new(Mutex::new(PatternPlayer::new(
+        config.led_count as usize,
+        Brightness::MAX,
+    )));
+    apply_settings(&saved_settings, &mut leds.lock().unwrap(), false);
+    let mut applied_settings = saved_settings.clone();
+    let mut settings_changed = Instant::now();
+
+    if let Some(display) = board.display {
+        let ctx = Context {
+            pet: pet.clone(),
+            leds: leds.clone(),
+            theme: Theme::default(),
+            seeds: Arc::new(HardwareSeed),
+            achievements: achievements.clone(),
+            history: history.clone(),
+            graveyard: graveyard.clone(),
+            settings: settings.clone(),
+            clock: clock.clone(),
+            alarm: alarm.clone(),
+            timer: timer.clone(),
+            toasts: Arc::new(Mutex::new(caught_up)),
+            has_battery: config.has_battery,
+        };
+        let tft = display.start(Box::new(move || {
+            Box::new(OverlayApp::new(SceneManager::new(
+                ctx,
+                Box::new(HomeScene::new()),
+            )))
+        }));
+        supervisor
+            .spawn(tft, Instant::now())
+            .map_err(anyhow::Error::msg)?;
     }
 
     {
-        let lcd_bl = peripherals.pins.gpio15.downgrade_output();
-        let lcd_dc = peripherals.pins.gpio13.downgrade_output();
-        let lcd_cs = peripherals.pins.gpio10.downgrade_output();
-        let lcd_clk = peripherals.pins.gpio12.downgrade_output();
-        let lcd_sdo = peripherals.pins.gpio11.downgrade_output();
-        let lcd_rst = peripherals.pins.gpio9.downgrade_output();
-
-        tft_init(
-            peripherals.spi2,
-            lcd_clk,
-            lcd_sdo,
-            lcd_cs,
-            lcd_bl,
-            lcd_dc,
-            lcd_rst,
-            Box::new(|| Box::new(TestApp { counter: 0 })),
-        );
+        let mut console = Console::new();
+        diagnostics::interface::register_commands(&mut console);
+        button_interface::register_commands(&mut console);
+        rotary_encoder::interface::register_commands(&mut console);
+        tft::register_commands(&mut console);
+        apa102::commands::register_commands(&mut console, leds.clone());
+        pet::commands::register_commands(&mut console, pet.clone());
+        pet::commands::register_generation_commands(&mut console, pet.clone(), graveyard.clone());
+        achievements::commands::register_commands(&mut console, achievements.clone());
+        persistence::commands::register_commands(&mut console, store.clone(), pet.clone());
+        supervisor
+            .spawn(console_init(console), Instant::now())
+            .map_err(anyhow::Error::msg)?;
     }
 
+    let mut last_tick = Instant::now();
+    let mut autosave = AutoSave::new(AUTOSAVE_INTERVAL, last_tick);
+    let mut lit = None;
+    let mut generation = pet.lock().unwrap().generation;
+    let mut notifier = Notifier::new();
+    let mut ringing = false;
+    let mut timing = false;
+
     loop {
         FreeRtos::delay_ms(10);
-    }
-}
 
-struct TestApp {
-    counter: u8,
-}
+        let now = Instant::now();
+        let settings = settings.lock().unwrap().clone();
+        if settings != applied_settings {
+            apply_settings(&settings, &mut leds.lock().unwrap(), lit == Some(true));
+            idle_policy = idle_policy_for(&settings);
+            applied_settings = settings.clone();
+            settings_changed = now;
+        }
+        if settings != saved_settings && now.duration_since(settings_changed) >= SETTINGS_SAVE_DELAY
+        {
+            if let Err(err) = store.lock().unwrap().save(&settings) {
+                log::error!("Failed to save settings, {}", err);
+            }
+            saved_settings = settings.clone();
+        }
 
-impl App for TestApp {
-    fn update(&mut self, display: &mut ST7789) {
-        led_circle_thingy();
-
-        let circle1 = Circle::new(Point::new(self.counter as i32, self.counter as i32), 64)
-            .into_styled(PrimitiveStyle::with_fill(Rgb565::RED));
-        display
-            .fill_solid(
-                &Rectangle::with_corners(
-                    circle1.fill_area().top_left - Point::new(1, 1),
-                    Point::new(
-                        circle1.fill_area().top_left.x + circle1.fill_area().diameter as i32,
-                        circle1.fill_area().top_left.y + circle1.fill_area().diameter as i32,
-                    ),
-                ),
-                Rgb565::BLACK,
-            )
-            .unwrap();
+        {
+            let mut pet = pet.lock().unwrap();
+            let local_time = clock.now().local_time(settings.utc_offset);
+            let elapsed = now.duration_since(last_tick);
+            let events = pet.tick(elapsed, local_time);
+            for event in &events {
+                log::info!("{}: {:?}", pet.name, event);
+            }
+            pet.last_seen = Some(clock.now());
+            last_tick = now;
 
-        let val = match rotary_interface::get_position() {
-            Ok(data) => -data,
-            Err(err) => {
-                log::error!("{}", err);
-                0
+            let mut achievements = achievements.lock().unwrap();
+            let unlocked = achievements.record_pet(&pet, &events);
+            for idx in &unlocked {
+                log::info!("Unlocked {:?}", ACHIEVEMENTS[*idx].name);
             }
-        };
 
-        self.counter = ((self.counter as i8 + val) % 100) as u8;
+            // a new egg is saved straight away along with the pet it replaced
+            let passed_on = pet.generation != generation;
+            if autosave.is_due(now) || !unlocked.is_empty() || passed_on {
+                let mut store = store.lock().unwrap();
+                if let Err(err) = store.save(&*pet) {
+                    log::error!("Autosave failed, {}", err);
+                }
+                if let Err(err) = store.save(&*achievements) {
+                    log::error!("Failed to save achievements, {}", err);
+                }
+                if passed_on {
+                    generation = pet.generation;
+                    if let Err(err) = store.save(&*graveyard.lock().unwrap()) {
+                        log::error!("Failed to save graveyard, {}", err);
+                    }
+                }
+                autosave.restart(now);
+            }
+            drop(achievements);
 
-        circle1.draw(display).unwrap();
-    }
-}
+            let mut history = history.lock().unwrap();
+            if history.record(elapsed, &pet.stats) {
+                if let Err(err) = store.lock().unwrap().save(&*history) {
+                    log::error!("Failed to save history, {}", err);
+                }
+            }
+            drop(history);
 
-fn led_circle_thingy() {
-    let mut vec: Vec<LEDState> = vec![];
+            // a call lights the screen up even with the lights off
+            let call = pet.calls.current();
+            let on = pet.sleep.lights_on || call.is_some();
+            let mut leds = leds.lock().unwrap();
+            if lit != Some(on) {
+                lit = Some(on);
+                apply_lights(on, &settings, &mut leds, now);
+            }
+            if let Some(kind) = notifier.update(call, &mut leds, now) {
+                log::info!("{}", kind.message(&pet.name));
+                device::mark_activity();
+            }
+        }
 
-    let val = match rotary_interface::get_position() {
-        Ok(data) => -data,
-        Err(err) => {
-            log::error!("{}", err);
-            0
+        {
+            let mut alarm = alarm.lock().unwrap();
+            let mut timer = timer.lock().unwrap();
+            let mut leds = leds.lock().unwrap();
+
+            if alarm.update(&*clock, settings.utc_offset) {
+                log::info!("Alarm ringing");
+                device::mark_activity();
+            }
+            if alarm.is_ringing() {
+                leds.set(LedLayer::Alarm, ALARM_FLASH, now);
+            } else if ringing {
+                leds.clear(LedLayer::Alarm);
+            }
+            ringing = alarm.is_ringing();
+
+            if (alarm.time(), alarm.enabled()) != saved_alarm {
+                if let Err(err) = store.lock().unwrap().save(&*alarm) {
+                    log::error!("Failed to save alarm, {}", err);
+                }
+                saved_alarm = (alarm.time(), alarm.enabled());
+            }
+
+            if let Some(event) = timer.update(&*clock) {
+                log::info!("Timer {:?}", event);
+                leds.set_for(LedLayer::Alarm, TIMER_FLASH, now, TIMER_FLASH_TIME);
+                device::mark_activity();
+            }
+            timing = timer.is_running() || timer.is_paused();
+            if timing {
+                let gauge = Pattern::Gauge {
+                    colour: timer_colour(&timer),
+                    filled: timer.remaining(&*clock),
+                };
+                leds.set(LedLayer::Timer, gauge, now);
+            } else {
+                leds.clear(LedLayer::Timer);
+            }
         }
-    };
 
-    let val = max(min(val, 7), 0);
+        for event in supervisor.poll(now) {
+            match event {
+                SupervisorEvent::Exited { name, exit } => {
+                    log::error!("{} stopped, {:?}", name, exit)
+                }
+                SupervisorEvent::Restarted { name, restarts } => {
+                    log::warn!("Restarted {}, {} restarts so far", name, restarts)
+                }
+                SupervisorEvent::Stalled { name, stalls } => {
+                    log::error!("{} stalled, {} times since boot", name, stalls)
+                }
+                SupervisorEvent::GaveUp { name } => log::error!("Gave up restarting {}", name),
+            }
 
-    let state = ButtonInterface::get_toggle_state();
+            if let SupervisorEvent::Restarted { .. } = event {
+                if let Err(err) = store.lock().unwrap().save(supervisor.counts()) {
+                    log::error!("Failed to save restart counts, {}", err);
+                }
+            }
+        }
 
-    for _ in 0..val {
-        vec.push(LEDState {
-            brightness: Brightness::MAX,
-            blue: if state { 100 } else { 0 },
-            red: if state { 0 } else { 50 },
-            green: if state { 0 } else { 50 },
-        });
-    }
-    for _ in val..7 {
-        vec.push(LEDState {
-            brightness: Brightness::MAX,
-            blue: 0,
-            red: if state { 100 } else { 0 },
-            green: if state { 0 } else { 100 },
-        });
-    }
+        let mut leds = leds.lock().unwrap();
+        while let Some(event) = BatteryInterface::take_event().filter(|_| config.has_battery) {
+            match event {
+                BatteryEvent::Level(BatteryLevel::Low) => leds.set_for(
+                    LedLayer::Warning,
+                    Pattern::Blink {
+                        colour: Colour::AMBER,
+                        period: Duration::from_secs(1),
+                    },
+                    now,
+                    BATTERY_WARNING_TIME,
+                ),
+                BatteryEvent::Level(BatteryLevel::Critical) => {
+                    log::warn!("Battery critical, saving and going to sleep");
+                    if let Err(err) = power.set_mode(PowerMode::DeepSleep) {
+                        log::error!("Failed to sleep on critical battery, {}", err);
+                    }
+                }
+                BatteryEvent::Level(BatteryLevel::Ok)
+                | BatteryEvent::Charge(ChargeState::Charging | ChargeState::External) => {
+                    leds.clear(LedLayer::Warning)
+                }
+                BatteryEvent::Charge(ChargeState::Discharging) => {}
+            }
+        }
 
-    match LEDInterface::set_led_vec(vec) {
-        Ok(_) => {}
-        Err(err) => log::error!("{}", err),
-    };
+        if let Some(frame) = leds.frame(now) {
+            if let Err(err) = LEDInterface::set_led_vec(frame) {
+                log::error!("{}", err);
+            }
+        }
+        drop(leds);
+
+        // stay awake until the call is answered or times out and until the alarm stops, and keep
+        // the strip going while the timer shows on it
+        let mode = match (notifier.showing(), ringing) {
+            (None, false) => idle_policy.mode_for(device::idle_for()),
+            _ => PowerMode::Active,
+        };
+        let mode = match mode {
+            PowerMode::LightSleep if timing => PowerMode::ScreenOff,
+            mode => mode,
+        };
+        if mode != power.mode() {
+            if let Err(err) = power.set_mode(mode) {
+                log::error!("Failed to switch to {:?}, {}", mode, err);
+            }
+        }
+    }
 }
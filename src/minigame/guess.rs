@@ -0,0 +1,319 @@
+use crate::i18n::{self, Key};
+use crate::minigame::{GameResult, MiniGame};
+use crate::random::Rng;
+use crate::ui::{Input, Theme};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::MonoTextStyleBuilder;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+pub const ROUNDS: u32 = 5;
+const REVEAL_TIME: Duration = Duration::from_secs(1);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Phase {
+    Choosing,
+    Reveal { pet: Side, remaining: Duration },
+    Done,
+}
+
+/// The pet hides behind one of two doors, turn the encoder to pick a side and click to check.
+pub struct GuessSide {
+    bounds: Rectangle,
+    rng: Rng,
+    round: u32,
+    score: u32,
+    choice: Option<Side>,
+    phase: Phase,
+    dirty: bool,
+}
+
+impl GuessSide {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            rng: Rng::new(0),
+            round: 0,
+            score: 0,
+            choice: None,
+            phase: Phase::Done,
+            dirty: true,
+        }
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    fn door(&self, side: Side) -> Rectangle {
+        let width = self.bounds.size.width / 3;
+        let height = self.bounds.size.height * 3 / 5;
+        let x = match side {
+            Side::Left => self.bounds.size.width / 6,
+            Side::Right => self.bounds.size.width - self.bounds.size.width / 6 - width,
+        };
+
+        Rectangle::new(
+            self.bounds.top_left + Point::new(x as i32, (self.bounds.size.height / 4) as i32),
+            Size::new(width, height),
+        )
+    }
+}
+
+impl MiniGame for GuessSide {
+    fn title(&self) -> &'static str {
+        i18n::text(Key::GameGuess)
+    }
+
+    fn start(&mut self, seed: u32) {
+        self.rng = Rng::new(seed);
+        self.round = 0;
+        self.score = 0;
+        self.choice = None;
+        self.phase = Phase::Choosing;
+        self.dirty = true;
+    }
+
+    fn input(&mut self, input: Input) {
+        if self.phase != Phase::Choosing {
+            return;
+        }
+
+        match input {
+            Input::Rotate(detents) if detents < 0 => self.choice = Some(Side::Left),
+            Input::Rotate(detents) if detents > 0 => self.choice = Some(Side::Right),
+            Input::Click => {
+                let Some(choice) = self.choice else {
+                    return;
+                };
+                let pet = if self.rng.next_u32() & 1 == 0 {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+
+                if pet == choice {
+                    self.score += 1;
+                }
+                self.phase = Phase::Reveal {
+                    pet,
+                    remaining: REVEAL_TIME,
+                };
+            }
+            _ => return,
+        }
+        self.dirty = true;
+    }
+
+    fn tick(&mut self, elapsed: Duration) {
+        if let Phase::Reveal { remaining, .. } = &mut self.phase {
+            *remaining = remaining.saturating_sub(elapsed);
+
+            if remaining.is_zero() {
+                self.round += 1;
+                self.choice = None;
+                self.phase = if self.round >= ROUNDS {
+                    Phase::Done
+                } else {
+                    Phase::Choosing
+                };
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn result(&self) -> Option<GameResult> {
+        match self.phase {
+            Phase::Done if self.round >= ROUNDS => Some(GameResult::new(self.score, ROUNDS)),
+            _ => None,
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+        self.dirty = false;
+
+        let centred = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+        let centre_x = self.bounds.center().x;
+
+        let heading = match self.phase {
+            Phase::Reveal { pet, .. } if Some(pet) == self.choice => {
+                String::from(i18n::text(Key::FoundMe))
+            }
+            Phase::Reveal { .. } => String::from(i18n::text(Key::NotThere)),
+            _ => i18n::format(
+                Key::Round,
+                &[
+                    ("round", &(self.round + 1).min(ROUNDS)),
+                    ("rounds", &ROUNDS),
+                ],
+            ),
+        };
+        Text::with_text_style(
+            &heading,
+            Point::new(centre_x, self.bounds.top_left.y + 4),
+            theme.title_style(),
+            centred,
+        )
+        .draw(target)?;
+
+        for side in [Side::Left, Side::Right] {
+            let door = self.door(side);
+            let selected = self.choice == Some(side);
+            let fill = if selected {
+                theme.accent
+            } else {
+                theme.inactive
+            };
+            door.into_styled(PrimitiveStyle::with_fill(fill))
+                .draw(target)?;
+
+            let label = match self.phase {
+                Phase::Reveal { pet, .. } if pet == side => "^_^",
+                _ if side == Side::Left => "<",
+                _ => ">",
+            };
+            let style = MonoTextStyleBuilder::new()
+                .font(theme.title_font)
+                .text_color(if selected {
+                    theme.on_accent
+                } else {
+                    theme.foreground
+                })
+                .background_color(fill)
+                .build();
+            Text::with_text_style(
+                label,
+                door.center() - Point::new(0, theme.title_font.character_size.height as i32 / 2),
+                style,
+                centred,
+            )
+            .draw(target)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rectangle = Rectangle::new(Point::zero(), Size::new(200, 120));
+
+    /// Where the pet hides each round for `seed`, drawn the same way the game does.
+    fn hiding_places(seed: u32) -> Vec<Side> {
+        let mut rng = Rng::new(seed);
+        (0..ROUNDS)
+            .map(|_| match rng.next_u32() & 1 {
+                0 => Side::Left,
+                _ => Side::Right,
+            })
+            .collect()
+    }
+
+    fn pick(game: &mut GuessSide, side: Side) {
+        let detents = match side {
+            Side::Left => -1,
+            Side::Right => 1,
+        };
+        game.input(Input::Rotate(detents));
+        game.input(Input::Click);
+        game.tick(REVEAL_TIME);
+    }
+
+    #[test]
+    fn always_left_scores_the_lefts() {
+        let mut game = GuessSide::new(BOUNDS);
+        game.start(1234);
+
+        for _ in 0..ROUNDS {
+            assert_eq!(game.result(), None);
+            pick(&mut game, Side::Left);
+        }
+
+        let lefts = hiding_places(1234)
+            .into_iter()
+            .filter(|side| *side == Side::Left)
+            .count() as u32;
+        assert_eq!(game.round(), ROUNDS);
+        assert_eq!(game.result(), Some(GameResult::new(lefts, ROUNDS)));
+    }
+
+    #[test]
+    fn knowing_the_seed_wins() {
+        let mut game = GuessSide::new(BOUNDS);
+        game.start(99);
+
+        for side in hiding_places(99) {
+            pick(&mut game, side);
+        }
+
+        assert!(game.result().unwrap().is_perfect());
+    }
+
+    #[test]
+    fn click_needs_a_choice() {
+        let mut game = GuessSide::new(BOUNDS);
+        game.start(5);
+
+        game.input(Input::Click);
+        game.tick(REVEAL_TIME);
+        assert_eq!(game.round(), 0);
+
+        // the choice is cleared for the next round
+        pick(&mut game, Side::Right);
+        assert_eq!(game.round(), 1);
+        game.input(Input::Click);
+        game.tick(REVEAL_TIME);
+        assert_eq!(game.round(), 1);
+    }
+
+    #[test]
+    fn choice_is_locked_during_the_reveal() {
+        let mut game = GuessSide::new(BOUNDS);
+        game.start(5);
+
+        game.input(Input::Rotate(1));
+        game.input(Input::Click);
+        game.input(Input::Rotate(-1));
+        game.input(Input::Click);
+        assert_eq!(game.choice, Some(Side::Right));
+
+        game.tick(REVEAL_TIME / 2);
+        assert_eq!(game.round(), 0);
+        game.tick(REVEAL_TIME / 2);
+        assert_eq!(game.round(), 1);
+    }
+}
@@ -0,0 +1,136 @@
+//! Short games played with the encoder and button to cheer the pet up.
+//!
+//! Games only see [`Input`] and the time passed in [`MiniGame::tick`], so a whole round can be
+//! scripted off the device. Drawing goes to any `Rgb565` target inside the bounds they were made
+//! with.
+
+pub mod guess;
+pub mod needle;
+pub mod reflex;
+
+use crate::apa102::pattern::Pattern;
+use crate::pet::food::FoodKind;
+use crate::ui::{Input, Theme};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::primitives::Rectangle;
+use std::time::Duration;
+
+/// Happiness for a perfect game, anything else scales down to [`MIN_REWARD`].
+pub const MAX_REWARD: u8 = 25;
+/// Playing at all is worth something.
+pub const MIN_REWARD: u8 = 5;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GameResult {
+    pub score: u32,
+    pub max_score: u32,
+    pub happiness: u8,
+}
+
+impl GameResult {
+    pub fn new(score: u32, max_score: u32) -> Self {
+        let score = score.min(max_score);
+        let bonus = (MAX_REWARD - MIN_REWARD) as u32 * score / max_score.max(1);
+
+        Self {
+            score,
+            max_score,
+            happiness: MIN_REWARD + bonus as u8,
+        }
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        self.score == self.max_score
+    }
+
+    /// Food won on top of the happiness, a treat for a perfect game and a snack for half marks.
+    pub fn food_reward(&self) -> Option<FoodKind> {
+        if self.is_perfect() {
+            Some(FoodKind::Treat)
+        } else if self.score * 2 >= self.max_score {
+            Some(FoodKind::Snack)
+        } else {
+            None
+        }
+    }
+}
+
+pub trait MiniGame {
+    fn title(&self) -> &'static str;
+
+    /// Resets the game to the first round.
+    fn start(&mut self, seed: u32);
+
+    fn input(&mut self, input: Input);
+
+    fn tick(&mut self, elapsed: Duration);
+
+    /// Set once the last round is over.
+    fn result(&self) -> Option<GameResult>;
+
+    /// Pattern the LED strip should show right now, `None` leaves it alone.
+    fn leds(&self) -> Option<Pattern> {
+        None
+    }
+
+    fn bounds(&self) -> Rectangle;
+
+    fn is_dirty(&self) -> bool;
+
+    fn invalidate(&mut self);
+
+    /// Draws whatever changed since the last draw, everything after [`MiniGame::invalidate`].
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>;
+
+    fn redraw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        if self.is_dirty() {
+            self.draw(target, theme)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn happiness_scales_with_the_score() {
+        assert_eq!(GameResult::new(0, 6).happiness, MIN_REWARD);
+        assert_eq!(GameResult::new(3, 6).happiness, 15);
+        assert_eq!(GameResult::new(6, 6).happiness, MAX_REWARD);
+
+        let over = GameResult::new(9, 6);
+        assert_eq!(over.score, 6);
+        assert_eq!(over.happiness, MAX_REWARD);
+    }
+
+    #[test]
+    fn food_reward_thresholds() {
+        let rewards: Vec<Option<FoodKind>> = (0..=5)
+            .map(|score| GameResult::new(score, 5).food_reward())
+            .collect();
+        assert_eq!(
+            rewards,
+            vec![
+                None,
+                None,
+                None,
+                Some(FoodKind::Snack),
+                Some(FoodKind::Snack),
+                Some(FoodKind::Treat),
+            ]
+        );
+
+        // exactly half is enough for a snack
+        assert_eq!(GameResult::new(3, 6).food_reward(), Some(FoodKind::Snack));
+        assert_eq!(GameResult::new(2, 6).food_reward(), None);
+    }
+}
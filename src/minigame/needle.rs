@@ -0,0 +1,355 @@
+use crate::i18n::{self, Key};
+use crate::minigame::{GameResult, MiniGame};
+use crate::random::Rng;
+use crate::ui::{Input, Theme};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle, Rectangle, Sector};
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+pub const ROUNDS: u32 = 3;
+/// Degrees per second for each round.
+const SPEEDS: [f32; ROUNDS as usize] = [180.0, 270.0, 360.0];
+const ZONE_WIDTH: f32 = 40.0;
+const RESULT_TIME: Duration = Duration::from_secs(1);
+/// Points for stopping in the zone, and for hitting its middle quarter.
+const ZONE_POINTS: u32 = 1;
+const CENTRE_POINTS: u32 = 2;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Phase {
+    Spinning,
+    Stopped { points: u32, remaining: Duration },
+    Done,
+}
+
+/// A needle sweeps round a dial, click to stop it inside the highlighted zone. It speeds up
+/// every round.
+pub struct Needle {
+    bounds: Rectangle,
+    rng: Rng,
+    round: u32,
+    score: u32,
+    /// Clockwise from three o'clock, like `embedded_graphics` angles.
+    angle: f32,
+    zone_start: f32,
+    phase: Phase,
+    dirty: bool,
+    full_redraw: bool,
+    drawn_angle: Option<f32>,
+}
+
+impl Needle {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            rng: Rng::new(0),
+            round: 0,
+            score: 0,
+            angle: 0.0,
+            zone_start: 0.0,
+            phase: Phase::Done,
+            dirty: true,
+            full_redraw: true,
+            drawn_angle: None,
+        }
+    }
+
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    pub fn zone_start(&self) -> f32 {
+        self.zone_start
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Points for stopping at `angle`, by its distance from the middle of the zone.
+    pub fn points_at(&self, angle: f32) -> u32 {
+        let centre = self.zone_start + ZONE_WIDTH / 2.0;
+        let distance = (angle - centre).rem_euclid(360.0);
+        let distance = distance.min(360.0 - distance);
+
+        if distance <= ZONE_WIDTH / 8.0 {
+            CENTRE_POINTS
+        } else if distance <= ZONE_WIDTH / 2.0 {
+            ZONE_POINTS
+        } else {
+            0
+        }
+    }
+
+    fn next_round(&mut self) {
+        self.angle = 0.0;
+        // keep the zone away from where the needle starts
+        self.zone_start = self.rng.range(60, 300 - ZONE_WIDTH as u32) as f32;
+        self.phase = Phase::Spinning;
+        self.full_redraw = true;
+        self.dirty = true;
+    }
+
+    fn dial(&self) -> (Point, u32) {
+        let diameter = self.bounds.size.height.min(self.bounds.size.width) - 8;
+        (self.bounds.center(), diameter)
+    }
+
+    fn needle_line(&self, angle: f32) -> Line {
+        let (centre, diameter) = self.dial();
+        let length = (diameter / 2 - 6) as f32;
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        Line::new(
+            centre,
+            centre + Point::new((cos * length) as i32, (sin * length) as i32),
+        )
+    }
+}
+
+impl MiniGame for Needle {
+    fn title(&self) -> &'static str {
+        i18n::text(Key::GameNeedle)
+    }
+
+    fn start(&mut self, seed: u32) {
+        self.rng = Rng::new(seed);
+        self.round = 0;
+        self.score = 0;
+        self.next_round();
+    }
+
+    fn input(&mut self, input: Input) {
+        if self.phase == Phase::Spinning && input == Input::Click {
+            let points = self.points_at(self.angle);
+            self.score += points;
+            self.phase = Phase::Stopped {
+                points,
+                remaining: RESULT_TIME,
+            };
+            self.full_redraw = true;
+            self.dirty = true;
+        }
+    }
+
+    fn tick(&mut self, elapsed: Duration) {
+        match &mut self.phase {
+            Phase::Spinning => {
+                let speed = SPEEDS[self.round as usize];
+                self.angle = (self.angle + speed * elapsed.as_secs_f32()).rem_euclid(360.0);
+                self.dirty = true;
+            }
+            Phase::Stopped { remaining, .. } => {
+                *remaining = remaining.saturating_sub(elapsed);
+
+                if remaining.is_zero() {
+                    self.round += 1;
+                    if self.round >= ROUNDS {
+                        self.phase = Phase::Done;
+                        self.dirty = true;
+                    } else {
+                        self.next_round();
+                    }
+                }
+            }
+            Phase::Done => {}
+        }
+    }
+
+    fn result(&self) -> Option<GameResult> {
+        match self.phase {
+            Phase::Done if self.round >= ROUNDS => {
+                Some(GameResult::new(self.score, ROUNDS * CENTRE_POINTS))
+            }
+            _ => None,
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.full_redraw = true;
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let (centre, diameter) = self.dial();
+        let needle_style = PrimitiveStyle::with_stroke(theme.foreground, 3);
+        let erase_style = PrimitiveStyle::with_stroke(theme.background, 3);
+        let zone = Sector::with_center(
+            centre,
+            diameter - 4,
+            Angle::from_degrees(self.zone_start),
+            Angle::from_degrees(ZONE_WIDTH),
+        )
+        .into_styled(PrimitiveStyle::with_fill(theme.accent));
+
+        if self.full_redraw {
+            target.fill_solid(&self.bounds, theme.background)?;
+            Circle::with_center(centre, diameter)
+                .into_styled(PrimitiveStyle::with_stroke(theme.foreground, 2))
+                .draw(target)?;
+
+            let status = match self.phase {
+                Phase::Stopped { points: 0, .. } => String::from(i18n::text(Key::Missed)),
+                Phase::Stopped { points, .. } => format!("+{}", points),
+                _ => i18n::format(
+                    Key::Round,
+                    &[
+                        ("round", &(self.round + 1).min(ROUNDS)),
+                        ("rounds", &ROUNDS),
+                    ],
+                ),
+            };
+            Text::with_baseline(
+                &status,
+                self.bounds.top_left + Point::new(4, 4),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(target)?;
+            Text::with_baseline(
+                &i18n::format(Key::Score, &[("score", &self.score)]),
+                self.bounds.top_left + Point::new(4, 4 + theme.line_height() as i32),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(target)?;
+
+            self.drawn_angle = None;
+            self.full_redraw = false;
+        } else if let Some(drawn) = self.drawn_angle {
+            self.needle_line(drawn)
+                .into_styled(erase_style)
+                .draw(target)?;
+        }
+
+        zone.draw(target)?;
+        self.needle_line(self.angle)
+            .into_styled(needle_style)
+            .draw(target)?;
+        self.drawn_angle = Some(self.angle);
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rectangle = Rectangle::new(Point::zero(), Size::new(200, 120));
+
+    fn started(seed: u32) -> Needle {
+        let mut game = Needle::new(BOUNDS);
+        game.start(seed);
+        game
+    }
+
+    /// Lets the needle sweep from the start of the round to `offset` degrees past the start of
+    /// the zone, then stops it there.
+    fn stop_at(game: &mut Needle, offset: f32) {
+        let speed = SPEEDS[game.round as usize];
+        let angle = game.zone_start() + offset;
+        game.tick(Duration::from_secs_f32(angle / speed));
+        game.input(Input::Click);
+        game.tick(RESULT_TIME);
+    }
+
+    #[test]
+    fn points_by_distance_from_the_centre() {
+        let game = started(7);
+        let zone = game.zone_start();
+
+        assert_eq!(game.points_at(zone + 20.0), CENTRE_POINTS);
+        assert_eq!(game.points_at(zone + 25.0), CENTRE_POINTS);
+        assert_eq!(game.points_at(zone + 15.0), CENTRE_POINTS);
+        assert_eq!(game.points_at(zone + 26.0), ZONE_POINTS);
+        assert_eq!(game.points_at(zone), ZONE_POINTS);
+        assert_eq!(game.points_at(zone + ZONE_WIDTH), ZONE_POINTS);
+        assert_eq!(game.points_at(zone - 1.0), 0);
+        assert_eq!(game.points_at(zone + ZONE_WIDTH + 1.0), 0);
+        assert_eq!(game.points_at(zone + 180.0), 0);
+    }
+
+    #[test]
+    fn zone_across_zero_degrees() {
+        let mut game = started(7);
+        game.zone_start = 350.0;
+
+        assert_eq!(game.points_at(10.0), CENTRE_POINTS);
+        assert_eq!(game.points_at(355.0), ZONE_POINTS);
+        assert_eq!(game.points_at(25.0), ZONE_POINTS);
+        assert_eq!(game.points_at(31.0), 0);
+    }
+
+    #[test]
+    fn zone_stays_away_from_the_start() {
+        for seed in 1..50 {
+            let game = started(seed);
+            assert!((60.0..=260.0).contains(&game.zone_start()));
+            assert_eq!(game.points_at(0.0), 0);
+        }
+    }
+
+    #[test]
+    fn three_rounds_add_up() {
+        let mut game = started(42);
+
+        stop_at(&mut game, ZONE_WIDTH / 2.0);
+        assert_eq!(game.score(), CENTRE_POINTS);
+
+        stop_at(&mut game, 1.0);
+        assert_eq!(game.score(), CENTRE_POINTS + ZONE_POINTS);
+        assert_eq!(game.result(), None);
+
+        stop_at(&mut game, -10.0);
+
+        let score = CENTRE_POINTS + ZONE_POINTS;
+        assert_eq!(
+            game.result(),
+            Some(GameResult::new(score, ROUNDS * CENTRE_POINTS))
+        );
+    }
+
+    #[test]
+    fn perfect_game() {
+        let mut game = started(3);
+        for _ in 0..ROUNDS {
+            stop_at(&mut game, ZONE_WIDTH / 2.0);
+        }
+
+        let result = game.result().unwrap();
+        assert!(result.is_perfect());
+        assert_eq!(result.score, ROUNDS * CENTRE_POINTS);
+    }
+
+    #[test]
+    fn only_the_first_click_of_a_round_counts() {
+        let mut game = started(42);
+        let centre = game.zone_start() + ZONE_WIDTH / 2.0;
+        game.tick(Duration::from_secs_f32(centre / SPEEDS[0]));
+        game.input(Input::Click);
+        game.input(Input::Click);
+        game.tick(RESULT_TIME / 2);
+        game.input(Input::Click);
+
+        assert_eq!(game.score(), CENTRE_POINTS);
+        assert_eq!(game.round, 0);
+        game.tick(RESULT_TIME / 2);
+        assert_eq!(game.round, 1);
+    }
+}
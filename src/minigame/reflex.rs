@@ -0,0 +1,343 @@
+use crate::apa102::pattern::{Colour, Pattern};
+use crate::i18n::{self, Key};
+use crate::minigame::{GameResult, MiniGame};
+use crate::random::Rng;
+use crate::ui::{Input, Theme};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::MonoTextStyleBuilder;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+pub const ROUNDS: u32 = 3;
+/// Random wait before the LEDs flash, in milliseconds.
+const MIN_WAIT: u32 = 1500;
+const MAX_WAIT: u32 = 4000;
+const TIMEOUT: Duration = Duration::from_millis(1500);
+const RESULT_TIME: Duration = Duration::from_secs(1);
+/// Reaction times worth 3, 2 and 1 points.
+const THRESHOLDS: [Duration; 3] = [
+    Duration::from_millis(250),
+    Duration::from_millis(400),
+    Duration::from_millis(700),
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Outcome {
+    Early,
+    Missed,
+    Reaction(Duration),
+}
+
+impl Outcome {
+    pub fn points(&self) -> u32 {
+        match self {
+            Outcome::Reaction(time) => THRESHOLDS
+                .iter()
+                .position(|threshold| time < threshold)
+                .map_or(0, |idx| (THRESHOLDS.len() - idx) as u32),
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Phase {
+    Waiting {
+        remaining: Duration,
+    },
+    Go {
+        waited: Duration,
+    },
+    Shown {
+        outcome: Outcome,
+        remaining: Duration,
+    },
+    Done,
+}
+
+/// Wait for the LED strip to flash and click as fast as possible, clicking early loses the round.
+pub struct Reflex {
+    bounds: Rectangle,
+    rng: Rng,
+    round: u32,
+    score: u32,
+    phase: Phase,
+    dirty: bool,
+}
+
+impl Reflex {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            rng: Rng::new(0),
+            round: 0,
+            score: 0,
+            phase: Phase::Done,
+            dirty: true,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn is_go(&self) -> bool {
+        matches!(self.phase, Phase::Go { .. })
+    }
+
+    fn wait(&mut self) {
+        let wait = self.rng.range(MIN_WAIT, MAX_WAIT);
+        self.phase = Phase::Waiting {
+            remaining: Duration::from_millis(wait as u64),
+        };
+        self.dirty = true;
+    }
+
+    fn show(&mut self, outcome: Outcome) {
+        self.score += outcome.points();
+        self.phase = Phase::Shown {
+            outcome,
+            remaining: RESULT_TIME,
+        };
+        self.dirty = true;
+    }
+}
+
+impl MiniGame for Reflex {
+    fn title(&self) -> &'static str {
+        i18n::text(Key::GameReflex)
+    }
+
+    fn start(&mut self, seed: u32) {
+        self.rng = Rng::new(seed);
+        self.round = 0;
+        self.score = 0;
+        self.wait();
+    }
+
+    fn input(&mut self, input: Input) {
+        if input != Input::Click {
+            return;
+        }
+
+        match self.phase {
+            Phase::Waiting { .. } => self.show(Outcome::Early),
+            Phase::Go { waited } => self.show(Outcome::Reaction(waited)),
+            _ => {}
+        }
+    }
+
+    fn tick(&mut self, elapsed: Duration) {
+        match &mut self.phase {
+            Phase::Waiting { remaining } => {
+                if elapsed >= *remaining {
+                    // the time past the flash already counts towards the reaction
+                    self.phase = Phase::Go {
+                        waited: elapsed - *remaining,
+                    };
+                    self.dirty = true;
+                } else {
+                    *remaining -= elapsed;
+                }
+            }
+            Phase::Go { waited } => {
+                *waited += elapsed;
+                if *waited >= TIMEOUT {
+                    self.show(Outcome::Missed);
+                }
+            }
+            Phase::Shown { remaining, .. } => {
+                *remaining = remaining.saturating_sub(elapsed);
+
+                if remaining.is_zero() {
+                    self.round += 1;
+                    if self.round >= ROUNDS {
+                        self.phase = Phase::Done;
+                        self.dirty = true;
+                    } else {
+                        self.wait();
+                    }
+                }
+            }
+            Phase::Done => {}
+        }
+    }
+
+    fn result(&self) -> Option<GameResult> {
+        match self.phase {
+            Phase::Done if self.round >= ROUNDS => Some(GameResult::new(
+                self.score,
+                ROUNDS * THRESHOLDS.len() as u32,
+            )),
+            _ => None,
+        }
+    }
+
+    fn leds(&self) -> Option<Pattern> {
+        match self.phase {
+            Phase::Go { .. } => Some(Pattern::Solid(Colour::GREEN)),
+            Phase::Shown {
+                outcome: Outcome::Early | Outcome::Missed,
+                ..
+            } => Some(Pattern::Solid(Colour::RED)),
+            _ => Some(Pattern::Off),
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let (background, foreground) = match self.phase {
+            Phase::Go { .. } => (theme.accent, theme.on_accent),
+            _ => (theme.background, theme.foreground),
+        };
+        target.fill_solid(&self.bounds, background)?;
+        self.dirty = false;
+
+        let message = match self.phase {
+            Phase::Waiting { .. } => String::from(i18n::text(Key::WaitForIt)),
+            Phase::Go { .. } => String::from(i18n::text(Key::Now)),
+            Phase::Shown {
+                outcome: Outcome::Early,
+                ..
+            } => String::from(i18n::text(Key::TooEarly)),
+            Phase::Shown {
+                outcome: Outcome::Missed,
+                ..
+            } => String::from(i18n::text(Key::TooSlow)),
+            Phase::Shown {
+                outcome: Outcome::Reaction(time),
+                ..
+            } => format!("{} ms", time.as_millis()),
+            Phase::Done => return Ok(()),
+        };
+
+        let style = MonoTextStyleBuilder::new()
+            .font(theme.title_font)
+            .text_color(foreground)
+            .background_color(background)
+            .build();
+        Text::with_text_style(
+            &message,
+            self.bounds.center(),
+            style,
+            TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Middle)
+                .build(),
+        )
+        .draw(target)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rectangle = Rectangle::new(Point::zero(), Size::new(200, 120));
+
+    /// The waits before each flash for `seed`, drawn the same way the game does.
+    fn waits(seed: u32) -> Vec<Duration> {
+        let mut rng = Rng::new(seed);
+        (0..ROUNDS)
+            .map(|_| Duration::from_millis(rng.range(MIN_WAIT, MAX_WAIT) as u64))
+            .collect()
+    }
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn points_by_reaction_time() {
+        let points = |time| Outcome::Reaction(millis(time)).points();
+        assert_eq!(points(0), 3);
+        assert_eq!(points(249), 3);
+        assert_eq!(points(250), 2);
+        assert_eq!(points(399), 2);
+        assert_eq!(points(400), 1);
+        assert_eq!(points(699), 1);
+        assert_eq!(points(700), 0);
+        assert_eq!(Outcome::Early.points(), 0);
+        assert_eq!(Outcome::Missed.points(), 0);
+    }
+
+    #[test]
+    fn scripted_game() {
+        let mut game = Reflex::new(BOUNDS);
+        game.start(77);
+        let waits = waits(77);
+
+        // quick
+        game.tick(waits[0] - millis(1));
+        assert!(!game.is_go());
+        assert_eq!(game.leds(), Some(Pattern::Off));
+        game.tick(millis(1));
+        assert!(game.is_go());
+        assert_eq!(game.leds(), Some(Pattern::Solid(Colour::GREEN)));
+        game.tick(millis(200));
+        game.input(Input::Click);
+        assert_eq!(game.score(), 3);
+        game.tick(RESULT_TIME);
+
+        // too early
+        game.tick(waits[1] / 2);
+        game.input(Input::Click);
+        assert_eq!(game.score(), 3);
+        assert_eq!(game.leds(), Some(Pattern::Solid(Colour::RED)));
+        game.tick(RESULT_TIME);
+
+        // too slow
+        game.tick(waits[2]);
+        assert!(game.is_go());
+        game.tick(TIMEOUT - millis(1));
+        assert!(game.is_go());
+        game.tick(millis(1));
+        assert!(!game.is_go());
+        assert_eq!(game.result(), None);
+        game.tick(RESULT_TIME);
+
+        assert_eq!(game.result(), Some(GameResult::new(3, 9)));
+    }
+
+    #[test]
+    fn time_past_the_flash_counts() {
+        let mut game = Reflex::new(BOUNDS);
+        game.start(8);
+
+        game.tick(waits(8)[0] + millis(300));
+        game.input(Input::Click);
+        assert_eq!(game.score(), 2);
+    }
+
+    #[test]
+    fn clicks_between_rounds_are_ignored() {
+        let mut game = Reflex::new(BOUNDS);
+        game.start(8);
+
+        game.tick(waits(8)[0]);
+        game.input(Input::Click);
+        game.input(Input::Click);
+        game.input(Input::Rotate(1));
+        assert_eq!(game.score(), 3);
+        assert_eq!(game.round, 0);
+    }
+}
@@ -0,0 +1,68 @@
+//! Shows the pet's calls for attention on the LED strip, kept free of the hardware so it runs on
+//! the host.
+
+use crate::apa102::pattern::{Colour, LedLayer, Pattern, PatternPlayer};
+use crate::pet::calls::CallKind;
+use std::time::{Duration, Instant};
+
+/// Each need gets its own pattern so it can be told apart without looking at the screen.
+pub fn pattern(kind: CallKind) -> Pattern {
+    match kind {
+        CallKind::Sick => Pattern::Blink {
+            colour: Colour::RED,
+            period: Duration::from_millis(500),
+        },
+        CallKind::Hungry => Pattern::Pulse {
+            colour: Colour::AMBER,
+            period: Duration::from_millis(1500),
+        },
+        CallKind::Waste => Pattern::Chase {
+            colour: Colour::new(140, 70, 0),
+            period: Duration::from_millis(150),
+        },
+        // a fake call looks just like a real one
+        CallKind::Bored | CallKind::Misbehaving => Pattern::Pulse {
+            colour: Colour::BLUE,
+            period: Duration::from_secs(2),
+        },
+        CallKind::Evolving => Pattern::Chase {
+            colour: Colour::WHITE,
+            period: Duration::from_millis(80),
+        },
+    }
+}
+
+/// Keeps the notification layer in step with the pet's current call.
+#[derive(Default)]
+pub struct Notifier {
+    showing: Option<CallKind>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn showing(&self) -> Option<CallKind> {
+        self.showing
+    }
+
+    /// Shows `call`, returning it if it wasn't already showing so the caller can wake the device.
+    pub fn update(
+        &mut self,
+        call: Option<CallKind>,
+        leds: &mut PatternPlayer,
+        now: Instant,
+    ) -> Option<CallKind> {
+        if call == self.showing {
+            return None;
+        }
+
+        self.showing = call;
+        match call {
+            Some(kind) => leds.set(LedLayer::Notification, pattern(kind), now),
+            None => leds.clear(LedLayer::Notification),
+        }
+        call
+    }
+}
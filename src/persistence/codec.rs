@@ -0,0 +1,111 @@
+/// Little endian writer for save payloads.
+#[derive(Default)]
+pub struct Writer(Vec<u8>);
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn put_u8(&mut self, val: u8) {
+        self.0.push(val);
+    }
+
+    pub fn put_bool(&mut self, val: bool) {
+        self.put_u8(val as u8);
+    }
+
+    pub fn put_u16(&mut self, val: u16) {
+        self.0.extend_from_slice(&val.to_le_bytes());
+    }
+
+    pub fn put_u32(&mut self, val: u32) {
+        self.0.extend_from_slice(&val.to_le_bytes());
+    }
+
+    pub fn put_u64(&mut self, val: u64) {
+        self.0.extend_from_slice(&val.to_le_bytes());
+    }
+
+    pub fn put_i32(&mut self, val: i32) {
+        self.0.extend_from_slice(&val.to_le_bytes());
+    }
+
+    pub fn put_bytes(&mut self, val: &[u8]) {
+        self.put_u16(val.len() as u16);
+        self.0.extend_from_slice(val);
+    }
+
+    pub fn put_str(&mut self, val: &str) {
+        self.put_bytes(val.as_bytes());
+    }
+}
+
+/// Reads back what [`Writer`] produced, every read fails instead of panicking on short data.
+pub struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.data.len() < len {
+            return Err(String::from("Save data ended early"));
+        }
+
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(self.take(N)?);
+        Ok(buf)
+    }
+
+    pub fn get_u8(&mut self) -> Result<u8, String> {
+        Ok(self.take_array::<1>()?[0])
+    }
+
+    pub fn get_bool(&mut self) -> Result<bool, String> {
+        Ok(self.get_u8()? != 0)
+    }
+
+    pub fn get_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn get_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn get_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn get_i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn get_bytes(&mut self) -> Result<&'a [u8], String> {
+        let len = self.get_u16()? as usize;
+        self.take(len)
+    }
+
+    pub fn get_str(&mut self) -> Result<String, String> {
+        String::from_utf8(self.get_bytes()?.to_vec())
+            .map_err(|_| String::from("Save data has an invalid string"))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::console::Console;
+use crate::persistence::{Storage, Store};
+use crate::pet::Pet;
+use std::sync::{Arc, Mutex};
+
+pub fn register_commands<S>(
+    console: &mut Console,
+    store: Arc<Mutex<Store<S>>>,
+    pet: Arc<Mutex<Pet>>,
+) where
+    S: Storage + Send + 'static,
+{
+    {
+        let store = store.clone();
+        let pet = pet.clone();
+        console.register("save", "", "save the pet now", move |_| {
+            let pet = pet
+                .lock()
+                .map_err(|_| String::from("Failed to gain pet lock"))?;
+            store
+                .lock()
+                .map_err(|_| String::from("Failed to gain store lock"))?
+                .save(&*pet)?;
+            Ok(String::from("Saved"))
+        });
+    }
+
+    console.register("load", "", "reload the pet from the last save", move |_| {
+        let loaded = store
+            .lock()
+            .map_err(|_| String::from("Failed to gain store lock"))?
+            .load::<Pet>()?
+            .ok_or("Nothing saved yet")?;
+
+        *pet.lock()
+            .map_err(|_| String::from("Failed to gain pet lock"))? = loaded;
+        Ok(String::from("Loaded"))
+    });
+}
@@ -0,0 +1,154 @@
+use crate::persistence::Storage;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Keeps every key in its own file, used to run the save code on the host.
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, String> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|err| format!("Failed to create save dir, {}", err))?;
+
+        Ok(Self { dir })
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&mut self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        match fs::read(self.dir.join(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("Failed to read {}, {}", key, err)),
+        }
+    }
+
+    fn write(&mut self, key: &str, data: &[u8]) -> Result<(), String> {
+        // write then rename so a key is never left half written
+        let tmp = self.dir.join(format!("{}.tmp", key));
+        fs::write(&tmp, data).map_err(|err| format!("Failed to write {}, {}", key, err))?;
+        fs::rename(&tmp, self.dir.join(key))
+            .map_err(|err| format!("Failed to write {}, {}", key, err))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), String> {
+        match fs::remove_file(self.dir.join(key)) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Failed to remove {}, {}", key, err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::codec::{Reader, Writer};
+    use crate::persistence::{Record, Store};
+
+    #[derive(PartialEq, Debug)]
+    struct Count(u32);
+
+    impl Record for Count {
+        const KEY: &'static str = "count";
+        const VERSION: u16 = 2;
+
+        fn encode(&self, writer: &mut Writer) {
+            writer.put_u32(self.0);
+        }
+
+        fn decode(_version: u16, reader: &mut Reader) -> Result<Self, String> {
+            Ok(Self(reader.get_u32()?))
+        }
+    }
+
+    /// A store in an empty directory of its own.
+    fn store(name: &str) -> (Store<FileStorage>, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("jazagotchi-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        (Store::new(FileStorage::new(&dir).unwrap()), dir)
+    }
+
+    #[test]
+    fn round_trip_alternates_slots() {
+        let (mut store, dir) = store("round-trip");
+        assert_eq!(store.load::<Count>(), Ok(None));
+
+        store.save(&Count(1)).unwrap();
+        assert_eq!(store.load::<Count>(), Ok(Some(Count(1))));
+        assert!(dir.join("count.a").exists());
+        assert!(!dir.join("count.b").exists());
+
+        store.save(&Count(2)).unwrap();
+        store.save(&Count(3)).unwrap();
+        assert_eq!(store.load::<Count>(), Ok(Some(Count(3))));
+        assert!(dir.join("count.b").exists());
+        assert!(!dir.join("count.a.tmp").exists());
+
+        store.erase::<Count>().unwrap();
+        assert_eq!(store.load::<Count>(), Ok(None));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn torn_write_falls_back_to_the_other_slot() {
+        let (mut store, dir) = store("torn");
+        store.save(&Count(1)).unwrap();
+        store.save(&Count(2)).unwrap();
+
+        // cut the newest slot short as a brown out halfway through the write would
+        let newest = dir.join("count.b");
+        let data = fs::read(&newest).unwrap();
+        fs::write(&newest, &data[..data.len() - 2]).unwrap();
+        assert_eq!(store.load::<Count>(), Ok(Some(Count(1))));
+
+        // the next save goes over the damaged slot and keeps the good one
+        store.save(&Count(3)).unwrap();
+        assert_eq!(store.load::<Count>(), Ok(Some(Count(3))));
+        assert_eq!(fs::read(dir.join("count.a")).unwrap().len(), data.len());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn corrupt_slots_are_skipped() {
+        let (mut store, dir) = store("corrupt");
+        store.save(&Count(1)).unwrap();
+        store.save(&Count(2)).unwrap();
+
+        // a flipped payload bit fails the crc
+        let mut data = fs::read(dir.join("count.b")).unwrap();
+        *data.last_mut().unwrap() ^= 1;
+        fs::write(dir.join("count.b"), &data).unwrap();
+        assert_eq!(store.load::<Count>(), Ok(Some(Count(1))));
+
+        // with both slots bad it's as if nothing was saved
+        fs::write(dir.join("count.a"), b"garbage").unwrap();
+        assert_eq!(store.load::<Count>(), Ok(None));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        #[derive(Debug)]
+        struct Future;
+
+        impl Record for Future {
+            const KEY: &'static str = "count";
+            const VERSION: u16 = 3;
+
+            fn encode(&self, _writer: &mut Writer) {}
+
+            fn decode(_version: u16, _reader: &mut Reader) -> Result<Self, String> {
+                Ok(Self)
+            }
+        }
+
+        let (mut store, dir) = store("future");
+        store.save(&Future).unwrap();
+        assert!(store.load::<Count>().is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
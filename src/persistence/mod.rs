@@ -0,0 +1,241 @@
+//! Saving the pet and settings so they survive a reset or power cycle.
+//!
+//! Every record is written to two slots, `<key>.a` and `<key>.b`, alternating between them with an
+//! increasing sequence number. Each slot carries a CRC so a write cut short by a brown out only ever
+//! damages the slot being written, loading then falls back to the other one.
+
+pub mod codec;
+pub mod commands;
+pub mod file;
+#[cfg(target_os = "espidf")]
+pub mod nvs;
+pub mod records;
+
+use crate::persistence::codec::{Reader, Writer};
+use std::time::{Duration, Instant};
+
+const MAGIC: u32 = 0x4A5A_4731; // "JZG1"
+const HEADER_LEN: usize = 18;
+
+/// Backend the save slots are stored in.
+pub trait Storage {
+    /// Reads the blob stored under `key`, `None` when nothing has been written yet.
+    fn read(&mut self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    fn write(&mut self, key: &str, data: &[u8]) -> Result<(), String>;
+    fn remove(&mut self, key: &str) -> Result<(), String>;
+}
+
+/// Something that can be saved, `decode` is handed the version it was saved with and is
+/// responsible for migrating anything older than `VERSION`.
+pub trait Record: Sized {
+    /// Kept short, NVS keys are limited to 15 characters including the slot suffix.
+    const KEY: &'static str;
+    const VERSION: u16;
+
+    fn encode(&self, writer: &mut Writer);
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String>;
+}
+
+struct Slot {
+    key: String,
+    version: u16,
+    sequence: u32,
+    payload: Vec<u8>,
+}
+
+pub struct Store<S: Storage> {
+    storage: S,
+}
+
+impl<S: Storage> Store<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn storage(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Loads the newest valid copy of a record, `None` if it has never been saved.
+    pub fn load<R: Record>(&mut self) -> Result<Option<R>, String> {
+        let slots = self.slots(R::KEY)?;
+        let mut last_err = None;
+
+        for slot in slots {
+            if slot.version > R::VERSION {
+                last_err = Some(format!(
+                    "{} was saved by newer firmware, version {}",
+                    slot.key, slot.version
+                ));
+                continue;
+            }
+
+            match R::decode(slot.version, &mut Reader::new(&slot.payload)) {
+                Ok(record) => return Ok(Some(record)),
+                Err(err) => {
+                    log::error!("Failed to decode {}, {}", slot.key, err);
+                    last_err = Some(err);
+                }
+            }
+        }
+
+        match last_err {
+            Some(err) => Err(err),
+            None => Ok(None),
+        }
+    }
+
+    /// Writes the record over the older of the two slots.
+    pub fn save<R: Record>(&mut self, record: &R) -> Result<(), String> {
+        let slots = self.slots(R::KEY)?;
+
+        let (key, sequence) = match slots.first() {
+            Some(newest) => {
+                let other = if newest.key.ends_with(".a") { "b" } else { "a" };
+                (slot_key(R::KEY, other), newest.sequence.wrapping_add(1))
+            }
+            None => (slot_key(R::KEY, "a"), 0),
+        };
+
+        let mut writer = Writer::new();
+        record.encode(&mut writer);
+        let payload = writer.into_inner();
+
+        self.storage
+            .write(&key, &encode_slot(R::VERSION, sequence, &payload))
+    }
+
+    /// Removes both slots of a record.
+    pub fn erase<R: Record>(&mut self) -> Result<(), String> {
+        self.storage.remove(&slot_key(R::KEY, "a"))?;
+        self.storage.remove(&slot_key(R::KEY, "b"))
+    }
+
+    /// Valid slots for `key`, newest first.
+    fn slots(&mut self, key: &str) -> Result<Vec<Slot>, String> {
+        let mut slots = vec![];
+
+        for suffix in ["a", "b"] {
+            let key = slot_key(key, suffix);
+
+            if let Some(data) = self.storage.read(&key)? {
+                match decode_slot(&data) {
+                    Ok((version, sequence, payload)) => slots.push(Slot {
+                        key,
+                        version,
+                        sequence,
+                        payload,
+                    }),
+                    Err(err) => log::warn!("Ignoring save slot {}, {}", key, err),
+                }
+            }
+        }
+
+        if slots.len() == 2 && is_newer(slots[1].sequence, slots[0].sequence) {
+            slots.swap(0, 1);
+        }
+
+        Ok(slots)
+    }
+}
+
+fn slot_key(key: &str, suffix: &str) -> String {
+    format!("{}.{}", key, suffix)
+}
+
+/// Sequence numbers wrap, so compare on distance rather than value.
+fn is_newer(sequence: u32, other: u32) -> bool {
+    (sequence.wrapping_sub(other) as i32) > 0
+}
+
+fn encode_slot(version: u16, sequence: u32, payload: &[u8]) -> Vec<u8> {
+    let mut writer = Writer::new();
+    writer.put_u32(MAGIC);
+    writer.put_u16(version);
+    writer.put_u32(sequence);
+    writer.put_u32(payload.len() as u32);
+
+    let mut data = writer.into_inner();
+    let crc = crc32(&data[4..]).update(payload);
+    data.extend_from_slice(&crc.finish().to_le_bytes());
+    data.extend_from_slice(payload);
+
+    data
+}
+
+fn decode_slot(data: &[u8]) -> Result<(u16, u32, Vec<u8>), String> {
+    if data.len() < HEADER_LEN {
+        return Err(String::from("slot too short"));
+    }
+
+    let mut reader = Reader::new(data);
+    if reader.get_u32()? != MAGIC {
+        return Err(String::from("bad magic"));
+    }
+
+    let version = reader.get_u16()?;
+    let sequence = reader.get_u32()?;
+    let len = reader.get_u32()? as usize;
+    let crc = reader.get_u32()?;
+
+    let payload = &data[HEADER_LEN..];
+    if payload.len() != len {
+        return Err(String::from("length mismatch"));
+    }
+
+    if crc32(&data[4..14]).update(payload).finish() != crc {
+        return Err(String::from("crc mismatch"));
+    }
+
+    Ok((version, sequence, payload.to_vec()))
+}
+
+/// CRC-32 (IEEE), computed bitwise as the payloads are small.
+pub struct Crc32(u32);
+
+pub fn crc32(data: &[u8]) -> Crc32 {
+    Crc32(0xFFFF_FFFF).update(data)
+}
+
+impl Crc32 {
+    pub fn update(mut self, data: &[u8]) -> Self {
+        for byte in data {
+            self.0 ^= *byte as u32;
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+
+        self
+    }
+
+    pub fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+/// Tracks when the next periodic save is due.
+pub struct AutoSave {
+    interval: Duration,
+    last_save: Instant,
+}
+
+impl AutoSave {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            last_save: now,
+        }
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        now.duration_since(self.last_save) >= self.interval
+    }
+
+    /// Starts the next interval, called after every save attempt so a failing save isn't retried
+    /// in a tight loop.
+    pub fn restart(&mut self, now: Instant) {
+        self.last_save = now;
+    }
+}
@@ -0,0 +1,46 @@
+use crate::persistence::Storage;
+use esp_idf_svc::nvs::{EspDefaultNvsPartition, EspNvs, NvsDefault};
+
+/// Stores keys as blobs in a namespace of the default NVS partition.
+pub struct NvsStorage {
+    nvs: EspNvs<NvsDefault>,
+}
+
+impl NvsStorage {
+    pub fn new(partition: EspDefaultNvsPartition, namespace: &str) -> Result<Self, String> {
+        let nvs = EspNvs::new(partition, namespace, true)
+            .map_err(|err| format!("Failed to open nvs namespace {}, {}", namespace, err))?;
+
+        Ok(Self { nvs })
+    }
+}
+
+impl Storage for NvsStorage {
+    fn read(&mut self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let len = match self.nvs.blob_len(key) {
+            Ok(Some(len)) => len,
+            Ok(None) => return Ok(None),
+            Err(err) => return Err(format!("Failed to read {} from nvs, {}", key, err)),
+        };
+
+        let mut buf = vec![0u8; len];
+        match self.nvs.get_blob(key, &mut buf) {
+            Ok(Some(data)) => Ok(Some(data.to_vec())),
+            Ok(None) => Ok(None),
+            Err(err) => Err(format!("Failed to read {} from nvs, {}", key, err)),
+        }
+    }
+
+    fn write(&mut self, key: &str, data: &[u8]) -> Result<(), String> {
+        self.nvs
+            .set_blob(key, data)
+            .map_err(|err| format!("Failed to write {} to nvs, {}", key, err))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), String> {
+        self.nvs
+            .remove(key)
+            .map(|_| ())
+            .map_err(|err| format!("Failed to remove {} from nvs, {}", key, err))
+    }
+}
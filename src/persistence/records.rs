@@ -0,0 +1,628 @@
+use crate::achievements::{Achievements, Progress};
+use crate::clock::alarm::Alarm;
+use crate::clock::{ClockFace, ClockFormat, TimeOfDay, Timestamp};
+use crate::history::{History, Sample};
+use crate::i18n::Language;
+use crate::persistence::codec::{Reader, Writer};
+use crate::persistence::Record;
+use crate::pet::care::{CareMistake, CareMistakeKind, Form};
+use crate::pet::discipline::Misbehaviour;
+use crate::pet::food::FoodKind;
+use crate::pet::generations::{Farewell, Genes, Graveyard, Memorial};
+use crate::pet::personality::Personality;
+use crate::pet::world::HappeningKind;
+use crate::pet::{LifeStage, Pet, Stats};
+use crate::random::Rng;
+use crate::settings::Settings;
+use crate::supervisor::RestartCounts;
+use std::time::Duration;
+
+impl Record for Pet {
+    const KEY: &'static str = "pet";
+    const VERSION: u16 = 9;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_str(&self.name);
+        writer.put_u8(self.stage.to_u8());
+        writer.put_u64(self.age.as_secs());
+        writer.put_u32(self.pending.as_secs() as u32);
+        writer.put_u64(self.steps);
+
+        writer.put_u8(self.stats.hunger);
+        writer.put_u8(self.stats.happiness);
+        writer.put_u8(self.stats.health);
+        writer.put_u16(self.stats.weight);
+
+        writer.put_u64(self.last_seen.map_or(0, |seen| seen.as_secs()));
+
+        for kind in FoodKind::ALL {
+            writer.put_u8(self.inventory.stock(kind));
+            writer.put_u32(self.inventory.refilling(kind).as_secs() as u32);
+        }
+
+        writer.put_u8(self.stats.hygiene);
+        writer.put_u8(self.form.to_u8());
+        writer.put_u8(self.care.poops);
+        writer.put_u8(self.care.doses_needed);
+        writer.put_bool(self.care.dead);
+        writer.put_u32(self.care.risk);
+        // neglect timers, unused since calls took over
+        writer.put_u32(0);
+        writer.put_u32(0);
+        writer.put_u8(self.care.digesting.len() as u8);
+        for left in &self.care.digesting {
+            writer.put_u32(left.as_secs() as u32);
+        }
+
+        let log = &self.care.log;
+        writer.put_u32(log.total);
+        writer.put_u32(log.this_stage);
+        let recent: Vec<_> = log.recent().collect();
+        writer.put_u8(recent.len() as u8);
+        for mistake in recent.into_iter().rev() {
+            writer.put_u8(mistake.kind.to_u8());
+            writer.put_u64(mistake.age.as_secs());
+        }
+
+        writer.put_bool(self.sleep.asleep);
+        writer.put_bool(self.sleep.lights_on);
+        writer.put_u32(self.sleep.lights_on_for.as_secs() as u32);
+
+        let discipline = &self.discipline;
+        writer.put_u8(discipline.level);
+        writer.put_u8(discipline.misbehaving.map_or(0, |kind| kind.to_u8() + 1));
+        writer.put_u32(discipline.misbehaving_for.as_secs() as u32);
+        writer.put_u32(discipline.next_in.as_secs() as u32);
+        writer.put_u32(discipline.good_for.as_secs() as u32);
+        writer.put_u32(discipline.count);
+
+        writer.put_u32(self.rng.state());
+        writer.put_u8(self.personality.to_u8());
+
+        let world = &self.world;
+        writer.put_u8(world.current.map_or(0, |kind| kind.to_u8() + 1));
+        writer.put_u32(world.current_for.as_secs() as u32);
+        writer.put_u32(world.next_in.as_secs() as u32);
+        writer.put_u32(world.rain_left.as_secs() as u32);
+
+        writer.put_u32(self.generation);
+        writer.put_u8(self.genes.palette);
+        writer.put_u8(self.genes.bias.to_u8());
+        writer.put_u8(
+            self.care
+                .farewell
+                .map_or(0, |farewell| farewell.to_u8() + 1),
+        );
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        match version {
+            1 => decode_pet_v1(reader),
+            2 => decode_pet_v2(reader),
+            3 => decode_pet_v3(reader),
+            4 => decode_pet_v4(reader),
+            5 => decode_pet_v5(reader),
+            6 => decode_pet_v6(reader),
+            7 => decode_pet_v7(reader),
+            8 => decode_pet_v8(reader),
+            9 => decode_pet_v9(reader),
+            _ => Err(format!("Unknown pet version {}", version)),
+        }
+    }
+}
+
+/// Version 9 added generations, genes and how the pet left, 0 meaning it hasn't.
+fn decode_pet_v9(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v8(reader)?;
+
+    pet.generation = reader.get_u32()?;
+    pet.genes = Genes {
+        palette: reader.get_u8()?,
+        bias: Personality::from_u8(reader.get_u8()?),
+    };
+    pet.care.farewell = match reader.get_u8()? {
+        0 => None,
+        val => Some(Farewell::from_u8(val - 1).ok_or("Invalid farewell")?),
+    };
+
+    Ok(pet)
+}
+
+/// Version 8 added happenings, 0 meaning none waiting.
+fn decode_pet_v8(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v7(reader)?;
+
+    let world = &mut pet.world;
+    world.current = match reader.get_u8()? {
+        0 => None,
+        val => Some(HappeningKind::from_u8(val - 1).ok_or("Invalid happening")?),
+    };
+    world.current_for = Duration::from_secs(reader.get_u32()? as u64);
+    world.next_in = Duration::from_secs(reader.get_u32()? as u64);
+    world.rain_left = Duration::from_secs(reader.get_u32()? as u64);
+
+    Ok(pet)
+}
+
+/// Version 7 added the random state and personality.
+fn decode_pet_v7(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v6(reader)?;
+
+    pet.rng = Rng::new(reader.get_u32()?);
+    pet.personality = Personality::from_u8(reader.get_u8()?);
+
+    Ok(pet)
+}
+
+/// Version 6 added discipline, 0 meaning not misbehaving.
+fn decode_pet_v6(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v5(reader)?;
+
+    let discipline = &mut pet.discipline;
+    discipline.level = reader.get_u8()?;
+    discipline.misbehaving = match reader.get_u8()? {
+        0 => None,
+        val => Some(Misbehaviour::from_u8(val - 1).ok_or("Invalid misbehaviour")?),
+    };
+    discipline.misbehaving_for = Duration::from_secs(reader.get_u32()? as u64);
+    discipline.next_in = Duration::from_secs(reader.get_u32()? as u64);
+    discipline.good_for = Duration::from_secs(reader.get_u32()? as u64);
+    discipline.count = reader.get_u32()?;
+
+    Ok(pet)
+}
+
+/// Version 5 added sleep and the lights, older pets start awake with them on.
+fn decode_pet_v5(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v4(reader)?;
+
+    pet.sleep.asleep = reader.get_bool()?;
+    pet.sleep.lights_on = reader.get_bool()?;
+    pet.sleep.lights_on_for = Duration::from_secs(reader.get_u32()? as u64);
+
+    Ok(pet)
+}
+
+/// Version 4 added hygiene, sickness and the care log, older pets start clean and healthy.
+fn decode_pet_v4(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v3(reader)?;
+
+    pet.stats.hygiene = reader.get_u8()?;
+    pet.form = Form::from_u8(reader.get_u8()?).ok_or("Invalid form")?;
+    pet.care.poops = reader.get_u8()?;
+    pet.care.doses_needed = reader.get_u8()?;
+    pet.care.dead = reader.get_bool()?;
+    // older pets could only die of sickness or hunger
+    pet.care.farewell = pet.care.dead.then_some(match pet.care.doses_needed {
+        0 => Farewell::Starvation,
+        _ => Farewell::Sickness,
+    });
+    pet.care.risk = reader.get_u32()?;
+    // neglect timers, calls are rebuilt on the next tick instead
+    reader.get_u32()?;
+    reader.get_u32()?;
+    for _ in 0..reader.get_u8()? {
+        let left = Duration::from_secs(reader.get_u32()? as u64);
+        pet.care.digesting.push(left);
+    }
+
+    let total = reader.get_u32()?;
+    let this_stage = reader.get_u32()?;
+    let mut recent = vec![];
+    for _ in 0..reader.get_u8()? {
+        recent.push(CareMistake {
+            kind: CareMistakeKind::from_u8(reader.get_u8()?).ok_or("Invalid care mistake")?,
+            age: Duration::from_secs(reader.get_u64()?),
+        });
+    }
+    pet.care.log.restore(total, this_stage, recent);
+
+    Ok(pet)
+}
+
+/// Version 3 added the food inventory, older pets start with a full one.
+fn decode_pet_v3(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v2(reader)?;
+
+    for kind in FoodKind::ALL {
+        let stock = reader.get_u8()?;
+        let refilling = Duration::from_secs(reader.get_u32()? as u64);
+        pet.inventory.set(kind, stock, refilling);
+    }
+
+    Ok(pet)
+}
+
+/// Version 2 added the last seen time, 0 meaning never.
+fn decode_pet_v2(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = decode_pet_v1(reader)?;
+
+    pet.last_seen = match reader.get_u64()? {
+        0 => None,
+        secs => Some(Timestamp::from_secs(secs)),
+    };
+
+    Ok(pet)
+}
+
+/// Older pets are seeded from their age, and roll a personality if they've already hatched.
+fn decode_pet_v1(reader: &mut Reader) -> Result<Pet, String> {
+    let mut pet = Pet::new(reader.get_str()?, Rng::new(0));
+
+    pet.stage = LifeStage::from_u8(reader.get_u8()?).ok_or("Invalid life stage")?;
+    pet.age = Duration::from_secs(reader.get_u64()?);
+    pet.pending = Duration::from_secs(reader.get_u32()? as u64);
+    pet.steps = reader.get_u64()?;
+
+    pet.stats = Stats {
+        hunger: reader.get_u8()?,
+        happiness: reader.get_u8()?,
+        health: reader.get_u8()?,
+        weight: reader.get_u16()?,
+        ..Stats::default()
+    };
+
+    pet.rng = Rng::new(pet.age.as_secs() as u32);
+    if pet.stage != LifeStage::Egg {
+        pet.personality = Personality::roll(&mut pet.rng);
+    }
+
+    Ok(pet)
+}
+
+impl Record for Achievements {
+    const KEY: &'static str = "achievements";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_u64(self.unlocked);
+        writer.put_u32(self.progress.evolutions);
+        writer.put_u32(self.progress.games_played);
+        writer.put_u32(self.progress.perfect_games);
+        writer.put_u8(self.progress.adult_forms);
+        writer.put_u8(self.progress.happenings);
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        match version {
+            1 => Ok(Achievements {
+                unlocked: reader.get_u64()?,
+                progress: Progress {
+                    evolutions: reader.get_u32()?,
+                    games_played: reader.get_u32()?,
+                    perfect_games: reader.get_u32()?,
+                    adult_forms: reader.get_u8()?,
+                    happenings: reader.get_u8()?,
+                },
+                ..Achievements::default()
+            }),
+            _ => Err(format!("Unknown achievements version {}", version)),
+        }
+    }
+}
+
+impl Record for History {
+    const KEY: &'static str = "history";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_u32(self.since_last.as_secs() as u32);
+        writer.put_u16(self.samples.len() as u16);
+        // gaps only take the flag
+        for sample in self.samples.iter() {
+            writer.put_bool(sample.is_some());
+            if let Some(sample) = sample {
+                writer.put_u8(sample.hunger);
+                writer.put_u8(sample.happiness);
+                writer.put_u8(sample.health);
+                writer.put_u16(sample.weight);
+            }
+        }
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        if version != 1 {
+            return Err(format!("Unknown history version {}", version));
+        }
+
+        let mut history = History {
+            since_last: Duration::from_secs(reader.get_u32()? as u64),
+            ..History::default()
+        };
+        for _ in 0..reader.get_u16()? {
+            let sample = match reader.get_bool()? {
+                true => Some(Sample {
+                    hunger: reader.get_u8()?,
+                    happiness: reader.get_u8()?,
+                    health: reader.get_u8()?,
+                    weight: reader.get_u16()?,
+                }),
+                false => None,
+            };
+            history.samples.push(sample);
+        }
+
+        Ok(history)
+    }
+}
+
+impl Record for Graveyard {
+    const KEY: &'static str = "graveyard";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_u8(self.memorials.len() as u8);
+        for memorial in self.memorials.iter() {
+            writer.put_str(&memorial.name);
+            writer.put_u32(memorial.generation);
+            writer.put_u64(memorial.age.as_secs());
+            writer.put_u8(memorial.stage.to_u8());
+            writer.put_u8(memorial.form.to_u8());
+            writer.put_u8(memorial.farewell.to_u8());
+        }
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        if version != 1 {
+            return Err(format!("Unknown graveyard version {}", version));
+        }
+
+        let mut graveyard = Graveyard::default();
+        for _ in 0..reader.get_u8()? {
+            graveyard.memorials.push(Memorial {
+                name: reader.get_str()?,
+                generation: reader.get_u32()?,
+                age: Duration::from_secs(reader.get_u64()?),
+                stage: LifeStage::from_u8(reader.get_u8()?).ok_or("Invalid life stage")?,
+                form: Form::from_u8(reader.get_u8()?).ok_or("Invalid form")?,
+                farewell: Farewell::from_u8(reader.get_u8()?).ok_or("Invalid farewell")?,
+            });
+        }
+
+        Ok(graveyard)
+    }
+}
+
+impl Record for Settings {
+    const KEY: &'static str = "settings";
+    const VERSION: u16 = 6;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_u8(self.backlight);
+        writer.put_u8(self.led_brightness);
+        writer.put_i32(self.utc_offset as i32);
+        writer.put_str(&self.owner);
+        writer.put_u8(self.language.to_u8());
+        writer.put_bool(self.encoder_reversed);
+        writer.put_u16(
+            self.sleep_timeout
+                .map_or(0, |timeout| timeout.as_secs() as u16),
+        );
+        writer.put_u8(self.clock_format.to_u8());
+        writer.put_u8(self.clock_face.to_u8());
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        let settings = match version {
+            1 => decode_settings_v1(reader),
+            2 => decode_settings_v2(reader),
+            3 => decode_settings_v3(reader),
+            4 => decode_settings_v4(reader),
+            5 => decode_settings_v5(reader),
+            6 => decode_settings_v6(reader),
+            _ => Err(format!("Unknown settings version {}", version)),
+        }?;
+
+        Ok(settings.validated())
+    }
+}
+
+/// Version 6 added the clock face.
+fn decode_settings_v6(reader: &mut Reader) -> Result<Settings, String> {
+    let mut settings = decode_settings_v5(reader)?;
+
+    settings.clock_face = ClockFace::from_u8(reader.get_u8()?).unwrap_or_default();
+
+    Ok(settings)
+}
+
+/// Version 5 added the encoder direction, screen timeout and clock format, 0 meaning never.
+fn decode_settings_v5(reader: &mut Reader) -> Result<Settings, String> {
+    let mut settings = decode_settings_v4(reader)?;
+
+    settings.encoder_reversed = reader.get_bool()?;
+    settings.sleep_timeout = match reader.get_u16()? {
+        0 => None,
+        secs => Some(Duration::from_secs(secs as u64)),
+    };
+    settings.clock_format = ClockFormat::from_u8(reader.get_u8()?).unwrap_or_default();
+
+    Ok(settings)
+}
+
+/// Version 4 added the language.
+fn decode_settings_v4(reader: &mut Reader) -> Result<Settings, String> {
+    let mut settings = decode_settings_v3(reader)?;
+
+    settings.language = Language::from_u8(reader.get_u8()?).unwrap_or_default();
+
+    Ok(settings)
+}
+
+/// Version 3 added the owner's name.
+fn decode_settings_v3(reader: &mut Reader) -> Result<Settings, String> {
+    let mut settings = decode_settings_v2(reader)?;
+
+    settings.owner = reader.get_str()?;
+
+    Ok(settings)
+}
+
+/// Version 2 added the time zone.
+fn decode_settings_v2(reader: &mut Reader) -> Result<Settings, String> {
+    let mut settings = decode_settings_v1(reader)?;
+
+    settings.utc_offset = reader.get_i32()?.clamp(-14 * 60, 14 * 60) as i16;
+
+    Ok(settings)
+}
+
+/// The first settings only had the backlight and LED brightness.
+fn decode_settings_v1(reader: &mut Reader) -> Result<Settings, String> {
+    Ok(Settings {
+        backlight: reader.get_u8()?,
+        led_brightness: reader.get_u8()?,
+        ..Settings::default()
+    })
+}
+
+impl Record for Alarm {
+    const KEY: &'static str = "alarm";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_u32(self.time().as_secs());
+        writer.put_bool(self.enabled());
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        if version != 1 {
+            return Err(format!("Unknown alarm version {}", version));
+        }
+
+        Ok(Alarm::new(
+            TimeOfDay::from_secs(reader.get_u32()?),
+            reader.get_bool()?,
+        ))
+    }
+}
+
+impl Record for RestartCounts {
+    const KEY: &'static str = "restarts";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.put_u16(self.0.len() as u16);
+        for (name, count) in &self.0 {
+            writer.put_str(name);
+            writer.put_u32(*count);
+        }
+    }
+
+    fn decode(version: u16, reader: &mut Reader) -> Result<Self, String> {
+        if version != 1 {
+            return Err(format!("Unknown restart counts version {}", version));
+        }
+
+        let mut counts = RestartCounts::default();
+        for _ in 0..reader.get_u16()? {
+            let name = reader.get_str()?;
+            counts.0.insert(name, reader.get_u32()?);
+        }
+
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode<R: Record>(version: u16, bytes: &[u8]) -> Result<R, String> {
+        R::decode(version, &mut Reader::new(bytes))
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let settings = Settings {
+            backlight: 60,
+            led_brightness: 7,
+            utc_offset: -150,
+            owner: String::from("Sam"),
+            language: Language::French,
+            encoder_reversed: true,
+            sleep_timeout: Some(Duration::from_secs(5 * 60)),
+            clock_format: ClockFormat::TwelveHour,
+            clock_face: ClockFace::Analogue,
+        };
+        let mut writer = Writer::new();
+        settings.encode(&mut writer);
+
+        assert_eq!(
+            decode::<Settings>(Settings::VERSION, &writer.into_inner()),
+            Ok(settings)
+        );
+    }
+
+    #[test]
+    fn old_settings_get_defaults_for_newer_fields() {
+        let mut writer = Writer::new();
+        writer.put_u8(40);
+        writer.put_u8(3);
+        let settings: Settings = decode(1, &writer.into_inner()).unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                backlight: 40,
+                led_brightness: 3,
+                ..Settings::default()
+            }
+        );
+
+        let mut writer = Writer::new();
+        writer.put_u8(40);
+        writer.put_u8(3);
+        writer.put_i32(60);
+        writer.put_str("Sam");
+        writer.put_u8(Language::French.to_u8());
+        writer.put_bool(true);
+        writer.put_u16(0);
+        writer.put_u8(ClockFormat::TwelveHour.to_u8());
+        let settings: Settings = decode(5, &writer.into_inner()).unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                backlight: 40,
+                led_brightness: 3,
+                utc_offset: 60,
+                owner: String::from("Sam"),
+                language: Language::French,
+                encoder_reversed: true,
+                sleep_timeout: None,
+                clock_format: ClockFormat::TwelveHour,
+                ..Settings::default()
+            }
+        );
+    }
+
+    #[test]
+    fn bad_stored_settings_fall_back() {
+        let mut writer = Writer::new();
+        writer.put_u8(0);
+        writer.put_u8(255);
+        writer.put_i32(100_000);
+        writer.put_str("");
+        writer.put_u8(9);
+        writer.put_bool(false);
+        writer.put_u16(45);
+        writer.put_u8(9);
+        writer.put_u8(9);
+        let settings: Settings = decode(6, &writer.into_inner()).unwrap();
+
+        assert_eq!(
+            settings,
+            Settings {
+                backlight: 10,
+                led_brightness: 0b11111,
+                utc_offset: 14 * 60,
+                ..Settings::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_or_short_settings_are_errors() {
+        assert!(decode::<Settings>(Settings::VERSION + 1, &[]).is_err());
+        assert!(decode::<Settings>(Settings::VERSION, &[100, 31]).is_err());
+    }
+}
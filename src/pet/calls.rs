@@ -0,0 +1,359 @@
+//! Calls for attention, only the most urgent one that hasn't been answered is shown at a time.
+
+use crate::i18n::{self, Key};
+use crate::pet::care::{CareMistakeKind, CALL_TIMEOUT};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Evolving is only worth a look, it goes quiet after this without counting as a mistake.
+pub const EVOLVING_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// A call that was acknowledged or timed out calls again after this if the need is still there,
+/// a need that's still unmet is another mistake this long after the call times out.
+pub const CALL_REPEAT: Duration = Duration::from_secs(30 * 60);
+
+/// Most urgent first.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum CallKind {
+    Sick,
+    Hungry,
+    Waste,
+    Bored,
+    /// Calling for nothing, see [`crate::pet::discipline`].
+    Misbehaving,
+    Evolving,
+}
+
+impl CallKind {
+    pub fn timeout(&self) -> Duration {
+        match self {
+            CallKind::Evolving => EVOLVING_TIMEOUT,
+            _ => CALL_TIMEOUT,
+        }
+    }
+
+    /// Mistake recorded when the call times out.
+    pub fn mistake(&self) -> Option<CareMistakeKind> {
+        match self {
+            CallKind::Sick => Some(CareMistakeKind::Sickness),
+            CallKind::Hungry => Some(CareMistakeKind::Hunger),
+            CallKind::Waste => Some(CareMistakeKind::Waste),
+            CallKind::Bored => Some(CareMistakeKind::Lonely),
+            CallKind::Misbehaving | CallKind::Evolving => None,
+        }
+    }
+
+    /// One off calls go away once answered instead of calling again.
+    fn repeats(&self) -> bool {
+        *self != CallKind::Evolving
+    }
+
+    pub fn message(&self, pet_name: &str) -> String {
+        let key = match self {
+            CallKind::Sick => Key::CallSick,
+            CallKind::Hungry => Key::CallHungry,
+            CallKind::Waste => Key::CallWaste,
+            CallKind::Bored => Key::CallBored,
+            CallKind::Misbehaving => Key::CallMisbehaving,
+            CallKind::Evolving => Key::CallEvolving,
+        };
+
+        i18n::format(key, &[("name", &pet_name)])
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Waiting {
+    /// Time calling, or time quiet once acknowledged or timed out.
+    waited: Duration,
+    quiet: bool,
+    /// Left until the unmet need is a mistake, acknowledging the call doesn't stop this.
+    neglect_left: Duration,
+}
+
+impl Waiting {
+    fn new(kind: CallKind) -> Self {
+        Self {
+            waited: Duration::ZERO,
+            quiet: false,
+            neglect_left: kind.timeout(),
+        }
+    }
+
+    fn quieten(&mut self) {
+        self.waited = Duration::ZERO;
+        self.quiet = true;
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Calls {
+    active: BTreeMap<CallKind, Waiting>,
+}
+
+impl Calls {
+    /// Calls `kind` while `needed`, dropping it once it isn't.
+    pub fn set(&mut self, kind: CallKind, needed: bool) {
+        if needed {
+            self.raise(kind);
+        } else {
+            self.active.remove(&kind);
+        }
+    }
+
+    /// Starts calling `kind` unless it already is.
+    pub fn raise(&mut self, kind: CallKind) {
+        self.active
+            .entry(kind)
+            .or_insert_with(|| Waiting::new(kind));
+    }
+
+    /// The most urgent call nobody has answered yet.
+    pub fn current(&self) -> Option<CallKind> {
+        self.active
+            .iter()
+            .find(|(_, waiting)| !waiting.quiet)
+            .map(|(kind, _)| *kind)
+    }
+
+    pub fn is_active(&self, kind: CallKind) -> bool {
+        self.active.contains_key(&kind)
+    }
+
+    /// Answers the current call, returning it. It goes quiet for a while but the need is still
+    /// neglected until something is done about it.
+    pub fn acknowledge(&mut self) -> Option<CallKind> {
+        let kind = self.current()?;
+        if !kind.repeats() {
+            self.active.remove(&kind);
+        } else if let Some(waiting) = self.active.get_mut(&kind) {
+            waiting.quieten();
+        }
+        Some(kind)
+    }
+
+    /// Quietens `kind` once something was done about it, e.g. a dose of medicine, so it's no
+    /// longer neglected.
+    pub fn answer(&mut self, kind: CallKind) {
+        if !kind.repeats() {
+            self.active.remove(&kind);
+        } else if let Some(waiting) = self.active.get_mut(&kind) {
+            *waiting = Waiting::new(kind);
+            waiting.quiet = true;
+        }
+    }
+
+    /// Moves every call forward by `elapsed`, returning the needs that were just left unmet for
+    /// too long.
+    ///
+    /// That's when a call nobody acknowledged times out, and an acknowledged one whose need is
+    /// still there is neglected on the same schedule.
+    pub fn tick(&mut self, elapsed: Duration) -> Vec<CallKind> {
+        let mut neglected = vec![];
+
+        for (kind, waiting) in self.active.iter_mut() {
+            waiting.waited += elapsed;
+
+            if waiting.quiet && waiting.waited >= CALL_REPEAT {
+                waiting.waited = Duration::ZERO;
+                waiting.quiet = false;
+            } else if !waiting.quiet && waiting.waited >= kind.timeout() {
+                waiting.quieten();
+            }
+
+            waiting.neglect_left = waiting.neglect_left.saturating_sub(elapsed);
+            if waiting.neglect_left.is_zero() {
+                waiting.neglect_left = kind.timeout() + CALL_REPEAT;
+                neglected.push(*kind);
+            }
+        }
+
+        self.active
+            .retain(|kind, waiting| kind.repeats() || !waiting.quiet);
+        neglected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::discipline::Misbehaviour;
+    use crate::pet::food::FoodKind;
+    use crate::pet::{LifeStage, Pet, PetEvent, STEP};
+
+    fn calls(kinds: &[CallKind]) -> Calls {
+        let mut calls = Calls::default();
+        for kind in kinds {
+            calls.raise(*kind);
+        }
+        calls
+    }
+
+    #[test]
+    fn most_urgent_call_first() {
+        let mut calls = calls(&[
+            CallKind::Evolving,
+            CallKind::Bored,
+            CallKind::Hungry,
+            CallKind::Sick,
+            CallKind::Misbehaving,
+            CallKind::Waste,
+        ]);
+
+        let mut order = vec![];
+        while let Some(kind) = calls.acknowledge() {
+            order.push(kind);
+        }
+        assert_eq!(
+            order,
+            vec![
+                CallKind::Sick,
+                CallKind::Hungry,
+                CallKind::Waste,
+                CallKind::Bored,
+                CallKind::Misbehaving,
+                CallKind::Evolving,
+            ]
+        );
+        // answered calls stay active until the need is gone, apart from evolving
+        assert!(calls.is_active(CallKind::Sick));
+        assert!(!calls.is_active(CallKind::Evolving));
+    }
+
+    #[test]
+    fn unanswered_call_times_out_then_repeats() {
+        let mut calls = calls(&[CallKind::Hungry]);
+
+        assert!(calls.tick(CALL_TIMEOUT - STEP).is_empty());
+        assert_eq!(calls.current(), Some(CallKind::Hungry));
+        assert_eq!(calls.tick(STEP), vec![CallKind::Hungry]);
+
+        // quiet for a while, then calling again from the start
+        assert_eq!(calls.current(), None);
+        assert!(calls.tick(CALL_REPEAT - STEP).is_empty());
+        assert_eq!(calls.current(), None);
+        assert!(calls.tick(STEP).is_empty());
+        assert_eq!(calls.current(), Some(CallKind::Hungry));
+        assert!(calls.tick(CALL_TIMEOUT - STEP).is_empty());
+        assert_eq!(calls.tick(STEP), vec![CallKind::Hungry]);
+    }
+
+    #[test]
+    fn acknowledged_call_repeats_but_is_still_neglected() {
+        let mut calls = calls(&[CallKind::Waste, CallKind::Bored]);
+
+        assert_eq!(calls.acknowledge(), Some(CallKind::Waste));
+        assert_eq!(calls.current(), Some(CallKind::Bored));
+        assert_eq!(
+            calls.tick(CALL_TIMEOUT),
+            vec![CallKind::Waste, CallKind::Bored]
+        );
+        assert_eq!(calls.current(), None);
+
+        assert!(calls.tick(CALL_REPEAT - CALL_TIMEOUT).is_empty());
+        assert_eq!(calls.current(), Some(CallKind::Waste));
+    }
+
+    #[test]
+    fn seeing_to_a_need_restarts_its_neglect() {
+        let mut calls = calls(&[CallKind::Sick]);
+        calls.tick(CALL_TIMEOUT - STEP);
+        calls.answer(CallKind::Sick);
+        assert_eq!(calls.current(), None);
+        assert!(calls.is_active(CallKind::Sick));
+
+        assert!(calls.tick(CALL_TIMEOUT - STEP).is_empty());
+        assert_eq!(calls.tick(STEP), vec![CallKind::Sick]);
+    }
+
+    #[test]
+    fn evolving_goes_quiet_for_good() {
+        let mut calls = calls(&[CallKind::Evolving]);
+        assert_eq!(calls.tick(EVOLVING_TIMEOUT), vec![CallKind::Evolving]);
+        assert!(!calls.is_active(CallKind::Evolving));
+        assert!(calls.tick(CALL_REPEAT).is_empty());
+        assert_eq!(calls.current(), None);
+        assert_eq!(CallKind::Evolving.mistake(), None);
+    }
+
+    #[test]
+    fn calls_stop_once_the_need_is_gone() {
+        let mut calls = calls(&[CallKind::Hungry]);
+        calls.tick(CALL_TIMEOUT - STEP);
+        calls.set(CallKind::Hungry, false);
+        assert_eq!(calls.current(), None);
+
+        // a new need starts from the beginning
+        calls.set(CallKind::Hungry, true);
+        calls.set(CallKind::Hungry, true);
+        assert!(calls.tick(CALL_TIMEOUT - STEP).is_empty());
+        assert_eq!(calls.tick(STEP), vec![CallKind::Hungry]);
+    }
+
+    #[test]
+    fn ignored_hunger_is_a_mistake_every_repeat() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hunger = 0;
+
+        let mut mistakes = vec![];
+        let cycle = ((CALL_TIMEOUT + CALL_REPEAT).as_secs() / 60) as u32;
+        for step in 1..=2 * cycle {
+            if pet
+                .tick(STEP, None)
+                .contains(&PetEvent::CareMistake(CareMistakeKind::Hunger))
+            {
+                mistakes.push(step);
+            }
+        }
+
+        let timeout = (CALL_TIMEOUT.as_secs() / 60) as u32;
+        assert_eq!(mistakes, vec![timeout, timeout + cycle]);
+    }
+
+    #[test]
+    fn acknowledging_doesnt_stop_the_mistake_while_the_need_is_unmet() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hunger = 0;
+        let hunger = PetEvent::CareMistake(CareMistakeKind::Hunger);
+
+        pet.tick(STEP, None);
+        assert_eq!(pet.calls.current(), Some(CallKind::Hungry));
+        assert_eq!(pet.acknowledge_call(), Some(CallKind::Hungry));
+        assert_eq!(pet.acknowledge_call(), None);
+
+        // clicking every time it calls again still leaves it hungry
+        let mut mistakes = 0;
+        for _ in 0..3 * (CALL_TIMEOUT + CALL_REPEAT).as_secs() / 60 {
+            pet.acknowledge_call();
+            if pet.tick(STEP, None).contains(&hunger) {
+                mistakes += 1;
+            }
+        }
+        assert_eq!(mistakes, 3);
+
+        // feeding it is what stops them
+        pet.feed(FoodKind::Meal);
+        let events = pet.tick(CALL_TIMEOUT, None);
+        assert!(!events.contains(&hunger));
+        assert_eq!(pet.calls.current(), None);
+    }
+
+    #[test]
+    fn acknowledging_a_fake_call_gives_in() {
+        let mut pet = Pet::quiet(LifeStage::Child);
+        pet.discipline.level = 50;
+        pet.discipline.misbehaving = Some(Misbehaviour::FakeCall);
+
+        pet.tick(STEP, None);
+        assert_eq!(pet.calls.current(), Some(CallKind::Misbehaving));
+        assert_eq!(pet.acknowledge_call(), Some(CallKind::Misbehaving));
+        assert_eq!(pet.discipline.misbehaving, None);
+        assert_eq!(pet.discipline.level, 40);
+
+        // and it isn't a care mistake to leave one
+        pet.discipline.misbehaving = Some(Misbehaviour::FakeCall);
+        let events = pet.tick(CALL_TIMEOUT, None);
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, PetEvent::CareMistake(_))));
+    }
+}
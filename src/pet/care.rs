@@ -0,0 +1,427 @@
+//! Waste, hygiene and sickness, and the care mistakes made by leaving the pet's needs unanswered.
+
+use crate::i18n::{self, Key};
+use crate::pet::generations::Farewell;
+use crate::pet::STEP;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Poops that can pile up, more food than this just adds to the last one.
+pub const MAX_POOPS: u8 = 4;
+/// Time from eating something filling until it comes out again.
+pub const DIGESTION: Duration = Duration::from_secs(45 * 60);
+/// Below this the pet's hygiene counts as low and it can fall sick.
+pub const LOW_HYGIENE: u8 = 30;
+/// Medicine doses it takes to get better.
+pub const MEDICINE_DOSES: u8 = 2;
+/// How long a call can go unanswered before it counts as a care mistake.
+pub const CALL_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+/// Care mistakes kept with their time, older ones only count towards the totals.
+pub const RECENT_MISTAKES: usize = 10;
+
+/// Minutes per hygiene point lost, for each poop left lying around and with none.
+pub(crate) const POOP_HYGIENE_RATE: u64 = 3;
+pub(crate) const HYGIENE_RATE: u64 = 20;
+/// Minutes per health point lost while sick or starving, and gained while neither.
+pub(crate) const SICK_HEALTH_RATE: u64 = 4;
+pub(crate) const STARVING_HEALTH_RATE: u64 = 6;
+pub(crate) const RECOVERY_RATE: u64 = 15;
+/// Risk at which the pet falls sick, each step adds one per poop and two with low hygiene.
+pub(crate) const SICK_RISK: u32 = 60;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CareMistakeKind {
+    /// Left at zero hunger.
+    Hunger,
+    /// Left sick without medicine.
+    Sickness,
+    /// Lights left on while the pet was asleep.
+    LightsOn,
+    /// Poop left lying around.
+    Waste,
+    /// Left at zero happiness.
+    Lonely,
+}
+
+impl CareMistakeKind {
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(CareMistakeKind::Hunger),
+            1 => Some(CareMistakeKind::Sickness),
+            2 => Some(CareMistakeKind::LightsOn),
+            3 => Some(CareMistakeKind::Waste),
+            4 => Some(CareMistakeKind::Lonely),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        i18n::text(match self {
+            CareMistakeKind::Hunger => Key::MistakeHunger,
+            CareMistakeKind::Sickness => Key::MistakeSickness,
+            CareMistakeKind::LightsOn => Key::MistakeLightsOn,
+            CareMistakeKind::Waste => Key::MistakeWaste,
+            CareMistakeKind::Lonely => Key::MistakeLonely,
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CareMistake {
+    pub kind: CareMistakeKind,
+    /// Age of the pet when it happened, unlike the wall clock this survives catch up.
+    pub age: Duration,
+}
+
+/// How well the pet was raised through its last stage.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Form {
+    Good,
+    Average,
+    Poor,
+}
+
+impl Form {
+    pub fn name(&self) -> &'static str {
+        i18n::text(match self {
+            Form::Good => Key::FormGood,
+            Form::Average => Key::FormAverage,
+            Form::Poor => Key::FormPoor,
+        })
+    }
+
+    /// Decided by the mistakes made, then moved a step by very low or high discipline.
+    pub fn from_care(mistakes: u32, discipline: u8) -> Self {
+        let form = match mistakes {
+            0..=1 => Form::Good,
+            2..=4 => Form::Average,
+            _ => Form::Poor,
+        };
+
+        match (form, discipline) {
+            (Form::Good, 0..=24) => Form::Average,
+            (Form::Average, 0..=24) => Form::Poor,
+            (Form::Average, 75..) => Form::Good,
+            (Form::Poor, 75..) => Form::Average,
+            (form, _) => form,
+        }
+    }
+
+    /// Minutes added to each decay rate, well raised pets get needy more slowly.
+    pub(crate) fn decay_bonus(&self) -> i32 {
+        match self {
+            Form::Good => 2,
+            Form::Average => 0,
+            Form::Poor => -2,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(Form::Good),
+            1 => Some(Form::Average),
+            2 => Some(Form::Poor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CareLog {
+    pub total: u32,
+    /// Mistakes since the pet last evolved.
+    pub this_stage: u32,
+    recent: VecDeque<CareMistake>,
+}
+
+impl CareLog {
+    pub fn record(&mut self, mistake: CareMistake) {
+        self.total += 1;
+        self.this_stage += 1;
+        if self.recent.len() == RECENT_MISTAKES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(mistake);
+    }
+
+    /// Newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &CareMistake> {
+        self.recent.iter().rev()
+    }
+
+    /// Used when loading, oldest first.
+    pub(crate) fn restore(&mut self, total: u32, this_stage: u32, recent: Vec<CareMistake>) {
+        self.total = total;
+        self.this_stage = this_stage;
+        self.recent = recent
+            .into_iter()
+            .rev()
+            .take(RECENT_MISTAKES)
+            .rev()
+            .collect();
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Care {
+    pub poops: u8,
+    /// Doses of medicine still needed, zero while healthy.
+    pub doses_needed: u8,
+    pub dead: bool,
+    /// Why the pet is gone, set along with `dead`.
+    pub farewell: Option<Farewell>,
+    pub log: CareLog,
+
+    /// Time left until each meal comes out.
+    pub(crate) digesting: Vec<Duration>,
+    pub(crate) risk: u32,
+}
+
+impl Care {
+    pub fn is_sick(&self) -> bool {
+        self.doses_needed > 0
+    }
+
+    /// Starts digesting something just eaten.
+    pub(crate) fn eat(&mut self) {
+        if self.digesting.len() < MAX_POOPS as usize {
+            self.digesting.push(DIGESTION);
+        }
+    }
+
+    /// Moves digestion forward a step, returning how many poops came out.
+    pub(crate) fn digest(&mut self) -> u8 {
+        let before = self.digesting.len();
+        for left in self.digesting.iter_mut() {
+            *left = left.saturating_sub(STEP);
+        }
+        self.digesting.retain(|left| !left.is_zero());
+
+        let count = (before - self.digesting.len()) as u8;
+        self.poops = self.poops.saturating_add(count).min(MAX_POOPS);
+        count
+    }
+
+    /// Counts up how long a need has gone unanswered, true on the step it becomes a mistake.
+    pub(crate) fn neglected(unanswered: &mut Duration) -> bool {
+        *unanswered += STEP;
+        *unanswered == CALL_TIMEOUT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::calls::CALL_REPEAT;
+    use crate::pet::food::{FeedOutcome, FoodKind};
+    use crate::pet::{LifeStage, Pet, PetEvent};
+
+    fn minutes(duration: Duration) -> u32 {
+        (duration.as_secs() / 60) as u32
+    }
+
+    fn mistakes(events: &[PetEvent]) -> Vec<CareMistakeKind> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                PetEvent::CareMistake(kind) => Some(*kind),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn poop_comes_out_after_digestion() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hunger = 50;
+        pet.feed(FoodKind::Meal);
+
+        assert_eq!(
+            pet.steps_until(&PetEvent::Pooped, 120),
+            Some(minutes(DIGESTION))
+        );
+        assert_eq!(pet.care.poops, 1);
+        assert_eq!(pet.steps_until(&PetEvent::Pooped, 120), None);
+    }
+
+    #[test]
+    fn poops_pile_up_to_the_maximum() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        for _ in 0..MAX_POOPS + 2 {
+            pet.stats.hunger = 0;
+            pet.feed(FoodKind::Meal);
+            pet.inventory.add(FoodKind::Meal, 1);
+        }
+
+        pet.tick(DIGESTION, None);
+        assert_eq!(pet.care.poops, MAX_POOPS);
+        assert!(pet.clean());
+        assert_eq!(pet.care.poops, 0);
+    }
+
+    #[test]
+    fn waste_left_lying_around_is_a_mistake() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.care.poops = 1;
+
+        let events = pet.tick(CALL_TIMEOUT, None);
+        assert_eq!(mistakes(&events), vec![CareMistakeKind::Waste]);
+        assert_eq!(pet.care.log.total, 1);
+
+        // cleaning up in time isn't
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.care.poops = 1;
+        pet.tick(CALL_TIMEOUT - STEP, None);
+        pet.clean();
+        assert!(mistakes(&pet.tick(CALL_TIMEOUT, None)).is_empty());
+    }
+
+    #[test]
+    fn waste_makes_the_pet_sick() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.care.poops = MAX_POOPS;
+
+        // each poop adds a point of risk every step
+        let onset = SICK_RISK / MAX_POOPS as u32;
+        assert_eq!(pet.steps_until(&PetEvent::GotSick, 120), Some(onset));
+        assert_eq!(pet.care.doses_needed, MEDICINE_DOSES);
+    }
+
+    #[test]
+    fn low_hygiene_alone_makes_the_pet_sick() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hygiene = LOW_HYGIENE - 1;
+
+        assert_eq!(
+            pet.steps_until(&PetEvent::GotSick, 120),
+            Some(SICK_RISK / 2)
+        );
+    }
+
+    #[test]
+    fn a_clean_pet_stays_healthy() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        assert_eq!(pet.steps_until(&PetEvent::GotSick, 8 * 60), None);
+        assert_eq!(pet.care.risk, 0);
+    }
+
+    #[test]
+    fn medicine_cures_sickness() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hygiene = LOW_HYGIENE - 1;
+        pet.steps_until(&PetEvent::GotSick, 120).unwrap();
+        pet.clean();
+
+        assert_eq!(pet.feed(FoodKind::Medicine), FeedOutcome::Medicated(1));
+        assert_eq!(pet.feed(FoodKind::Medicine), FeedOutcome::Medicated(0));
+        assert!(!pet.care.is_sick());
+
+        let events = pet.tick(Duration::from_secs(8 * 60 * 60), None);
+        assert!(!pet.care.is_sick());
+        assert!(!events.contains(&PetEvent::GotSick));
+        assert!(!events.contains(&PetEvent::Died));
+        assert!(!mistakes(&events).contains(&CareMistakeKind::Sickness));
+    }
+
+    #[test]
+    fn untreated_sickness_is_fatal() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.care.doses_needed = MEDICINE_DOSES;
+        pet.stats.health = 20;
+
+        // health goes a point every few minutes and the sick call keeps coming back
+        let death = 20 * SICK_HEALTH_RATE as u32;
+        let events = pet.tick(STEP * death, None);
+        assert_eq!(events.last(), Some(&PetEvent::Died));
+        assert_eq!(pet.care.farewell, Some(Farewell::Sickness));
+
+        let timeout = minutes(CALL_TIMEOUT);
+        let cycle = timeout + minutes(CALL_REPEAT);
+        let expected = (death - timeout) / cycle + 1;
+        assert_eq!(
+            mistakes(&events),
+            vec![CareMistakeKind::Sickness; expected as usize]
+        );
+        assert_eq!(pet.care.log.total, expected);
+    }
+
+    #[test]
+    fn starvation_is_fatal() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hunger = 0;
+        pet.stats.health = 10;
+
+        let death = 10 * STARVING_HEALTH_RATE as u32;
+        assert_eq!(pet.steps_until(&PetEvent::Died, 24 * 60), Some(death));
+        assert_eq!(pet.care.farewell, Some(Farewell::Starvation));
+        assert_eq!(
+            pet.care
+                .log
+                .recent()
+                .map(|mistake| mistake.kind)
+                .collect::<Vec<_>>(),
+            vec![CareMistakeKind::Hunger]
+        );
+    }
+
+    #[test]
+    fn nothing_happens_after_death() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.hunger = 0;
+        pet.stats.health = 1;
+        pet.steps_until(&PetEvent::Died, 60).unwrap();
+
+        let before = pet.clone();
+        assert!(pet.tick(Duration::from_secs(24 * 60 * 60), None).is_empty());
+        assert_eq!(pet.age, before.age);
+        assert_eq!(pet.stats, before.stats);
+        assert_eq!(pet.calls.current(), None);
+        assert_eq!(pet.feed(FoodKind::Meal), FeedOutcome::CantEat);
+    }
+
+    #[test]
+    fn recovers_health_while_well() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.stats.health = 50;
+        pet.tick(STEP * RECOVERY_RATE as u32 * 10, None);
+        assert_eq!(pet.stats.health, 60);
+    }
+
+    #[test]
+    fn form_from_care() {
+        assert_eq!(Form::from_care(0, 50), Form::Good);
+        assert_eq!(Form::from_care(1, 50), Form::Good);
+        assert_eq!(Form::from_care(2, 50), Form::Average);
+        assert_eq!(Form::from_care(5, 50), Form::Poor);
+        assert_eq!(Form::from_care(0, 10), Form::Average);
+        assert_eq!(Form::from_care(4, 10), Form::Poor);
+        assert_eq!(Form::from_care(4, 80), Form::Good);
+        assert_eq!(Form::from_care(9, 80), Form::Average);
+    }
+
+    #[test]
+    fn log_keeps_the_newest_mistakes() {
+        let mut log = CareLog::default();
+        for minute in 0..RECENT_MISTAKES as u64 + 3 {
+            log.record(CareMistake {
+                kind: CareMistakeKind::Waste,
+                age: Duration::from_secs(minute * 60),
+            });
+        }
+
+        assert_eq!(log.total, RECENT_MISTAKES as u32 + 3);
+        assert_eq!(log.recent().count(), RECENT_MISTAKES);
+        assert_eq!(
+            log.recent().next().unwrap().age,
+            Duration::from_secs((RECENT_MISTAKES as u64 + 2) * 60)
+        );
+    }
+}
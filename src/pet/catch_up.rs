@@ -0,0 +1,163 @@
+use crate::clock::TimeOfDay;
+use crate::i18n::{self, Key};
+use crate::pet::{Pet, PetEvent, Stats};
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Longest stretch that is simulated, anything beyond is dropped.
+pub const MAX_CATCH_UP: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+/// Elapsed time is fed to the pet in chunks no longer than this.
+pub const CATCH_UP_CHUNK: Duration = Duration::from_secs(10 * 60);
+
+/// Below this a stat is reported as having run low while the device was off.
+const LOW_STAT: u8 = 25;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CatchUpSummary {
+    pub elapsed: Duration,
+    pub simulated: Duration,
+    pub before: Stats,
+    pub after: Stats,
+    pub events: Vec<PetEvent>,
+}
+
+impl CatchUpSummary {
+    pub fn capped(&self) -> bool {
+        self.simulated < self.elapsed
+    }
+
+    /// Short lines describing what happened, empty when nothing worth mentioning did.
+    pub fn messages(&self, pet_name: &str) -> Vec<String> {
+        let mut messages = vec![];
+        let name: (&str, &dyn Display) = ("name", &pet_name);
+
+        if self.before.hunger >= LOW_STAT && self.after.hunger < LOW_STAT {
+            messages.push(i18n::format(Key::CaughtUpHungry, &[name]));
+        }
+        if self.before.happiness >= LOW_STAT && self.after.happiness < LOW_STAT {
+            messages.push(i18n::format(Key::CaughtUpLonely, &[name]));
+        }
+
+        let mistakes = self
+            .events
+            .iter()
+            .filter(|event| matches!(event, PetEvent::CareMistake(_)))
+            .count();
+
+        for event in &self.events {
+            match event {
+                PetEvent::Evolved(stage) => messages.push(i18n::format(
+                    Key::CaughtUpEvolved,
+                    &[name, ("stage", &stage.name().to_lowercase())],
+                )),
+                PetEvent::GotSick => messages.push(i18n::format(Key::CaughtUpSick, &[name])),
+                PetEvent::Died => messages.push(i18n::format(Key::CaughtUpDied, &[name])),
+                PetEvent::Left => messages.push(i18n::format(Key::FarewellOldAge, &[name])),
+                PetEvent::Happened(kind) => messages.push(kind.summary(pet_name)),
+                PetEvent::Pooped
+                | PetEvent::CareMistake(_)
+                | PetEvent::FellAsleep
+                | PetEvent::WokeUp
+                | PetEvent::Misbehaved(_) => {}
+            }
+        }
+
+        let poops = self
+            .events
+            .iter()
+            .filter(|event| **event == PetEvent::Pooped)
+            .count();
+        if poops > 0 {
+            messages.push(i18n::format_count(
+                Key::CaughtUpPooped,
+                poops as u32,
+                &[name],
+            ));
+        }
+        if mistakes > 0 {
+            messages.push(i18n::format_count(
+                Key::CaughtUpMistakes,
+                mistakes as u32,
+                &[],
+            ));
+        }
+
+        messages
+    }
+}
+
+/// Fast forwards the pet over time the device spent off or asleep.
+///
+/// The pet is ticked in chunks of at most [`CATCH_UP_CHUNK`] and at most [`MAX_CATCH_UP`] is
+/// simulated, so the result only depends on the pet, `elapsed` and `now`, the local time it ends
+/// at if known.
+pub fn catch_up(pet: &mut Pet, elapsed: Duration, now: Option<TimeOfDay>) -> CatchUpSummary {
+    let simulated = elapsed.min(MAX_CATCH_UP);
+    let before = pet.stats.clone();
+    let mut events = vec![];
+
+    let mut remaining = simulated;
+    while !remaining.is_zero() {
+        let chunk = remaining.min(CATCH_UP_CHUNK);
+        remaining -= chunk;
+        events.extend(pet.tick(chunk, now.map(|now| now.sub(remaining))));
+    }
+
+    CatchUpSummary {
+        elapsed,
+        simulated,
+        before,
+        after: pet.stats.clone(),
+        events,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    fn pet() -> Pet {
+        let mut pet = Pet::new(String::from("Test"), Rng::new(42));
+        // past the egg so the stats are moving
+        pet.tick(2 * HOUR, None);
+        pet
+    }
+
+    #[test]
+    fn same_start_and_elapsed_give_the_same_result() {
+        let (mut first, mut second) = (pet(), pet());
+        let now = Some(TimeOfDay::new(9, 30));
+
+        let summary = catch_up(&mut first, 20 * HOUR, now);
+        assert_eq!(catch_up(&mut second, 20 * HOUR, now), summary);
+        assert_eq!(first, second);
+        assert_eq!(summary.after, first.stats);
+        assert!(!summary.capped());
+    }
+
+    #[test]
+    fn chunks_add_up_to_a_single_tick() {
+        let (mut caught_up, mut ticked) = (pet(), pet());
+
+        let summary = catch_up(&mut caught_up, 7 * HOUR + CATCH_UP_CHUNK / 2, None);
+        let events = ticked.tick(7 * HOUR + CATCH_UP_CHUNK / 2, None);
+        assert_eq!(caught_up, ticked);
+        assert_eq!(summary.events, events);
+    }
+
+    #[test]
+    fn only_the_cap_is_simulated() {
+        let (mut capped, mut exact) = (pet(), pet());
+        let now = Some(TimeOfDay::new(18, 0));
+
+        let summary = catch_up(&mut capped, MAX_CATCH_UP * 3, now);
+        catch_up(&mut exact, MAX_CATCH_UP, now);
+        assert!(summary.capped());
+        assert_eq!(summary.elapsed, MAX_CATCH_UP * 3);
+        assert_eq!(summary.simulated, MAX_CATCH_UP);
+        assert_eq!(capped, exact);
+    }
+}
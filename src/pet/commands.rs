@@ -0,0 +1,271 @@
+use crate::console::{arg, parse_duration, Console};
+use crate::pet::catch_up::catch_up;
+use crate::pet::food::{FoodKind, FOODS};
+use crate::pet::generations::{self, Graveyard};
+use crate::pet::{Pet, STAT_MAX};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+fn lock(pet: &Arc<Mutex<Pet>>) -> Result<MutexGuard<'_, Pet>, String> {
+    pet.lock()
+        .map_err(|_| String::from("Failed to gain pet lock"))
+}
+
+pub fn register_commands(console: &mut Console, pet: Arc<Mutex<Pet>>) {
+    {
+        let pet = pet.clone();
+        console.register("pet status", "", "show the pet's stats", move |_| {
+            let pet = lock(&pet)?;
+            Ok(format!(
+                "{} the {:?} ({:?}), gen {}, {}h old{}{}\nhunger {} happiness {} health {} hygiene {} weight {}g\npoops {} doses needed {} care mistakes {} discipline {}\npersonality {} colour {}",
+                pet.name,
+                pet.stage,
+                pet.form,
+                pet.generation,
+                pet.age.as_secs() / 3600,
+                if pet.care.dead { ", dead" } else { "" },
+                if pet.sleep.asleep { ", asleep" } else { "" },
+                pet.stats.hunger,
+                pet.stats.happiness,
+                pet.stats.health,
+                pet.stats.hygiene,
+                pet.stats.weight,
+                pet.care.poops,
+                pet.care.doses_needed,
+                pet.care.log.total,
+                pet.discipline.level,
+                pet.personality,
+                pet.genes.palette().0,
+            ))
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register(
+            "pet set",
+            "<hunger|happiness|health|hygiene|weight> <value>",
+            "change a stat",
+            move |args| {
+                let mut pet = lock(&pet)?;
+                let stat: String = arg(args, 0, "stat")?;
+
+                if stat == "weight" {
+                    pet.stats.weight = arg(args, 1, "value")?;
+                    return Ok(String::new());
+                }
+
+                let value: u8 = arg(args, 1, "value")?;
+                let value = value.min(STAT_MAX);
+                match stat.as_str() {
+                    "hunger" => pet.stats.hunger = value,
+                    "happiness" => pet.stats.happiness = value,
+                    "health" => pet.stats.health = value,
+                    "hygiene" => pet.stats.hygiene = value,
+                    _ => return Err(format!("Unknown stat {}", stat)),
+                }
+
+                Ok(String::new())
+            },
+        );
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet rename", "<name>", "rename the pet", move |args| {
+            lock(&pet)?.name = arg(args, 0, "name")?;
+            Ok(String::new())
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register(
+            "pet feed",
+            "<meal|snack|treat|medicine>",
+            "feed the pet",
+            move |args| {
+                let name: String = arg(args, 0, "food")?;
+                let kind = FoodKind::from_name(&name).ok_or(format!("Unknown food {}", name))?;
+
+                let mut pet = lock(&pet)?;
+                let outcome = pet.feed(kind);
+                Ok(outcome.message(&pet.name, kind))
+            },
+        );
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet clean", "", "clean up after the pet", move |_| {
+            lock(&pet)?.clean();
+            Ok(String::new())
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet lights", "<on|off>", "switch the lights", move |args| {
+            let on = match args.first().copied() {
+                Some("on") => true,
+                Some("off") => false,
+                _ => return Err(String::from("Expected on or off")),
+            };
+
+            lock(&pet)?.set_lights(on);
+            Ok(String::new())
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet ack", "", "answer the pet's call", move |_| {
+            let mut pet = lock(&pet)?;
+            match pet.acknowledge_call() {
+                Some(kind) => Ok(format!("Answered {:?}", kind)),
+                None => Err(String::from("Nobody is calling")),
+            }
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register(
+            "pet happen",
+            "",
+            "bring the next happening forward to the next tick",
+            move |_| {
+                let mut pet = lock(&pet)?;
+                pet.world.next_in = Duration::ZERO;
+                Ok(String::new())
+            },
+        );
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet scold", "", "tell the pet off", move |_| {
+            let mut pet = lock(&pet)?;
+            let outcome = pet.scold();
+            Ok(outcome.message(&pet.name))
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet praise", "", "praise the pet", move |_| {
+            let mut pet = lock(&pet)?;
+            let outcome = pet.praise();
+            Ok(outcome.message(&pet.name))
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet mistakes", "", "list recent care mistakes", move |_| {
+            let pet = lock(&pet)?;
+            let mut lines = vec![format!("{} in total", pet.care.log.total)];
+            for mistake in pet.care.log.recent() {
+                lines.push(format!(
+                    "{:>4}h {}",
+                    mistake.age.as_secs() / 3600,
+                    mistake.kind.description()
+                ));
+            }
+
+            Ok(lines.join("\n"))
+        });
+    }
+
+    {
+        let pet = pet.clone();
+        console.register("pet stock", "", "show the food inventory", move |_| {
+            let pet = lock(&pet)?;
+            let lines: Vec<String> = FOODS
+                .iter()
+                .map(|item| {
+                    format!(
+                        "{:<9} {}/{}",
+                        item.name,
+                        pet.inventory.stock(item.kind),
+                        item.max_stock
+                    )
+                })
+                .collect();
+
+            Ok(lines.join("\n"))
+        });
+    }
+
+    console.register(
+        "time skip",
+        "<duration>",
+        "fast forward the pet, e.g. 3h or 1d12h",
+        move |args| {
+            let duration = args
+                .first()
+                .and_then(|text| parse_duration(text))
+                .ok_or("Invalid duration")?;
+
+            let mut pet = lock(&pet)?;
+            let now = pet.time.map(|time| time.add(duration));
+            let summary = catch_up(&mut pet, duration, now);
+            let mut messages = summary.messages(&pet.name);
+            if summary.capped() {
+                messages.push(format!("Capped to {}h", summary.simulated.as_secs() / 3600));
+            }
+
+            Ok(messages.join("\n"))
+        },
+    );
+}
+
+pub fn register_generation_commands(
+    console: &mut Console,
+    pet: Arc<Mutex<Pet>>,
+    graveyard: Arc<Mutex<Graveyard>>,
+) {
+    {
+        let graveyard = graveyard.clone();
+        console.register(
+            "pet next",
+            "",
+            "replace a pet that's gone with its egg",
+            move |_| {
+                let mut pet = lock(&pet)?;
+                let mut graveyard = graveyard
+                    .lock()
+                    .map_err(|_| String::from("Failed to gain graveyard lock"))?;
+
+                match generations::pass_on(&mut pet, &mut graveyard) {
+                    true => Ok(format!("Generation {} egg laid", pet.generation)),
+                    false => Err(format!("{} is still around", pet.name)),
+                }
+            },
+        );
+    }
+
+    console.register("graveyard", "", "list past pets", move |_| {
+        let graveyard = graveyard
+            .lock()
+            .map_err(|_| String::from("Failed to gain graveyard lock"))?;
+
+        let lines: Vec<String> = graveyard
+            .memorials
+            .iter()
+            .rev()
+            .map(|memorial| {
+                format!(
+                    "gen {:<3} {:>3}d {:?} {:?}, {}",
+                    memorial.generation,
+                    memorial.age.as_secs() / (24 * 60 * 60),
+                    memorial.form,
+                    memorial.stage,
+                    memorial.farewell.message(&memorial.name)
+                )
+            })
+            .collect();
+
+        Ok(lines.join("\n"))
+    });
+}
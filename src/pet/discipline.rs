@@ -0,0 +1,414 @@
+//! Misbehaviour, and the scolding and praise that teach the pet to behave.
+//!
+//! A misbehaving pet either calls for attention it doesn't need or refuses its food, the right
+//! answer is to scold it. Giving in makes it worse, praise only helps once it has behaved.
+
+use crate::i18n::{self, Key};
+use crate::pet::care::CALL_TIMEOUT;
+use crate::pet::{LifeStage, STAT_MAX};
+use crate::random::Rng;
+use std::time::Duration;
+
+/// Misbehaviour that isn't scolded within this passes on its own.
+pub const MISBEHAVE_TIME: Duration = CALL_TIMEOUT;
+/// After behaving, praise is deserved for this long.
+pub const GOOD_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+pub(crate) const SCOLD_GAIN: i16 = 25;
+pub(crate) const PRAISE_GAIN: i16 = 10;
+/// Lost by giving in to misbehaviour.
+pub(crate) const GIVE_IN_LOSS: i16 = -10;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Misbehaviour {
+    /// Calling without needing anything.
+    FakeCall,
+    /// Turning down anything but a treat.
+    RefusingFood,
+}
+
+impl Misbehaviour {
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(Misbehaviour::FakeCall),
+            1 => Some(Misbehaviour::RefusingFood),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DisciplineOutcome {
+    /// Scolded while misbehaving.
+    Corrected,
+    /// Scolded for nothing.
+    Unfair,
+    /// Praised after behaving.
+    Praised,
+    /// Praised while misbehaving.
+    Spoiled,
+    /// Praised for nothing in particular, it's still nice to hear.
+    Pleased,
+}
+
+impl DisciplineOutcome {
+    pub fn message(&self, pet_name: &str) -> String {
+        let key = match self {
+            DisciplineOutcome::Corrected => Key::DisciplineCorrected,
+            DisciplineOutcome::Unfair => Key::DisciplineUnfair,
+            DisciplineOutcome::Praised => Key::DisciplinePraised,
+            DisciplineOutcome::Spoiled => Key::DisciplineSpoiled,
+            DisciplineOutcome::Pleased => Key::DisciplinePleased,
+        };
+
+        i18n::format(key, &[("name", &pet_name)])
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Discipline {
+    /// 0 to [`STAT_MAX`].
+    pub level: u8,
+    pub misbehaving: Option<Misbehaviour>,
+
+    pub(crate) misbehaving_for: Duration,
+    /// Time until the next misbehaviour.
+    pub(crate) next_in: Duration,
+    /// Time left to praise good behaviour.
+    pub(crate) good_for: Duration,
+    /// Misbehaviours so far.
+    pub(crate) count: u32,
+}
+
+impl Default for Discipline {
+    fn default() -> Self {
+        Self {
+            level: 0,
+            misbehaving: None,
+            misbehaving_for: Duration::ZERO,
+            next_in: Duration::from_secs(60 * 60),
+            good_for: Duration::ZERO,
+            count: 0,
+        }
+    }
+}
+
+impl Discipline {
+    /// Babies are too young to know better and seniors too old to bother.
+    pub fn can_misbehave(stage: LifeStage) -> bool {
+        matches!(stage, LifeStage::Child | LifeStage::Teen | LifeStage::Adult)
+    }
+
+    /// Time between misbehaviours, around an hour with no discipline to six at full.
+    pub(crate) fn interval(&self, rng: &mut Rng) -> Duration {
+        let minutes = 60 + self.level as u32 * 3;
+        let jitter = rng.range(0, minutes / 2);
+        Duration::from_secs((minutes * 3 / 4 + jitter) as u64 * 60)
+    }
+
+    /// Percentage of the happiness from playing that gets through, a disciplined pet listens.
+    pub(crate) fn responsiveness(&self) -> u8 {
+        50 + self.level / 2
+    }
+
+    pub(crate) fn has_behaved(&self) -> bool {
+        !self.good_for.is_zero()
+    }
+
+    pub(crate) fn adjust(&mut self, delta: i16) {
+        self.level = (self.level as i16 + delta).clamp(0, STAT_MAX as i16) as u8;
+    }
+
+    pub(crate) fn behaved(&mut self) {
+        self.good_for = GOOD_WINDOW;
+    }
+
+    /// Ends any misbehaviour, the next one comes after a full interval.
+    pub(crate) fn stop(&mut self, rng: &mut Rng) {
+        self.misbehaving = None;
+        self.misbehaving_for = Duration::ZERO;
+        self.next_in = self.interval(rng);
+    }
+
+    /// Moves misbehaviour on by `elapsed`, returning a new one if it started.
+    pub(crate) fn tick(&mut self, elapsed: Duration, rng: &mut Rng) -> Option<Misbehaviour> {
+        self.good_for = self.good_for.saturating_sub(elapsed);
+
+        if self.misbehaving.is_some() {
+            self.misbehaving_for += elapsed;
+            if self.misbehaving_for >= MISBEHAVE_TIME {
+                self.stop(rng);
+            }
+            return None;
+        }
+
+        self.next_in = self.next_in.saturating_sub(elapsed);
+        if !self.next_in.is_zero() {
+            return None;
+        }
+
+        let misbehaviour = match rng.chance(50) {
+            true => Misbehaviour::FakeCall,
+            false => Misbehaviour::RefusingFood,
+        };
+        self.count += 1;
+        self.misbehaving = Some(misbehaviour);
+        self.misbehaving_for = Duration::ZERO;
+        Some(misbehaviour)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::calls::CallKind;
+    use crate::pet::care::Form;
+    use crate::pet::food::{FeedOutcome, FoodKind};
+    use crate::pet::{Pet, PetEvent, STEP};
+
+    fn minutes(duration: Duration) -> u32 {
+        (duration.as_secs() / 60) as u32
+    }
+
+    fn misbehaving(misbehaviour: Misbehaviour, level: u8) -> Pet {
+        let mut pet = Pet::quiet(LifeStage::Child);
+        pet.stats.happiness = 50;
+        pet.discipline.level = level;
+        pet.discipline.misbehaving = Some(misbehaviour);
+        pet.tick(STEP, None);
+        pet
+    }
+
+    /// Ticks a step at a time, keeping the pet fed and happy, collecting what it got up to.
+    fn misbehaviours(pet: &mut Pet, steps: u32) -> Vec<(u32, Misbehaviour)> {
+        let mut seen = vec![];
+        for step in 1..=steps {
+            pet.stats.hunger = STAT_MAX;
+            pet.stats.happiness = STAT_MAX;
+            for event in pet.tick(STEP, None) {
+                if let PetEvent::Misbehaved(misbehaviour) = event {
+                    seen.push((step, misbehaviour));
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn scolding_misbehaviour_corrects_it() {
+        let mut pet = misbehaving(Misbehaviour::FakeCall, 50);
+        assert_eq!(pet.calls.current(), Some(CallKind::Misbehaving));
+
+        assert_eq!(pet.scold(), DisciplineOutcome::Corrected);
+        assert_eq!(pet.discipline.level, 75);
+        assert_eq!(pet.discipline.misbehaving, None);
+        assert!(pet.discipline.has_behaved());
+        assert_eq!(pet.calls.current(), None);
+        assert_eq!(pet.stats.happiness, 50);
+
+        pet.tick(STEP, None);
+        assert!(!pet.calls.is_active(CallKind::Misbehaving));
+    }
+
+    #[test]
+    fn scolding_for_nothing_is_unfair() {
+        let mut pet = Pet::quiet(LifeStage::Child);
+        pet.stats.happiness = 50;
+        pet.discipline.level = 50;
+
+        assert_eq!(pet.scold(), DisciplineOutcome::Unfair);
+        assert_eq!(pet.stats.happiness, 40);
+        assert_eq!(pet.discipline.level, 50);
+        assert!(!pet.discipline.has_behaved());
+    }
+
+    #[test]
+    fn praise_after_behaving_teaches_once() {
+        let mut pet = misbehaving(Misbehaviour::RefusingFood, 0);
+        pet.scold();
+
+        assert_eq!(pet.praise(), DisciplineOutcome::Praised);
+        assert_eq!(pet.discipline.level, 35);
+        assert_eq!(pet.stats.happiness, 60);
+
+        assert_eq!(pet.praise(), DisciplineOutcome::Pleased);
+        assert_eq!(pet.discipline.level, 35);
+        assert_eq!(pet.stats.happiness, 62);
+    }
+
+    #[test]
+    fn praise_comes_too_late_after_the_window() {
+        let mut pet = misbehaving(Misbehaviour::FakeCall, 0);
+        pet.scold();
+
+        for _ in 0..minutes(GOOD_WINDOW) - 1 {
+            pet.tick(STEP, None);
+        }
+        assert!(pet.discipline.has_behaved());
+        pet.tick(STEP, None);
+        assert!(!pet.discipline.has_behaved());
+        assert_eq!(pet.praise(), DisciplineOutcome::Pleased);
+        assert_eq!(pet.discipline.level, SCOLD_GAIN as u8);
+    }
+
+    #[test]
+    fn praising_misbehaviour_spoils_the_pet() {
+        let mut pet = misbehaving(Misbehaviour::FakeCall, 50);
+        assert_eq!(pet.praise(), DisciplineOutcome::Spoiled);
+        assert_eq!(pet.discipline.level, 40);
+        assert_eq!(pet.discipline.misbehaving, Some(Misbehaviour::FakeCall));
+
+        // and it doesn't go below nothing
+        pet.discipline.level = 5;
+        pet.praise();
+        assert_eq!(pet.discipline.level, 0);
+    }
+
+    #[test]
+    fn misbehaviour_starts_on_schedule_and_passes_on_its_own() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.discipline.next_in = Duration::from_secs(10 * 60);
+
+        let seen = misbehaviours(&mut pet, 10);
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, 10);
+        assert_eq!(pet.discipline.count, 1);
+
+        for _ in 0..minutes(MISBEHAVE_TIME) - 1 {
+            pet.tick(STEP, None);
+        }
+        assert!(pet.discipline.misbehaving.is_some());
+        pet.tick(STEP, None);
+        assert_eq!(pet.discipline.misbehaving, None);
+        // left alone it isn't a lesson either way
+        assert_eq!(pet.discipline.level, 0);
+    }
+
+    #[test]
+    fn more_discipline_means_less_misbehaviour() {
+        // the next one is timed after the scolding, so a level of 0 is timed at 25
+        for (level, low, high) in [(0, 101, 169), (100, 270, 450)] {
+            let mut pet = Pet::quiet(LifeStage::Adult);
+            pet.rng = Rng::new(7);
+            pet.discipline.level = level;
+            pet.discipline.next_in = Duration::from_secs(60);
+
+            // three days of being scolded straight away
+            let mut starts = vec![];
+            for step in 1..=3 * 24 * 60 {
+                pet.stats.hunger = STAT_MAX;
+                pet.stats.happiness = STAT_MAX;
+                if pet
+                    .tick(STEP, None)
+                    .iter()
+                    .any(|event| matches!(event, PetEvent::Misbehaved(_)))
+                {
+                    starts.push(step);
+                    pet.scold();
+                    pet.discipline.level = level;
+                }
+            }
+
+            assert!(starts.len() > 2, "{}: {:?}", level, starts);
+            for gap in starts.windows(2).map(|pair| pair[1] - pair[0]) {
+                assert!((low..high).contains(&gap), "{}: {:?}", level, starts);
+            }
+        }
+    }
+
+    #[test]
+    fn both_kinds_of_misbehaviour_happen() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        pet.discipline.next_in = Duration::from_secs(60);
+
+        let seen = misbehaviours(&mut pet, 3 * 24 * 60);
+        assert!(seen.len() > 20);
+        for kind in [Misbehaviour::FakeCall, Misbehaviour::RefusingFood] {
+            assert!(seen.iter().any(|(_, seen)| *seen == kind), "{:?}", seen);
+        }
+    }
+
+    #[test]
+    fn only_children_to_adults_misbehave() {
+        assert!(!Discipline::can_misbehave(LifeStage::Egg));
+        assert!(!Discipline::can_misbehave(LifeStage::Baby));
+        assert!(Discipline::can_misbehave(LifeStage::Child));
+        assert!(Discipline::can_misbehave(LifeStage::Teen));
+        assert!(Discipline::can_misbehave(LifeStage::Adult));
+        assert!(!Discipline::can_misbehave(LifeStage::Senior));
+
+        let mut pet = Pet::quiet(LifeStage::Senior);
+        pet.discipline.next_in = STEP;
+        assert!(misbehaviours(&mut pet, 24 * 60).is_empty());
+
+        // growing out of it ends any misbehaviour
+        pet.discipline.misbehaving = Some(Misbehaviour::FakeCall);
+        pet.tick(STEP, None);
+        assert_eq!(pet.discipline.misbehaving, None);
+    }
+
+    #[test]
+    fn fake_calls_are_answered_by_scolding() {
+        let mut pet = misbehaving(Misbehaviour::FakeCall, 0);
+        assert_eq!(pet.calls.current(), Some(CallKind::Misbehaving));
+        pet.scold();
+        pet.tick(STEP, None);
+        assert_eq!(pet.calls.current(), None);
+
+        // refusing food doesn't call
+        let pet = misbehaving(Misbehaviour::RefusingFood, 0);
+        assert_eq!(pet.calls.current(), None);
+    }
+
+    #[test]
+    fn scolding_ends_food_refusal() {
+        let mut pet = misbehaving(Misbehaviour::RefusingFood, 0);
+        pet.stats.hunger = 0;
+        assert_eq!(pet.feed(FoodKind::Meal), FeedOutcome::Refused);
+        assert_eq!(pet.stats.hunger, 0);
+
+        assert_eq!(pet.scold(), DisciplineOutcome::Corrected);
+        assert!(matches!(pet.feed(FoodKind::Meal), FeedOutcome::Fed(_)));
+        assert!(pet.stats.hunger > 0);
+    }
+
+    #[test]
+    fn a_disciplined_pet_takes_more_from_games() {
+        for (level, gained) in [(0, 10), (50, 15), (100, 20)] {
+            let mut pet = Pet::quiet(LifeStage::Child);
+            pet.stats.happiness = 0;
+            pet.discipline.level = level;
+            pet.play(20);
+            assert_eq!(pet.stats.happiness, gained, "{}", level);
+        }
+    }
+
+    #[test]
+    fn discipline_moves_the_form_when_growing_up() {
+        for (mistakes, level, form) in [
+            (0, 50, Form::Good),
+            (0, 10, Form::Average),
+            (3, 80, Form::Good),
+            (3, 10, Form::Poor),
+            (6, 80, Form::Average),
+        ] {
+            let mut pet = Pet::quiet(LifeStage::Child);
+            pet.age = LifeStage::Child.ends_at().unwrap() - STEP;
+            pet.care.log.this_stage = mistakes;
+            pet.discipline.level = level;
+
+            assert!(pet
+                .tick(STEP, None)
+                .contains(&PetEvent::Evolved(LifeStage::Teen)));
+            assert_eq!(
+                pet.form, form,
+                "{} mistakes, {} discipline",
+                mistakes, level
+            );
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! Food, what it does to the pet and the limited stock it comes from.
+//!
+//! Everything about a food lives in [`FOODS`] and which stage likes what in [`PREFERENCES`], the
+//! rest of the engine only looks things up.
+
+use crate::i18n::{self, Key};
+use crate::pet::{LifeStage, STAT_MAX};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+const HOUR: u64 = 60 * 60;
+
+/// Feeding a pet this close to full counts as overfeeding.
+pub const FULL_MARGIN: u8 = 5;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum FoodKind {
+    Meal,
+    Snack,
+    Treat,
+    Medicine,
+}
+
+impl FoodKind {
+    pub const ALL: [FoodKind; 4] = [
+        FoodKind::Meal,
+        FoodKind::Snack,
+        FoodKind::Treat,
+        FoodKind::Medicine,
+    ];
+
+    /// Looks a kind up by its item name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        FOODS
+            .iter()
+            .find(|item| item.name.eq_ignore_ascii_case(name))
+            .map(|item| item.kind)
+    }
+
+    /// Name in the current language.
+    pub fn label(&self) -> &'static str {
+        i18n::text(self.item().label)
+    }
+
+    pub fn item(&self) -> &'static FoodItem {
+        FOODS
+            .iter()
+            .find(|item| item.kind == *self)
+            .expect("Every food kind has an item")
+    }
+}
+
+/// Change to each stat from eating one portion, weight in grams.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Nutrition {
+    pub hunger: i16,
+    pub happiness: i16,
+    pub health: i16,
+    pub weight: i16,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FoodItem {
+    pub kind: FoodKind,
+    /// Used by the console, shown on screen as `label`.
+    pub name: &'static str,
+    pub label: Key,
+    pub nutrition: Nutrition,
+    pub max_stock: u8,
+    /// One portion comes back this often while below `max_stock`.
+    pub refill: Duration,
+}
+
+pub const FOODS: [FoodItem; 4] = [
+    FoodItem {
+        kind: FoodKind::Meal,
+        name: "Meal",
+        label: Key::FoodMeal,
+        nutrition: Nutrition {
+            hunger: 40,
+            happiness: 0,
+            health: 2,
+            weight: 2,
+        },
+        max_stock: 5,
+        refill: Duration::from_secs(2 * HOUR),
+    },
+    FoodItem {
+        kind: FoodKind::Snack,
+        name: "Snack",
+        label: Key::FoodSnack,
+        nutrition: Nutrition {
+            hunger: 15,
+            happiness: 10,
+            health: -1,
+            weight: 1,
+        },
+        max_stock: 3,
+        refill: Duration::from_secs(3 * HOUR),
+    },
+    FoodItem {
+        kind: FoodKind::Treat,
+        name: "Treat",
+        label: Key::FoodTreat,
+        nutrition: Nutrition {
+            hunger: 5,
+            happiness: 25,
+            health: -3,
+            weight: 2,
+        },
+        max_stock: 2,
+        refill: Duration::from_secs(8 * HOUR),
+    },
+    FoodItem {
+        kind: FoodKind::Medicine,
+        name: "Medicine",
+        label: Key::FoodMedicine,
+        nutrition: Nutrition {
+            hunger: 0,
+            happiness: -5,
+            health: 30,
+            weight: 0,
+        },
+        max_stock: 2,
+        refill: Duration::from_secs(12 * HOUR),
+    },
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Preference {
+    Loves,
+    Likes,
+    Dislikes,
+}
+
+impl Preference {
+    pub fn happiness(&self) -> i16 {
+        match self {
+            Preference::Loves => 10,
+            Preference::Likes => 0,
+            Preference::Dislikes => -10,
+        }
+    }
+}
+
+/// Tastes that differ from [`Preference::Likes`], medicine is disliked unless listed.
+pub const PREFERENCES: [(LifeStage, FoodKind, Preference); 8] = [
+    (LifeStage::Baby, FoodKind::Treat, Preference::Loves),
+    (LifeStage::Child, FoodKind::Snack, Preference::Loves),
+    (LifeStage::Child, FoodKind::Treat, Preference::Loves),
+    (LifeStage::Teen, FoodKind::Snack, Preference::Loves),
+    (LifeStage::Teen, FoodKind::Meal, Preference::Dislikes),
+    (LifeStage::Adult, FoodKind::Meal, Preference::Loves),
+    (LifeStage::Senior, FoodKind::Meal, Preference::Loves),
+    (LifeStage::Senior, FoodKind::Snack, Preference::Dislikes),
+];
+
+pub fn preference(stage: LifeStage, kind: FoodKind) -> Preference {
+    PREFERENCES
+        .iter()
+        .find(|(s, k, _)| *s == stage && *k == kind)
+        .map(|(_, _, preference)| *preference)
+        .unwrap_or(match kind {
+            FoodKind::Medicine => Preference::Dislikes,
+            _ => Preference::Likes,
+        })
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FeedOutcome {
+    Fed(Preference),
+    /// Eaten while already full, it still went down but made the pet feel worse.
+    Overfed,
+    OutOfStock,
+    /// Turned down while misbehaving, nothing was eaten.
+    Refused,
+    /// Medicine given while sick, with the doses still needed.
+    Medicated(u8),
+    /// Eggs and dead pets don't eat.
+    CantEat,
+}
+
+impl FeedOutcome {
+    pub fn message(&self, pet_name: &str, kind: FoodKind) -> String {
+        let food = kind.label().to_lowercase();
+        let args: [(&str, &dyn Display); 2] = [("name", &pet_name), ("food", &food)];
+
+        let key = match self {
+            FeedOutcome::Fed(Preference::Loves) => Key::FedLoved,
+            FeedOutcome::Fed(Preference::Likes) => Key::FedLiked,
+            FeedOutcome::Fed(Preference::Dislikes) => Key::FedDisliked,
+            FeedOutcome::Overfed => Key::Overfed,
+            FeedOutcome::OutOfStock => Key::OutOfStock,
+            FeedOutcome::Refused => Key::Refused,
+            FeedOutcome::Medicated(0) => Key::Cured,
+            FeedOutcome::Medicated(doses) => {
+                return i18n::format_count(Key::DosesNeeded, *doses as u32, &args)
+            }
+            FeedOutcome::CantEat => Key::CantEat,
+        };
+
+        i18n::format(key, &args)
+    }
+}
+
+/// Stock of each food and how far along its next refill is.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Inventory {
+    stock: BTreeMap<FoodKind, u8>,
+    refilling: BTreeMap<FoodKind, Duration>,
+}
+
+impl Inventory {
+    pub fn stock(&self, kind: FoodKind) -> u8 {
+        self.stock.get(&kind).copied().unwrap_or(0)
+    }
+
+    pub(crate) fn refilling(&self, kind: FoodKind) -> Duration {
+        self.refilling.get(&kind).copied().unwrap_or(Duration::ZERO)
+    }
+
+    /// Used when loading, anything above the maximum is dropped.
+    pub(crate) fn set(&mut self, kind: FoodKind, stock: u8, refilling: Duration) {
+        self.stock.insert(kind, stock.min(kind.item().max_stock));
+        self.refilling.insert(kind, refilling);
+    }
+
+    /// Adds portions, e.g. won in a mini-game, up to the maximum.
+    pub fn add(&mut self, kind: FoodKind, count: u8) {
+        let stock = self.stock(kind).saturating_add(count);
+        self.stock.insert(kind, stock.min(kind.item().max_stock));
+    }
+
+    pub fn take(&mut self, kind: FoodKind) -> bool {
+        match self.stock(kind) {
+            0 => false,
+            stock => {
+                self.stock.insert(kind, stock - 1);
+                true
+            }
+        }
+    }
+
+    pub(crate) fn tick(&mut self, elapsed: Duration) {
+        for item in FOODS.iter() {
+            if self.stock(item.kind) >= item.max_stock {
+                self.refilling.insert(item.kind, Duration::ZERO);
+                continue;
+            }
+
+            let mut refilling = self.refilling(item.kind) + elapsed;
+            while refilling >= item.refill && self.stock(item.kind) < item.max_stock {
+                refilling -= item.refill;
+                self.add(item.kind, 1);
+            }
+            if self.stock(item.kind) >= item.max_stock {
+                refilling = Duration::ZERO;
+            }
+            self.refilling.insert(item.kind, refilling);
+        }
+    }
+}
+
+impl Default for Inventory {
+    /// Starts fully stocked.
+    fn default() -> Self {
+        Self {
+            stock: FOODS
+                .iter()
+                .map(|item| (item.kind, item.max_stock))
+                .collect(),
+            refilling: BTreeMap::new(),
+        }
+    }
+}
+
+/// Moves `value` by `delta`, staying within `0..=STAT_MAX`.
+pub(crate) fn adjust(value: u8, delta: i16) -> u8 {
+    (value as i16 + delta).clamp(0, STAT_MAX as i16) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::discipline::Misbehaviour;
+    use crate::pet::{Pet, STEP};
+    use crate::random::Rng;
+
+    /// A pet without a personality at `stage`, half way on every meter.
+    fn pet(stage: LifeStage) -> Pet {
+        let mut pet = Pet::new(String::from("Test"), Rng::new(1));
+        pet.stage = stage;
+        pet.stats.hunger = 50;
+        pet.stats.happiness = 50;
+        pet.stats.health = 50;
+        pet
+    }
+
+    #[test]
+    fn each_food_changes_the_stats() {
+        // kind, outcome, hunger, happiness, health, weight, meals digesting
+        let table = [
+            (FoodKind::Meal, Preference::Loves, 90, 60, 52, 7, 1),
+            (FoodKind::Snack, Preference::Likes, 65, 60, 49, 6, 1),
+            (FoodKind::Treat, Preference::Likes, 55, 75, 47, 7, 1),
+            (FoodKind::Medicine, Preference::Dislikes, 50, 35, 80, 5, 0),
+        ];
+
+        for (kind, preference, hunger, happiness, health, weight, digesting) in table {
+            let mut pet = pet(LifeStage::Adult);
+            let stock = pet.inventory.stock(kind);
+
+            assert_eq!(pet.feed(kind), FeedOutcome::Fed(preference), "{:?}", kind);
+            assert_eq!(pet.stats.hunger, hunger, "{:?}", kind);
+            assert_eq!(pet.stats.happiness, happiness, "{:?}", kind);
+            assert_eq!(pet.stats.health, health, "{:?}", kind);
+            assert_eq!(pet.stats.weight, weight, "{:?}", kind);
+            assert_eq!(pet.care.digesting.len(), digesting, "{:?}", kind);
+            assert_eq!(pet.inventory.stock(kind), stock - 1, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn preferences_by_stage() {
+        let table = [
+            (LifeStage::Baby, FoodKind::Treat, Preference::Loves),
+            (LifeStage::Baby, FoodKind::Meal, Preference::Likes),
+            (LifeStage::Teen, FoodKind::Meal, Preference::Dislikes),
+            (LifeStage::Adult, FoodKind::Meal, Preference::Loves),
+            (LifeStage::Senior, FoodKind::Snack, Preference::Dislikes),
+            (LifeStage::Child, FoodKind::Medicine, Preference::Dislikes),
+        ];
+
+        for (stage, kind, expected) in table {
+            assert_eq!(preference(stage, kind), expected, "{:?} {:?}", stage, kind);
+            assert_eq!(pet(stage).feed(kind), FeedOutcome::Fed(expected));
+        }
+    }
+
+    #[test]
+    fn overfeeding() {
+        for kind in [FoodKind::Meal, FoodKind::Snack, FoodKind::Treat] {
+            let mut pet = pet(LifeStage::Adult);
+            pet.stats.hunger = STAT_MAX - FULL_MARGIN;
+
+            assert_eq!(pet.feed(kind), FeedOutcome::Overfed, "{:?}", kind);
+            assert_eq!(pet.stats.hunger, STAT_MAX - FULL_MARGIN);
+            assert_eq!(pet.stats.happiness, 40);
+            assert_eq!(pet.stats.health, 45);
+            assert_eq!(
+                pet.stats.weight,
+                5 + 2 * kind.item().nutrition.weight as u16
+            );
+            assert_eq!(pet.care.digesting.len(), 1);
+        }
+
+        let mut peckish = pet(LifeStage::Adult);
+        peckish.stats.hunger = STAT_MAX - FULL_MARGIN - 1;
+        assert_eq!(
+            peckish.feed(FoodKind::Snack),
+            FeedOutcome::Fed(Preference::Likes)
+        );
+
+        // medicine doesn't fill the pet up
+        let mut full = pet(LifeStage::Adult);
+        full.stats.hunger = STAT_MAX;
+        assert_eq!(
+            full.feed(FoodKind::Medicine),
+            FeedOutcome::Fed(Preference::Dislikes)
+        );
+    }
+
+    #[test]
+    fn refusing_everything_but_treats() {
+        let mut child = pet(LifeStage::Child);
+        child.discipline.level = 50;
+        child.discipline.misbehaving = Some(Misbehaviour::RefusingFood);
+
+        for kind in [FoodKind::Meal, FoodKind::Snack, FoodKind::Medicine] {
+            let stock = child.inventory.stock(kind);
+            assert_eq!(child.feed(kind), FeedOutcome::Refused, "{:?}", kind);
+            assert_eq!(child.inventory.stock(kind), stock);
+        }
+        assert_eq!(child.stats, pet(LifeStage::Child).stats);
+
+        // a treat bribes it into stopping, which costs discipline
+        assert_eq!(
+            child.feed(FoodKind::Treat),
+            FeedOutcome::Fed(Preference::Loves)
+        );
+        assert_eq!(child.discipline.misbehaving, None);
+        assert_eq!(child.discipline.level, 40);
+        assert_eq!(
+            child.feed(FoodKind::Meal),
+            FeedOutcome::Fed(Preference::Likes)
+        );
+    }
+
+    #[test]
+    fn medicine_cures_over_several_doses() {
+        let mut pet = pet(LifeStage::Adult);
+        pet.care.doses_needed = 2;
+
+        assert_eq!(pet.feed(FoodKind::Medicine), FeedOutcome::Medicated(1));
+        assert!(pet.care.is_sick());
+        assert_eq!(pet.feed(FoodKind::Medicine), FeedOutcome::Medicated(0));
+        assert!(!pet.care.is_sick());
+        assert_eq!(pet.stats.health, 100);
+        assert_eq!(pet.feed(FoodKind::Medicine), FeedOutcome::OutOfStock);
+    }
+
+    #[test]
+    fn out_of_stock_and_cant_eat() {
+        let mut adult = pet(LifeStage::Adult);
+        for _ in 0..FoodKind::Treat.item().max_stock {
+            adult.stats.hunger = 0;
+            assert_ne!(adult.feed(FoodKind::Treat), FeedOutcome::OutOfStock);
+        }
+        assert_eq!(adult.feed(FoodKind::Treat), FeedOutcome::OutOfStock);
+
+        assert_eq!(
+            pet(LifeStage::Egg).feed(FoodKind::Meal),
+            FeedOutcome::CantEat
+        );
+        let mut dead = pet(LifeStage::Adult);
+        dead.care.dead = true;
+        assert_eq!(dead.feed(FoodKind::Meal), FeedOutcome::CantEat);
+    }
+
+    #[test]
+    fn inventory_refills_up_to_the_maximum() {
+        let mut inventory = Inventory::default();
+        assert!(inventory.take(FoodKind::Meal));
+        assert!(inventory.take(FoodKind::Meal));
+        assert_eq!(inventory.stock(FoodKind::Meal), 3);
+
+        let refill = FoodKind::Meal.item().refill;
+        inventory.tick(refill - STEP);
+        assert_eq!(inventory.stock(FoodKind::Meal), 3);
+        inventory.tick(STEP);
+        assert_eq!(inventory.stock(FoodKind::Meal), 4);
+        inventory.tick(refill * 5);
+        assert_eq!(inventory.stock(FoodKind::Meal), 5);
+        assert_eq!(inventory.refilling(FoodKind::Meal), Duration::ZERO);
+
+        inventory.add(FoodKind::Treat, 10);
+        assert_eq!(inventory.stock(FoodKind::Treat), 2);
+    }
+}
@@ -0,0 +1,267 @@
+//! What happens once a pet is gone: it's remembered in the graveyard and the next egg inherits
+//! some of its genes.
+
+use crate::history::RingBuffer;
+use crate::i18n::{self, Key};
+use crate::pet::care::Form;
+use crate::pet::personality::Personality;
+use crate::pet::{LifeStage, Pet};
+use crate::random::Rng;
+use std::time::Duration;
+
+/// Seniors leave to see the world once they're this old.
+pub const LIFESPAN: Duration = Duration::from_secs(20 * 24 * 60 * 60);
+/// Past pets remembered, the oldest are forgotten first.
+pub const MEMORIALS: usize = 10;
+/// Chance of an egg keeping its parent's colours instead of getting new ones.
+const PALETTE_KEPT: u8 = 70;
+/// Chance of each of the parent's traits being passed on.
+const TRAIT_KEPT: u8 = 50;
+
+/// Name and RGB colour the pet is drawn in.
+pub const PALETTES: [(Key, [u8; 3]); 6] = [
+    (Key::PaletteWhite, [255, 255, 255]),
+    (Key::PaletteSunny, [255, 220, 60]),
+    (Key::PaletteMint, [120, 255, 170]),
+    (Key::PaletteSky, [110, 190, 255]),
+    (Key::PaletteRose, [255, 140, 180]),
+    (Key::PaletteLilac, [200, 150, 255]),
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Farewell {
+    Sickness,
+    Starvation,
+    /// Left peacefully as a senior.
+    OldAge,
+}
+
+impl Farewell {
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(Farewell::Sickness),
+            1 => Some(Farewell::Starvation),
+            2 => Some(Farewell::OldAge),
+            _ => None,
+        }
+    }
+
+    pub fn message(&self, pet_name: &str) -> String {
+        let key = match self {
+            Farewell::Sickness => Key::FarewellSickness,
+            Farewell::Starvation => Key::FarewellStarvation,
+            Farewell::OldAge => Key::FarewellOldAge,
+        };
+
+        i18n::format(key, &[("name", &pet_name)])
+    }
+}
+
+/// Handed down from the previous pet.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Genes {
+    /// Index into [`PALETTES`].
+    pub palette: u8,
+    /// Traits the pet will hatch with, see [`Personality::roll_from`].
+    pub bias: Personality,
+}
+
+impl Genes {
+    pub fn inherit(parent: &Pet, rng: &mut Rng) -> Self {
+        let palette = match rng.chance(PALETTE_KEPT) {
+            true => parent.genes.palette,
+            false => rng.range(0, PALETTES.len() as u32) as u8,
+        };
+
+        let mut bias = 0;
+        for pet_trait in parent.personality.traits() {
+            if rng.chance(TRAIT_KEPT) {
+                bias |= pet_trait.bit();
+            }
+        }
+
+        Self {
+            palette,
+            bias: Personality::from_u8(bias),
+        }
+    }
+
+    /// Name in the current language and colour.
+    pub fn palette(&self) -> (&'static str, [u8; 3]) {
+        let (name, colour) = PALETTES[self.palette as usize % PALETTES.len()];
+        (i18n::text(name), colour)
+    }
+}
+
+/// A past pet, as shown on the memorial screen.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Memorial {
+    pub name: String,
+    pub generation: u32,
+    pub age: Duration,
+    pub stage: LifeStage,
+    pub form: Form,
+    pub farewell: Farewell,
+}
+
+impl Memorial {
+    /// `None` while the pet is still around.
+    pub fn of(pet: &Pet) -> Option<Self> {
+        Some(Self {
+            name: pet.name.clone(),
+            generation: pet.generation,
+            age: pet.age,
+            stage: pet.stage,
+            form: pet.form,
+            farewell: pet.care.farewell?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Graveyard {
+    pub memorials: RingBuffer<Memorial>,
+}
+
+impl Default for Graveyard {
+    fn default() -> Self {
+        Self {
+            memorials: RingBuffer::new(MEMORIALS),
+        }
+    }
+}
+
+/// Remembers a pet that's gone and replaces it with its egg, false if the pet is still around.
+pub fn pass_on(pet: &mut Pet, graveyard: &mut Graveyard) -> bool {
+    let Some(memorial) = Memorial::of(pet) else {
+        return false;
+    };
+
+    graveyard.memorials.push(memorial);
+    *pet = pet.offspring();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::personality::Trait;
+    use crate::pet::{PetEvent, STEP};
+
+    fn parent(seed: u32) -> Pet {
+        let mut pet = Pet::new(String::from("Test"), Rng::new(seed));
+        pet.stage = LifeStage::Adult;
+        pet.genes.palette = 2;
+        pet.personality = Personality::from_u8(Trait::Playful.bit() | Trait::Shy.bit());
+        pet
+    }
+
+    fn gone(farewell: Farewell) -> Pet {
+        let mut pet = parent(1);
+        pet.care.dead = true;
+        pet.care.farewell = Some(farewell);
+        pet
+    }
+
+    #[test]
+    fn same_seed_same_genes() {
+        for seed in 1..50 {
+            let pet = parent(seed);
+            assert_eq!(
+                Genes::inherit(&pet, &mut Rng::new(seed)),
+                Genes::inherit(&pet, &mut Rng::new(seed))
+            );
+            assert_eq!(parent(seed).offspring(), parent(seed).offspring());
+        }
+    }
+
+    #[test]
+    fn genes_come_from_the_parent() {
+        let pet = parent(1);
+        let mut palettes = vec![];
+        let mut biases = vec![];
+
+        for seed in 1..200 {
+            let genes = Genes::inherit(&pet, &mut Rng::new(seed));
+            assert!((genes.palette as usize) < PALETTES.len());
+            assert!(genes
+                .bias
+                .traits()
+                .all(|pet_trait| pet.personality.has(pet_trait)));
+
+            palettes.push(genes.palette);
+            if !biases.contains(&genes.bias) {
+                biases.push(genes.bias);
+            }
+        }
+
+        // mostly the parent's colours, with all four combinations of its traits
+        let kept = palettes.iter().filter(|palette| **palette == 2).count();
+        assert!(kept > palettes.len() * 6 / 10, "{}", kept);
+        assert_eq!(biases.len(), 4);
+    }
+
+    #[test]
+    fn pass_on_leaves_a_live_pet_alone() {
+        let mut pet = parent(1);
+        let before = pet.clone();
+        let mut graveyard = Graveyard::default();
+
+        assert!(!pass_on(&mut pet, &mut graveyard));
+        assert_eq!(pet, before);
+        assert!(graveyard.memorials.is_empty());
+        assert_eq!(Memorial::of(&pet), None);
+    }
+
+    #[test]
+    fn pass_on_remembers_and_lays_an_egg() {
+        let mut pet = gone(Farewell::Sickness);
+        let mut graveyard = Graveyard::default();
+
+        assert!(pass_on(&mut pet, &mut graveyard));
+        let memorial = graveyard.memorials.get_back(0).unwrap();
+        assert_eq!(memorial.farewell, Farewell::Sickness);
+        assert_eq!(memorial.generation, 1);
+        assert_eq!(memorial.stage, LifeStage::Adult);
+
+        assert_eq!(pet.stage, LifeStage::Egg);
+        assert_eq!(pet.generation, 2);
+        assert_eq!(pet.name, "Test");
+        assert!(!pet.care.dead);
+        assert!(!pass_on(&mut pet, &mut graveyard));
+    }
+
+    #[test]
+    fn graveyard_forgets_the_oldest() {
+        let mut graveyard = Graveyard::default();
+        let mut pet = gone(Farewell::Starvation);
+
+        for _ in 0..MEMORIALS + 2 {
+            pet.care.dead = true;
+            pet.care.farewell = Some(Farewell::Starvation);
+            assert!(pass_on(&mut pet, &mut graveyard));
+        }
+
+        let generations: Vec<u32> = graveyard
+            .memorials
+            .iter()
+            .map(|memorial| memorial.generation)
+            .collect();
+        assert_eq!(generations, (3..=MEMORIALS as u32 + 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn seniors_leave_at_the_end_of_their_lifespan() {
+        let mut pet = Pet::quiet(LifeStage::Senior);
+        pet.age = LIFESPAN - STEP * 2;
+
+        assert!(!pet.tick(STEP, None).contains(&PetEvent::Left));
+        assert_eq!(pet.tick(STEP, None), vec![PetEvent::Left]);
+        assert_eq!(pet.care.farewell, Some(Farewell::OldAge));
+        assert_eq!(Memorial::of(&pet).unwrap().farewell, Farewell::OldAge);
+    }
+}
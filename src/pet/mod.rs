@@ -0,0 +1,579 @@
+//! Hardware free pet engine, nothing in here touches the device so it runs the same on the host.
+//!
+//! All meters run from 0 (empty) to [`STAT_MAX`] (full), hunger is how full the pet is.
+
+pub mod calls;
+pub mod care;
+pub mod catch_up;
+pub mod commands;
+pub mod discipline;
+pub mod food;
+pub mod generations;
+pub mod personality;
+pub mod sleep;
+pub mod world;
+
+use crate::clock::{TimeOfDay, Timestamp};
+use crate::i18n::{self, Key};
+use crate::pet::calls::{CallKind, Calls};
+use crate::pet::care::{Care, CareMistake, CareMistakeKind, Form};
+use crate::pet::discipline::{Discipline, DisciplineOutcome, Misbehaviour};
+use crate::pet::food::{adjust, FeedOutcome, FoodKind, Inventory, Preference, FULL_MARGIN};
+use crate::pet::generations::{Farewell, Genes};
+use crate::pet::personality::Personality;
+use crate::pet::sleep::Sleep;
+use crate::pet::world::{Conditions, Happening, HappeningKind, World};
+use crate::random::Rng;
+use std::time::Duration;
+
+pub const STAT_MAX: u8 = 100;
+
+/// Stats change in whole steps of this length so the outcome never depends on how often the pet
+/// is ticked.
+pub const STEP: Duration = Duration::from_secs(60);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LifeStage {
+    Egg,
+    Baby,
+    Child,
+    Teen,
+    Adult,
+    Senior,
+}
+
+impl LifeStage {
+    pub fn name(&self) -> &'static str {
+        i18n::text(match self {
+            LifeStage::Egg => Key::StageEgg,
+            LifeStage::Baby => Key::StageBaby,
+            LifeStage::Child => Key::StageChild,
+            LifeStage::Teen => Key::StageTeen,
+            LifeStage::Adult => Key::StageAdult,
+            LifeStage::Senior => Key::StageSenior,
+        })
+    }
+
+    /// Age at which the pet grows out of this stage.
+    pub fn ends_at(&self) -> Option<Duration> {
+        const HOUR: u64 = 60 * 60;
+
+        match self {
+            LifeStage::Egg => Some(Duration::from_secs(5 * 60)),
+            LifeStage::Baby => Some(Duration::from_secs(HOUR)),
+            LifeStage::Child => Some(Duration::from_secs(24 * HOUR)),
+            LifeStage::Teen => Some(Duration::from_secs(3 * 24 * HOUR)),
+            LifeStage::Adult => Some(Duration::from_secs(10 * 24 * HOUR)),
+            LifeStage::Senior => None,
+        }
+    }
+
+    pub fn next(&self) -> Option<LifeStage> {
+        match self {
+            LifeStage::Egg => Some(LifeStage::Baby),
+            LifeStage::Baby => Some(LifeStage::Child),
+            LifeStage::Child => Some(LifeStage::Teen),
+            LifeStage::Teen => Some(LifeStage::Adult),
+            LifeStage::Adult => Some(LifeStage::Senior),
+            LifeStage::Senior => None,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(LifeStage::Egg),
+            1 => Some(LifeStage::Baby),
+            2 => Some(LifeStage::Child),
+            3 => Some(LifeStage::Teen),
+            4 => Some(LifeStage::Adult),
+            5 => Some(LifeStage::Senior),
+            _ => None,
+        }
+    }
+
+    /// Minutes per point lost for (hunger, happiness), `None` while the stat doesn't decay.
+    fn decay_rates(&self) -> Option<(u32, u32)> {
+        match self {
+            LifeStage::Egg => None,
+            LifeStage::Baby => Some((3, 4)),
+            LifeStage::Child => Some((6, 8)),
+            LifeStage::Teen => Some((8, 10)),
+            LifeStage::Adult => Some((10, 12)),
+            LifeStage::Senior => Some((12, 10)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Stats {
+    pub hunger: u8,
+    pub happiness: u8,
+    pub health: u8,
+    /// Weight in grams.
+    pub weight: u16,
+    pub hygiene: u8,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            hunger: STAT_MAX,
+            happiness: STAT_MAX,
+            health: STAT_MAX,
+            weight: 5,
+            hygiene: STAT_MAX,
+        }
+    }
+}
+
+/// Things that happened during a tick that the rest of the device may want to react to.
+#[derive(Clone, PartialEq, Debug)]
+pub enum PetEvent {
+    Evolved(LifeStage),
+    Pooped,
+    GotSick,
+    CareMistake(CareMistakeKind),
+    Died,
+    /// Left peacefully as a senior.
+    Left,
+    FellAsleep,
+    WokeUp,
+    Misbehaved(Misbehaviour),
+    Happened(HappeningKind),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Pet {
+    pub name: String,
+    /// 1 for the first pet, one more for each egg after.
+    pub generation: u32,
+    pub genes: Genes,
+    pub stage: LifeStage,
+    pub age: Duration,
+    pub stats: Stats,
+    /// Wall clock time the pet was last ticked up to, used to catch up after the device was off.
+    pub last_seen: Option<Timestamp>,
+    pub inventory: Inventory,
+    pub care: Care,
+    pub form: Form,
+    pub sleep: Sleep,
+    pub discipline: Discipline,
+    /// Rolled on hatching, none before.
+    pub personality: Personality,
+    pub world: World,
+    /// Local time as of the last tick, `None` while the clock isn't set.
+    pub time: Option<TimeOfDay>,
+    /// Rebuilt from the rest of the pet's state on the next tick, so it isn't saved.
+    pub calls: Calls,
+
+    /// Time that has been ticked but not yet applied as a whole [`STEP`].
+    pub(crate) pending: Duration,
+    /// Whole steps taken since hatching, used to schedule per stat decay.
+    pub(crate) steps: u64,
+    /// Source of everything random about the pet, saved so a life replays exactly.
+    pub(crate) rng: Rng,
+}
+
+impl Pet {
+    pub fn new(name: String, rng: Rng) -> Self {
+        Self {
+            name,
+            generation: 1,
+            genes: Genes::default(),
+            stage: LifeStage::Egg,
+            age: Duration::ZERO,
+            stats: Stats::default(),
+            last_seen: None,
+            inventory: Inventory::default(),
+            care: Care::default(),
+            form: Form::Average,
+            sleep: Sleep::default(),
+            discipline: Discipline::default(),
+            personality: Personality::default(),
+            world: World::default(),
+            time: None,
+            calls: Calls::default(),
+            pending: Duration::ZERO,
+            steps: 0,
+            rng,
+        }
+    }
+
+    /// The egg that follows this pet, inheriting some of its genes.
+    pub fn offspring(&mut self) -> Pet {
+        let mut egg = Pet::new(self.name.clone(), Rng::new(self.rng.next_u32()));
+        egg.generation = self.generation + 1;
+        egg.genes = Genes::inherit(self, &mut egg.rng);
+        egg
+    }
+
+    /// Moves the pet forward by `elapsed`, applying every whole step that has passed.
+    ///
+    /// `time` is the local time at the end of `elapsed`, the pet only sleeps when it's known.
+    pub fn tick(&mut self, elapsed: Duration, time: Option<TimeOfDay>) -> Vec<PetEvent> {
+        let mut events = vec![];
+
+        self.pending += elapsed;
+        while self.pending >= STEP {
+            self.pending -= STEP;
+            self.step(time.map(|time| time.sub(self.pending)), &mut events);
+        }
+        self.time = time;
+
+        events
+    }
+
+    /// Switches the lights, the pet expects them off while it's asleep.
+    pub fn set_lights(&mut self, on: bool) {
+        self.sleep.lights_on = on;
+        if !on {
+            self.sleep.lights_on_for = Duration::ZERO;
+        }
+    }
+
+    /// Eats one portion from the inventory.
+    ///
+    /// Anything filling eaten while the pet is already full is overfeeding, it puts on twice the
+    /// weight and makes the pet feel worse instead of better.
+    pub fn feed(&mut self, kind: FoodKind) -> FeedOutcome {
+        if self.stage == LifeStage::Egg || self.care.dead {
+            return FeedOutcome::CantEat;
+        }
+
+        let refusing = self.discipline.misbehaving == Some(Misbehaviour::RefusingFood);
+        if refusing && kind != FoodKind::Treat {
+            return FeedOutcome::Refused;
+        }
+        if !self.inventory.take(kind) {
+            return FeedOutcome::OutOfStock;
+        }
+        if refusing {
+            // bribed with a treat
+            self.discipline.adjust(discipline::GIVE_IN_LOSS);
+            self.discipline.stop(&mut self.rng);
+        }
+
+        let nutrition = kind.item().nutrition;
+        let overfed = nutrition.hunger > 0 && self.stats.hunger >= STAT_MAX - FULL_MARGIN;
+
+        if overfed {
+            self.stats.happiness = adjust(self.stats.happiness, -10);
+            self.stats.health = adjust(self.stats.health, -5);
+            self.add_weight(nutrition.weight * 2);
+            self.care.eat();
+            return FeedOutcome::Overfed;
+        }
+
+        let preference = self
+            .personality
+            .preference(kind)
+            .unwrap_or_else(|| food::preference(self.stage, kind));
+        self.stats.hunger = adjust(self.stats.hunger, nutrition.hunger);
+        self.stats.happiness = adjust(
+            self.stats.happiness,
+            nutrition.happiness + preference.happiness(),
+        );
+        self.stats.health = adjust(self.stats.health, nutrition.health);
+        self.add_weight(nutrition.weight);
+        if nutrition.hunger > 0 {
+            self.care.eat();
+        }
+        if preference == Preference::Dislikes {
+            self.discipline.behaved();
+        }
+
+        if kind == FoodKind::Medicine && self.care.is_sick() {
+            self.care.doses_needed -= 1;
+            self.calls.answer(CallKind::Sick);
+            return FeedOutcome::Medicated(self.care.doses_needed);
+        }
+
+        FeedOutcome::Fed(preference)
+    }
+
+    /// Clears away any poop and gives the pet a wash, true if there was anything to clean.
+    pub fn clean(&mut self) -> bool {
+        let dirty = self.care.poops > 0 || self.stats.hygiene < STAT_MAX;
+        self.care.poops = 0;
+        self.stats.hygiene = STAT_MAX;
+        dirty
+    }
+
+    pub(crate) fn record_mistake(&mut self, kind: CareMistakeKind, events: &mut Vec<PetEvent>) {
+        self.care.log.record(CareMistake {
+            kind,
+            age: self.age,
+        });
+        events.push(PetEvent::CareMistake(kind));
+    }
+
+    fn add_weight(&mut self, grams: i16) {
+        self.stats.weight =
+            (self.stats.weight as i32 + grams as i32).clamp(1, u16::MAX as i32) as u16;
+    }
+
+    /// Playing cheers the pet up and burns off a little weight, how much depends on its
+    /// personality and a pet with little discipline doesn't take as much from it.
+    pub fn play(&mut self, happiness: u8) {
+        let happiness = happiness as u16 * self.personality.game_enjoyment() / 100
+            * self.discipline.responsiveness() as u16
+            / 100;
+        self.stats.happiness = adjust(self.stats.happiness, happiness as i16);
+        self.add_weight(-1);
+    }
+
+    /// Tells the pet off, which only helps while it's misbehaving.
+    pub fn scold(&mut self) -> DisciplineOutcome {
+        if self.discipline.misbehaving.is_none() {
+            self.stats.happiness = adjust(self.stats.happiness, -10);
+            return DisciplineOutcome::Unfair;
+        }
+
+        self.discipline.adjust(discipline::SCOLD_GAIN);
+        self.discipline.stop(&mut self.rng);
+        self.discipline.behaved();
+        self.calls.answer(CallKind::Misbehaving);
+        DisciplineOutcome::Corrected
+    }
+
+    /// Praises the pet, which teaches it the most right after it behaved.
+    pub fn praise(&mut self) -> DisciplineOutcome {
+        if self.discipline.misbehaving.is_some() {
+            self.discipline.adjust(discipline::GIVE_IN_LOSS);
+            return DisciplineOutcome::Spoiled;
+        }
+
+        if self.discipline.has_behaved() {
+            self.discipline.adjust(discipline::PRAISE_GAIN);
+            self.discipline.good_for = Duration::ZERO;
+            self.stats.happiness = adjust(self.stats.happiness, 10);
+            return DisciplineOutcome::Praised;
+        }
+
+        self.stats.happiness = adjust(self.stats.happiness, 2);
+        DisciplineOutcome::Pleased
+    }
+
+    /// Answers the current call, answering a fake one is giving in to it.
+    pub fn acknowledge_call(&mut self) -> Option<CallKind> {
+        let kind = self.calls.acknowledge()?;
+        if kind == CallKind::Misbehaving {
+            self.discipline.adjust(discipline::GIVE_IN_LOSS);
+            self.discipline.stop(&mut self.rng);
+        }
+        Some(kind)
+    }
+
+    fn step(&mut self, time: Option<TimeOfDay>, events: &mut Vec<PetEvent>) {
+        if self.care.dead {
+            return;
+        }
+
+        self.age += STEP;
+        self.steps += 1;
+        self.inventory.tick(STEP);
+        self.sleep_step(time, events);
+
+        if Discipline::can_misbehave(self.stage) && !self.sleep.asleep {
+            if let Some(misbehaviour) = self.discipline.tick(STEP, &mut self.rng) {
+                events.push(PetEvent::Misbehaved(misbehaviour));
+            }
+        } else if self.discipline.misbehaving.is_some() {
+            self.discipline.stop(&mut self.rng);
+        }
+
+        if let Some((hunger, happiness)) = self.stage.decay_rates() {
+            let slowdown = if self.sleep.is_resting() { 2 } else { 1 };
+            let cooped_up = if self.world.is_raining() { 2 } else { 1 };
+            let (hunger_bias, happiness_bias) = self.personality.decay_bias();
+            let rate = |minutes: u32, bias: i32| {
+                (minutes as i32 + bias + self.form.decay_bonus()).max(1) as u64 * slowdown
+            };
+
+            if self.steps % rate(hunger, hunger_bias) == 0 {
+                self.stats.hunger = self.stats.hunger.saturating_sub(1);
+            }
+            if self.steps % (rate(happiness, happiness_bias) / cooped_up).max(1) == 0 {
+                self.stats.happiness = self.stats.happiness.saturating_sub(1);
+            }
+        }
+
+        if self.stage != LifeStage::Egg {
+            self.care_step(events);
+            if self.care.dead {
+                self.calls = Calls::default();
+                return;
+            }
+        }
+
+        if let (Some(ends_at), Some(next)) = (self.stage.ends_at(), self.stage.next()) {
+            if self.age >= ends_at {
+                if self.stage == LifeStage::Egg {
+                    self.personality = Personality::roll_from(&mut self.rng, self.genes.bias);
+                }
+                self.stage = next;
+                self.form = Form::from_care(self.care.log.this_stage, self.discipline.level);
+                self.care.log.this_stage = 0;
+                self.calls.raise(CallKind::Evolving);
+                events.push(PetEvent::Evolved(next));
+            }
+        }
+
+        if self.stage == LifeStage::Senior && self.age >= generations::LIFESPAN {
+            self.care.dead = true;
+            self.care.farewell = Some(Farewell::OldAge);
+            self.calls = Calls::default();
+            events.push(PetEvent::Left);
+            return;
+        }
+
+        let conditions = (self.stage != LifeStage::Egg && !self.sleep.asleep).then(|| Conditions {
+            time,
+            stage: self.stage,
+            happiness: self.stats.happiness,
+            raining: self.world.is_raining(),
+        });
+        if let Some(kind) = self.world.tick(STEP, &mut self.rng, conditions) {
+            self.happen(kind.happening());
+            events.push(PetEvent::Happened(kind));
+        }
+
+        self.call_step(events);
+    }
+
+    /// Applies a happening's effect on the stats and hands over any gift.
+    fn happen(&mut self, happening: &Happening) {
+        self.stats.happiness = adjust(self.stats.happiness, happening.happiness);
+        self.stats.hunger = adjust(self.stats.hunger, happening.hunger);
+        if let Some((kind, count)) = happening.gift {
+            self.inventory.add(kind, count);
+        }
+    }
+
+    /// Calls for whatever the pet needs, a sleeping pet only calls when it's sick.
+    fn call_step(&mut self, events: &mut Vec<PetEvent>) {
+        let awake = !self.sleep.asleep;
+
+        self.calls.set(CallKind::Sick, self.care.is_sick());
+        self.calls
+            .set(CallKind::Hungry, awake && self.stats.hunger == 0);
+        self.calls
+            .set(CallKind::Waste, awake && self.care.poops > 0);
+        self.calls
+            .set(CallKind::Bored, awake && self.stats.happiness == 0);
+        self.calls.set(
+            CallKind::Misbehaving,
+            self.discipline.misbehaving == Some(Misbehaviour::FakeCall),
+        );
+
+        for kind in self.calls.tick(STEP) {
+            if let Some(mistake) = kind.mistake() {
+                self.record_mistake(mistake, events);
+            }
+        }
+    }
+
+    /// Falls asleep and wakes up on schedule, complaining if the lights are left on.
+    fn sleep_step(&mut self, time: Option<TimeOfDay>, events: &mut Vec<PetEvent>) {
+        let bedtime = match (self.stage.schedule(), time) {
+            (Some(schedule), Some(time)) => schedule.is_bedtime(time),
+            _ => false,
+        };
+
+        if bedtime && !self.sleep.asleep {
+            self.sleep.asleep = true;
+            self.sleep.lights_on_for = Duration::ZERO;
+            events.push(PetEvent::FellAsleep);
+        } else if !bedtime && self.sleep.asleep {
+            self.sleep.asleep = false;
+            events.push(PetEvent::WokeUp);
+        }
+
+        if self.sleep.asleep && self.sleep.lights_on {
+            if Care::neglected(&mut self.sleep.lights_on_for) {
+                self.record_mistake(CareMistakeKind::LightsOn, events);
+            }
+            if self.steps % sleep::LIGHTS_ON_RATE == 0 {
+                self.stats.happiness = self.stats.happiness.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Digestion, hygiene, sickness and health.
+    fn care_step(&mut self, events: &mut Vec<PetEvent>) {
+        if self.care.digest() > 0 {
+            events.push(PetEvent::Pooped);
+        }
+
+        if self.steps % care::HYGIENE_RATE == 0 {
+            self.stats.hygiene = self.stats.hygiene.saturating_sub(1);
+        }
+        if self.steps % care::POOP_HYGIENE_RATE == 0 {
+            self.stats.hygiene = self.stats.hygiene.saturating_sub(self.care.poops);
+        }
+
+        if !self.care.is_sick() {
+            let low_hygiene = self.stats.hygiene < care::LOW_HYGIENE;
+            let risk = self.care.poops as u32 + if low_hygiene { 2 } else { 0 };
+
+            self.care.risk = match risk {
+                0 => self.care.risk.saturating_sub(1),
+                risk => self.care.risk + risk,
+            };
+            if self.care.risk >= care::SICK_RISK {
+                self.care.risk = 0;
+                self.care.doses_needed = care::MEDICINE_DOSES;
+                events.push(PetEvent::GotSick);
+            }
+        }
+
+        let starving = self.stats.hunger == 0;
+        let sick = self.care.is_sick();
+
+        if sick && self.steps % care::SICK_HEALTH_RATE == 0 {
+            self.stats.health = self.stats.health.saturating_sub(1);
+        }
+        if starving && self.steps % care::STARVING_HEALTH_RATE == 0 {
+            self.stats.health = self.stats.health.saturating_sub(1);
+        }
+        if !sick && !starving && self.steps % care::RECOVERY_RATE == 0 {
+            self.stats.health = adjust(self.stats.health, 1);
+        }
+
+        if self.stats.health == 0 {
+            self.care.dead = true;
+            self.care.farewell = Some(match sick {
+                true => Farewell::Sickness,
+                false => Farewell::Starvation,
+            });
+            events.push(PetEvent::Died);
+        }
+    }
+}
+
+#[cfg(test)]
+impl Pet {
+    /// A pet at the start of `stage` with no personality and no happenings or misbehaviour due,
+    /// so a scripted test only sees what it set up.
+    pub(crate) fn quiet(stage: LifeStage) -> Self {
+        let mut pet = Pet::new(String::from("Test"), Rng::new(1));
+        let mut current = LifeStage::Egg;
+        while current != stage {
+            pet.age = current.ends_at().unwrap();
+            current = current.next().unwrap();
+        }
+        pet.stage = stage;
+        pet.world.next_in = Duration::MAX;
+        pet.discipline.next_in = Duration::MAX;
+        pet
+    }
+
+    /// Ticks a step at a time for up to `steps`, returning the step `event` first happened on.
+    pub(crate) fn steps_until(&mut self, event: &PetEvent, steps: u32) -> Option<u32> {
+        (1..=steps).find(|_| self.tick(STEP, None).contains(event))
+    }
+}
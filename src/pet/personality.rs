@@ -0,0 +1,280 @@
+//! Traits rolled when the pet hatches, nudging how quickly it gets needy, what it likes to eat, how
+//! much it enjoys games and how it fidgets while idle.
+
+use crate::i18n::{self, Key};
+use crate::pet::food::{FoodKind, Preference};
+use crate::random::Rng;
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Trait {
+    /// Gets bored quickly but loves games.
+    Playful,
+    /// Slow to get bored and slow to get into games.
+    Lazy,
+    /// Always hungry, loves anything filling.
+    Greedy,
+    /// Gets lonely slowly, likes to hide.
+    Shy,
+    /// Turns its nose up at plain food.
+    Picky,
+}
+
+impl Trait {
+    pub const ALL: [Trait; 5] = [
+        Trait::Playful,
+        Trait::Lazy,
+        Trait::Greedy,
+        Trait::Shy,
+        Trait::Picky,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        i18n::text(match self {
+            Trait::Playful => Key::TraitPlayful,
+            Trait::Lazy => Key::TraitLazy,
+            Trait::Greedy => Key::TraitGreedy,
+            Trait::Shy => Key::TraitShy,
+            Trait::Picky => Key::TraitPicky,
+        })
+    }
+
+    pub(crate) fn bit(&self) -> u8 {
+        1 << *self as u8
+    }
+
+    /// Traits that contradict each other aren't rolled together.
+    fn conflicts(&self, other: Trait) -> bool {
+        matches!(
+            (self, other),
+            (Trait::Playful, Trait::Lazy)
+                | (Trait::Lazy, Trait::Playful)
+                | (Trait::Greedy, Trait::Picky)
+                | (Trait::Picky, Trait::Greedy)
+        )
+    }
+}
+
+/// Little things the pet does on the home screen when nothing is going on.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum IdleAnimation {
+    Blink,
+    LookAround,
+    Bounce,
+    Yawn,
+    Hide,
+}
+
+impl IdleAnimation {
+    /// Faces shown one after another, each for a fraction of a second.
+    pub fn frames(&self) -> &'static [&'static str] {
+        match self {
+            IdleAnimation::Blink => &["(-_-)"],
+            IdleAnimation::LookAround => &["(<_<)", "(>_>)", "(<_<)"],
+            IdleAnimation::Bounce => &["\\(^o^)/", "(^o^)", "\\(^o^)/"],
+            IdleAnimation::Yawn => &["(-o-)", "(-O-)", "(-.-)"],
+            IdleAnimation::Hide => &["( ..)", "(  .)", "( ..)"],
+        }
+    }
+}
+
+/// The traits a pet was born with, none until it hatches.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Personality(u8);
+
+impl Personality {
+    /// One or two traits that don't contradict each other.
+    pub fn roll(rng: &mut Rng) -> Self {
+        let first = Trait::ALL[rng.range(0, Trait::ALL.len() as u32) as usize];
+        let mut personality = Self(first.bit());
+
+        if rng.chance(50) {
+            let second = Trait::ALL[rng.range(0, Trait::ALL.len() as u32) as usize];
+            if second != first && !first.conflicts(second) {
+                personality.0 |= second.bit();
+            }
+        }
+
+        personality
+    }
+
+    /// Keeps the inherited `bias` and may add a trait of its own, rolls from scratch without one.
+    pub fn roll_from(rng: &mut Rng, bias: Personality) -> Self {
+        if bias.0 == 0 {
+            return Self::roll(rng);
+        }
+
+        let mut personality = bias;
+        if personality.traits().count() < 2 && rng.chance(50) {
+            let extra = Trait::ALL[rng.range(0, Trait::ALL.len() as u32) as usize];
+            if personality
+                .traits()
+                .all(|own| own != extra && !own.conflicts(extra))
+            {
+                personality.0 |= extra.bit();
+            }
+        }
+
+        personality
+    }
+
+    pub fn has(&self, pet_trait: Trait) -> bool {
+        self.0 & pet_trait.bit() != 0
+    }
+
+    pub fn traits(&self) -> impl Iterator<Item = Trait> + '_ {
+        Trait::ALL
+            .into_iter()
+            .filter(|pet_trait| self.has(*pet_trait))
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        self.0
+    }
+
+    pub fn from_u8(val: u8) -> Self {
+        Self(val & ((1 << Trait::ALL.len()) - 1))
+    }
+
+    /// Minutes added to the (hunger, happiness) decay rates.
+    pub(crate) fn decay_bias(&self) -> (i32, i32) {
+        self.traits()
+            .map(|pet_trait| match pet_trait {
+                Trait::Playful => (0, -2),
+                Trait::Lazy => (0, 2),
+                Trait::Greedy => (-2, 0),
+                Trait::Shy => (0, 1),
+                Trait::Picky => (0, 0),
+            })
+            .fold((0, 0), |(hunger, happiness), (h, p)| {
+                (hunger + h, happiness + p)
+            })
+    }
+
+    /// Overrides the stage's taste for `kind`.
+    pub fn preference(&self, kind: FoodKind) -> Option<Preference> {
+        match kind {
+            FoodKind::Meal | FoodKind::Snack if self.has(Trait::Greedy) => Some(Preference::Loves),
+            FoodKind::Meal if self.has(Trait::Picky) => Some(Preference::Dislikes),
+            _ => None,
+        }
+    }
+
+    /// Percentage of a game's happiness that the pet takes from it.
+    pub(crate) fn game_enjoyment(&self) -> u16 {
+        let mut percent = 100;
+        if self.has(Trait::Playful) {
+            percent += 50;
+        }
+        if self.has(Trait::Lazy) {
+            percent -= 25;
+        }
+        percent
+    }
+
+    /// Picks the next idle animation, weighted towards ones that suit the pet.
+    pub fn idle_animation(&self, rng: &mut Rng) -> IdleAnimation {
+        let weight = |base: u32, pet_trait: Trait| {
+            if self.has(pet_trait) {
+                base * 4
+            } else {
+                base
+            }
+        };
+        let animations = [
+            (IdleAnimation::Blink, 6),
+            (IdleAnimation::LookAround, 3),
+            (IdleAnimation::Bounce, weight(2, Trait::Playful)),
+            (IdleAnimation::Yawn, weight(2, Trait::Lazy)),
+            (IdleAnimation::Hide, weight(1, Trait::Shy)),
+        ];
+
+        rng.weighted(&animations)
+            .copied()
+            .unwrap_or(IdleAnimation::Blink)
+    }
+}
+
+impl fmt::Display for Personality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "{}", i18n::text(Key::PersonalityUnknown));
+        }
+
+        let traits: Vec<_> = self.traits().map(|pet_trait| pet_trait.name()).collect();
+        write!(f, "{}", traits.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::{LifeStage, Pet, PetEvent};
+    use std::time::Duration;
+
+    fn hatched(seed: u32) -> Pet {
+        let mut pet = Pet::new(String::from("Test"), Rng::new(seed));
+        let events = pet.tick(LifeStage::Egg.ends_at().unwrap(), None);
+        assert!(events.contains(&PetEvent::Evolved(LifeStage::Baby)));
+        pet
+    }
+
+    #[test]
+    fn same_seed_same_personality() {
+        for seed in 1..50 {
+            let pet = hatched(seed);
+            assert_eq!(pet.personality, hatched(seed).personality);
+            assert_eq!(pet, hatched(seed));
+        }
+    }
+
+    #[test]
+    fn no_personality_before_hatching() {
+        let mut pet = Pet::new(String::from("Test"), Rng::new(3));
+        pet.tick(
+            LifeStage::Egg.ends_at().unwrap() - Duration::from_secs(60),
+            None,
+        );
+        assert_eq!(pet.personality, Personality::default());
+        assert_eq!(pet.personality.traits().count(), 0);
+    }
+
+    #[test]
+    fn rolls_one_or_two_compatible_traits() {
+        let mut rolled = vec![];
+        for seed in 1..200 {
+            let personality = Personality::roll(&mut Rng::new(seed));
+            let traits: Vec<Trait> = personality.traits().collect();
+
+            assert!((1..=2).contains(&traits.len()), "{:?}", traits);
+            if let [first, second] = traits[..] {
+                assert!(!first.conflicts(second), "{:?}", traits);
+            }
+            if !rolled.contains(&personality) {
+                rolled.push(personality);
+            }
+        }
+
+        // different seeds give different pets
+        assert!(rolled.len() > Trait::ALL.len());
+    }
+
+    #[test]
+    fn inherited_bias_is_kept() {
+        let bias = Personality::from_u8(Trait::Shy.bit());
+        for seed in 1..50 {
+            let personality = Personality::roll_from(&mut Rng::new(seed), bias);
+            assert!(personality.has(Trait::Shy));
+            assert_eq!(
+                personality,
+                Personality::roll_from(&mut Rng::new(seed), bias)
+            );
+        }
+
+        let mut rng = Rng::new(8);
+        assert_eq!(
+            Personality::roll_from(&mut rng, Personality::default()),
+            Personality::roll(&mut Rng::new(8))
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Bedtimes per life stage and the lights the pet expects turned off while it sleeps.
+
+use crate::clock::TimeOfDay;
+use crate::pet::LifeStage;
+use std::time::Duration;
+
+/// Minutes per happiness point lost while kept awake by the lights.
+pub(crate) const LIGHTS_ON_RATE: u64 = 10;
+
+/// Local times the pet falls asleep and wakes up.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Schedule {
+    pub sleep: TimeOfDay,
+    pub wake: TimeOfDay,
+}
+
+impl Schedule {
+    pub fn is_bedtime(&self, time: TimeOfDay) -> bool {
+        time.is_between(self.sleep, self.wake)
+    }
+}
+
+impl LifeStage {
+    /// `None` for stages that don't sleep.
+    pub fn schedule(&self) -> Option<Schedule> {
+        let (sleep, wake) = match self {
+            LifeStage::Egg => return None,
+            LifeStage::Baby => (TimeOfDay::new(19, 0), TimeOfDay::new(8, 0)),
+            LifeStage::Child => (TimeOfDay::new(20, 0), TimeOfDay::new(8, 0)),
+            LifeStage::Teen => (TimeOfDay::new(21, 30), TimeOfDay::new(8, 30)),
+            LifeStage::Adult => (TimeOfDay::new(22, 0), TimeOfDay::new(7, 0)),
+            LifeStage::Senior => (TimeOfDay::new(21, 0), TimeOfDay::new(6, 0)),
+        };
+
+        Some(Schedule { sleep, wake })
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sleep {
+    pub asleep: bool,
+    pub lights_on: bool,
+    /// How long the lights have been on while asleep.
+    pub(crate) lights_on_for: Duration,
+}
+
+impl Sleep {
+    /// True while asleep in the dark, when needs decay at half speed.
+    pub fn is_resting(&self) -> bool {
+        self.asleep && !self.lights_on
+    }
+}
+
+impl Default for Sleep {
+    fn default() -> Self {
+        Self {
+            asleep: false,
+            lights_on: true,
+            lights_on_for: Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::care::{CareMistakeKind, CALL_TIMEOUT};
+    use crate::pet::{Pet, PetEvent, STAT_MAX, STEP};
+
+    const DAY: u32 = 24 * 60;
+
+    /// Ticks a step at a time from `start`, returning the sleep events and care mistakes with
+    /// the time they happened at. The pet is kept fed and happy so nothing else calls.
+    fn run(pet: &mut Pet, start: TimeOfDay, steps: u32) -> Vec<(PetEvent, TimeOfDay)> {
+        let mut happened = vec![];
+        for step in 1..=steps {
+            pet.stats.hunger = STAT_MAX;
+            pet.stats.happiness = STAT_MAX;
+            let time = start.add(STEP * step);
+            for event in pet.tick(STEP, Some(time)) {
+                if matches!(
+                    event,
+                    PetEvent::FellAsleep | PetEvent::WokeUp | PetEvent::CareMistake(_)
+                ) {
+                    happened.push((event, time));
+                }
+            }
+        }
+        happened
+    }
+
+    #[test]
+    fn sleeps_on_the_stage_schedule() {
+        for stage in [LifeStage::Teen, LifeStage::Adult, LifeStage::Senior] {
+            let mut pet = Pet::quiet(stage);
+            pet.set_lights(false);
+
+            let schedule = stage.schedule().unwrap();
+            let events: Vec<_> = run(&mut pet, TimeOfDay::new(9, 0), DAY)
+                .into_iter()
+                .filter(|(event, _)| matches!(event, PetEvent::FellAsleep | PetEvent::WokeUp))
+                .collect();
+            assert_eq!(
+                events,
+                vec![
+                    (PetEvent::FellAsleep, schedule.sleep),
+                    (PetEvent::WokeUp, schedule.wake),
+                ],
+                "{:?}",
+                stage
+            );
+        }
+    }
+
+    #[test]
+    fn babies_go_to_bed_early() {
+        // a baby only lasts the hour
+        let mut pet = Pet::quiet(LifeStage::Baby);
+        let events = run(&mut pet, TimeOfDay::new(18, 30), 40);
+        assert_eq!(events, vec![(PetEvent::FellAsleep, TimeOfDay::new(19, 0))]);
+    }
+
+    #[test]
+    fn growing_up_overnight_moves_to_the_new_schedule() {
+        // the child hatched an hour ago grows up at 8:00, as it wakes up
+        let mut pet = Pet::quiet(LifeStage::Child);
+        pet.set_lights(false);
+
+        let events = run(&mut pet, TimeOfDay::new(9, 0), DAY);
+        let teen = LifeStage::Teen.schedule().unwrap();
+        assert_eq!(
+            events,
+            vec![
+                (PetEvent::FellAsleep, TimeOfDay::new(20, 0)),
+                (PetEvent::WokeUp, TimeOfDay::new(8, 0)),
+                (PetEvent::FellAsleep, TimeOfDay::new(8, 1)),
+                (PetEvent::WokeUp, teen.wake),
+            ]
+        );
+        assert_eq!(pet.stage, LifeStage::Teen);
+    }
+
+    #[test]
+    fn eggs_and_unset_clocks_dont_sleep() {
+        let mut egg = Pet::quiet(LifeStage::Egg);
+        let events = run(&mut egg, TimeOfDay::new(21, 0), 4);
+        assert!(events.is_empty());
+        assert!(!egg.sleep.asleep);
+
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        let events = pet.tick(STEP * DAY, None);
+        assert!(!events.contains(&PetEvent::FellAsleep));
+        assert!(!pet.sleep.asleep);
+    }
+
+    #[test]
+    fn lights_left_on_are_a_mistake_once_a_night() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        assert!(pet.sleep.lights_on);
+
+        let bedtime = TimeOfDay::new(22, 0);
+        let timeout = (CALL_TIMEOUT.as_secs() / 60) as u32;
+        let events = run(&mut pet, TimeOfDay::new(12, 0), DAY);
+        let lights: Vec<_> = events
+            .iter()
+            .filter(|(event, _)| *event == PetEvent::CareMistake(CareMistakeKind::LightsOn))
+            .map(|(_, time)| *time)
+            .collect();
+
+        // the step it fell asleep on is the first with the lights on
+        assert_eq!(lights, vec![bedtime.add(STEP * (timeout - 1))]);
+    }
+
+    #[test]
+    fn switching_the_lights_off_in_time_avoids_the_mistake() {
+        let mut pet = Pet::quiet(LifeStage::Adult);
+        let timeout = (CALL_TIMEOUT.as_secs() / 60) as u32;
+
+        let events = run(&mut pet, TimeOfDay::new(21, 0), 60 + timeout - 2);
+        assert_eq!(events, vec![(PetEvent::FellAsleep, TimeOfDay::new(22, 0))]);
+        pet.set_lights(false);
+        assert!(pet.sleep.is_resting());
+        let switched_off = TimeOfDay::new(22, timeout as u8 - 2);
+        assert!(run(&mut pet, switched_off, 60).is_empty());
+
+        // the time starts again if they're switched back on
+        pet.set_lights(true);
+        let switched_on = switched_off.add(STEP * 60);
+        assert_eq!(
+            run(&mut pet, switched_on, timeout),
+            vec![(
+                PetEvent::CareMistake(CareMistakeKind::LightsOn),
+                switched_on.add(CALL_TIMEOUT)
+            )]
+        );
+    }
+}
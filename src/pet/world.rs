@@ -0,0 +1,475 @@
+//! Happenings that turn up now and then to break up the pet's day: visitors, presents, the weather
+//! and the odd surprise.
+//!
+//! Each one is a row in [`HAPPENINGS`], picked by weight from the rows whose conditions fit.
+
+use crate::clock::TimeOfDay;
+use crate::i18n::{self, Key};
+use crate::pet::food::FoodKind;
+use crate::pet::LifeStage;
+use crate::random::Rng;
+use std::time::Duration;
+
+/// Happenings nobody looked at are gone after this, their effect still counts.
+pub const HAPPENING_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// Shortest and longest gaps between happenings.
+pub const MIN_GAP: Duration = Duration::from_secs(2 * 60 * 60);
+pub const MAX_GAP: Duration = Duration::from_secs(6 * 60 * 60);
+/// Wait before trying again when nothing fitted.
+pub const RETRY: Duration = Duration::from_secs(30 * 60);
+
+const HATCHED: &[LifeStage] = &[
+    LifeStage::Baby,
+    LifeStage::Child,
+    LifeStage::Teen,
+    LifeStage::Adult,
+    LifeStage::Senior,
+];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HappeningKind {
+    Visitor,
+    Present,
+    Rain,
+    ShootingStar,
+    Butterfly,
+}
+
+impl HappeningKind {
+    pub fn happening(&self) -> &'static Happening {
+        &HAPPENINGS[*self as usize]
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(val: u8) -> Option<Self> {
+        HAPPENINGS.get(val as usize).map(|happening| happening.kind)
+    }
+
+    /// What happened, for the catch up summary and the log.
+    pub fn summary(&self, pet_name: &str) -> String {
+        let key = match self {
+            HappeningKind::Visitor => Key::SummaryVisitor,
+            HappeningKind::Present => Key::SummaryPresent,
+            HappeningKind::Rain => Key::SummaryRain,
+            HappeningKind::ShootingStar => Key::SummaryShootingStar,
+            HappeningKind::Butterfly => Key::SummaryButterfly,
+        };
+
+        i18n::format(key, &[("name", &pet_name)])
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Mood {
+    Any,
+    /// Happiness of 50 or more.
+    Happy,
+    /// Happiness under 50.
+    Sad,
+}
+
+impl Mood {
+    fn fits(&self, happiness: u8) -> bool {
+        match self {
+            Mood::Any => true,
+            Mood::Happy => happiness >= 50,
+            Mood::Sad => happiness < 50,
+        }
+    }
+}
+
+/// A row of the happenings table.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Happening {
+    pub kind: HappeningKind,
+    pub title: Key,
+    pub weight: u32,
+    /// Local times it can happen between, needs the clock set. Any time if `None`.
+    pub window: Option<(TimeOfDay, TimeOfDay)>,
+    pub stages: &'static [LifeStage],
+    pub mood: Mood,
+    /// Outdoor happenings don't happen in the rain.
+    pub outdoors: bool,
+
+    pub happiness: i16,
+    pub hunger: i16,
+    pub gift: Option<(FoodKind, u8)>,
+    /// Weather that lasts this long, keeping the pet indoors where it gets bored twice as fast.
+    pub weather: Duration,
+}
+
+/// Indexed by [`HappeningKind`].
+pub const HAPPENINGS: [Happening; 5] = [
+    Happening {
+        kind: HappeningKind::Visitor,
+        title: Key::HappeningVisitor,
+        weight: 4,
+        window: Some((TimeOfDay::new(9, 0), TimeOfDay::new(19, 0))),
+        stages: HATCHED,
+        mood: Mood::Any,
+        outdoors: true,
+        happiness: 15,
+        hunger: -5,
+        gift: None,
+        weather: Duration::ZERO,
+    },
+    Happening {
+        kind: HappeningKind::Present,
+        title: Key::HappeningPresent,
+        weight: 2,
+        window: None,
+        stages: HATCHED,
+        mood: Mood::Any,
+        outdoors: false,
+        happiness: 5,
+        hunger: 0,
+        gift: Some((FoodKind::Treat, 1)),
+        weather: Duration::ZERO,
+    },
+    Happening {
+        kind: HappeningKind::Rain,
+        title: Key::HappeningRain,
+        weight: 3,
+        window: None,
+        stages: HATCHED,
+        mood: Mood::Any,
+        outdoors: true,
+        happiness: -5,
+        hunger: 0,
+        gift: None,
+        weather: Duration::from_secs(90 * 60),
+    },
+    Happening {
+        kind: HappeningKind::ShootingStar,
+        title: Key::HappeningShootingStar,
+        weight: 2,
+        window: Some((TimeOfDay::new(19, 0), TimeOfDay::new(5, 0))),
+        stages: HATCHED,
+        mood: Mood::Any,
+        outdoors: true,
+        happiness: 20,
+        hunger: 0,
+        gift: None,
+        weather: Duration::ZERO,
+    },
+    Happening {
+        kind: HappeningKind::Butterfly,
+        title: Key::HappeningButterfly,
+        weight: 3,
+        window: Some((TimeOfDay::new(8, 0), TimeOfDay::new(18, 0))),
+        stages: &[LifeStage::Baby, LifeStage::Child],
+        mood: Mood::Sad,
+        outdoors: true,
+        happiness: 10,
+        hunger: -2,
+        gift: None,
+        weather: Duration::ZERO,
+    },
+];
+
+/// What a happening's conditions are checked against.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Conditions {
+    pub time: Option<TimeOfDay>,
+    pub stage: LifeStage,
+    pub happiness: u8,
+    pub raining: bool,
+}
+
+impl Happening {
+    pub fn fits(&self, conditions: &Conditions) -> bool {
+        let in_window = match (self.window, conditions.time) {
+            (None, _) => true,
+            (Some((start, end)), Some(time)) => time.is_between(start, end),
+            (Some(_), None) => false,
+        };
+
+        in_window
+            && self.stages.contains(&conditions.stage)
+            && self.mood.fits(conditions.happiness)
+            && !(self.outdoors && conditions.raining)
+    }
+}
+
+/// Picks a happening by weight from the ones that fit, `None` if none do.
+pub fn pick(rng: &mut Rng, conditions: &Conditions) -> Option<HappeningKind> {
+    let fitting: Vec<_> = HAPPENINGS
+        .iter()
+        .filter(|happening| happening.fits(conditions))
+        .map(|happening| (happening.kind, happening.weight))
+        .collect();
+
+    rng.weighted(&fitting).copied()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct World {
+    /// Happening waiting to be seen.
+    pub current: Option<HappeningKind>,
+    pub(crate) current_for: Duration,
+    /// Time until the next happening.
+    pub(crate) next_in: Duration,
+    pub(crate) rain_left: Duration,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self {
+            current: None,
+            current_for: Duration::ZERO,
+            next_in: MIN_GAP,
+            rain_left: Duration::ZERO,
+        }
+    }
+}
+
+impl World {
+    pub fn is_raining(&self) -> bool {
+        !self.rain_left.is_zero()
+    }
+
+    /// Takes the happening waiting to be seen.
+    pub fn see(&mut self) -> Option<HappeningKind> {
+        self.current_for = Duration::ZERO;
+        self.current.take()
+    }
+
+    /// Moves on by `elapsed`, returning a happening that just started. Nothing starts while
+    /// `conditions` is `None`, e.g. while the pet sleeps.
+    pub(crate) fn tick(
+        &mut self,
+        elapsed: Duration,
+        rng: &mut Rng,
+        conditions: Option<Conditions>,
+    ) -> Option<HappeningKind> {
+        self.rain_left = self.rain_left.saturating_sub(elapsed);
+        if self.current.is_some() {
+            self.current_for += elapsed;
+            if self.current_for >= HAPPENING_TIMEOUT {
+                self.see();
+            }
+        }
+
+        self.next_in = self.next_in.saturating_sub(elapsed);
+        let conditions = conditions?;
+        if !self.next_in.is_zero() {
+            return None;
+        }
+
+        let Some(kind) = pick(rng, &conditions) else {
+            self.next_in = RETRY;
+            return None;
+        };
+
+        let gap = rng.range(MIN_GAP.as_secs() as u32, MAX_GAP.as_secs() as u32);
+        self.next_in = Duration::from_secs(gap as u64);
+        self.current = Some(kind);
+        self.current_for = Duration::ZERO;
+        if !kind.happening().weather.is_zero() {
+            self.rain_left = kind.happening().weather;
+        }
+
+        Some(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pet::STEP;
+
+    const DAYS: u32 = 365;
+
+    fn conditions(time: Option<TimeOfDay>, stage: LifeStage, happiness: u8) -> Conditions {
+        Conditions {
+            time,
+            stage,
+            happiness,
+            raining: false,
+        }
+    }
+
+    fn fitting(conditions: &Conditions) -> Vec<HappeningKind> {
+        HAPPENINGS
+            .iter()
+            .filter(|happening| happening.fits(conditions))
+            .map(|happening| happening.kind)
+            .collect()
+    }
+
+    #[test]
+    fn preconditions() {
+        use HappeningKind::*;
+        let noon = Some(TimeOfDay::new(12, 0));
+        let night = Some(TimeOfDay::new(23, 0));
+
+        assert_eq!(
+            fitting(&conditions(noon, LifeStage::Child, 20)),
+            vec![Visitor, Present, Rain, Butterfly]
+        );
+        // butterflies only visit sad little ones
+        assert_eq!(
+            fitting(&conditions(noon, LifeStage::Child, 50)),
+            vec![Visitor, Present, Rain]
+        );
+        assert_eq!(
+            fitting(&conditions(noon, LifeStage::Adult, 20)),
+            vec![Visitor, Present, Rain]
+        );
+        assert_eq!(
+            fitting(&conditions(night, LifeStage::Adult, 80)),
+            vec![Present, Rain, ShootingStar]
+        );
+        // windows need the clock
+        assert_eq!(
+            fitting(&conditions(None, LifeStage::Baby, 20)),
+            vec![Present, Rain]
+        );
+        assert_eq!(
+            fitting(&Conditions {
+                raining: true,
+                ..conditions(noon, LifeStage::Child, 20)
+            }),
+            vec![Present]
+        );
+        assert!(fitting(&conditions(noon, LifeStage::Egg, 20)).is_empty());
+    }
+
+    #[test]
+    fn pick_follows_the_weights() {
+        let conditions = conditions(Some(TimeOfDay::new(12, 0)), LifeStage::Child, 20);
+        let mut rng = Rng::new(2024);
+        let picks = 12_000;
+        let mut counts = [0u32; HAPPENINGS.len()];
+        for _ in 0..picks {
+            counts[pick(&mut rng, &conditions).unwrap() as usize] += 1;
+        }
+
+        // visitor 4, present 2, rain 3, butterfly 3 out of 12
+        let expected = [4000, 2000, 3000, 0, 3000];
+        for (count, expected) in counts.iter().zip(expected) {
+            assert!(count.abs_diff(expected) <= picks / 50, "{:?}", counts);
+        }
+    }
+
+    /// Runs the scheduler for [`DAYS`] with the clock set, a pet that changes between a child and
+    /// an adult and a good and a bad mood from day to day, and sleeps from 20:00 to 8:00.
+    fn simulate(seed: u32) -> Vec<(u32, HappeningKind, Conditions)> {
+        let mut world = World::default();
+        let mut rng = Rng::new(seed);
+        let mut happened = vec![];
+
+        for minute in 1..=DAYS * 24 * 60 {
+            let time = TimeOfDay::from_secs(minute % (24 * 60) * 60);
+            let awake = time.is_between(TimeOfDay::new(8, 0), TimeOfDay::new(20, 0));
+            let day = minute / (24 * 60);
+            let happiness = if day % 2 == 0 { 20 } else { 80 };
+            let stage = if day % 4 < 2 {
+                LifeStage::Child
+            } else {
+                LifeStage::Adult
+            };
+            let conditions = awake.then(|| Conditions {
+                raining: world.is_raining(),
+                ..conditions(Some(time), stage, happiness)
+            });
+
+            if let Some(kind) = world.tick(STEP, &mut rng, conditions) {
+                happened.push((minute, kind, conditions.unwrap()));
+                world.see();
+            }
+        }
+
+        happened
+    }
+
+    #[test]
+    fn scheduler_over_a_year() {
+        let happened = simulate(77);
+
+        let mut counts = [0f32; HAPPENINGS.len()];
+        let mut expected = [0f32; HAPPENINGS.len()];
+        let mut last = None;
+        for (minute, kind, conditions) in &happened {
+            let happening = kind.happening();
+            assert!(happening.fits(conditions), "{:?} {:?}", kind, conditions);
+
+            // nothing starts before the gap is up, asleep pets push it back
+            if let Some(last) = last {
+                assert!(STEP * (minute - last) >= MIN_GAP);
+            }
+            last = Some(*minute);
+
+            counts[*kind as usize] += 1.0;
+            let fits = fitting(conditions);
+            let total: u32 = fits.iter().map(|kind| kind.happening().weight).sum();
+            for fit in fits {
+                expected[fit as usize] += fit.happening().weight as f32 / total as f32;
+            }
+        }
+
+        // 12 waking hours a day with a 2 to 6 hour gap
+        let days = DAYS as usize;
+        assert!((2 * days..=6 * days).contains(&happened.len()));
+        for (count, expected) in counts.iter().zip(expected) {
+            assert!(
+                (count - expected).abs() <= expected * 0.15 + 5.0,
+                "{:?} {:?}",
+                counts,
+                expected
+            );
+        }
+        assert!(counts.iter().all(|count| *count > 0.0), "{:?}", counts);
+    }
+
+    #[test]
+    fn same_seed_same_schedule() {
+        let first: Vec<_> = simulate(5).into_iter().take(200).collect();
+        assert_eq!(first, simulate(5).into_iter().take(200).collect::<Vec<_>>());
+        assert_ne!(first, simulate(6).into_iter().take(200).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn waits_while_nothing_can_happen() {
+        let mut world = World::default();
+        let mut rng = Rng::new(1);
+
+        assert_eq!(world.tick(MIN_GAP, &mut rng, None), None);
+        assert_eq!(world.tick(STEP, &mut rng, None), None);
+
+        let egg = conditions(None, LifeStage::Egg, 50);
+        assert_eq!(world.tick(STEP, &mut rng, Some(egg)), None);
+        assert_eq!(world.next_in, RETRY);
+
+        let baby = conditions(None, LifeStage::Baby, 50);
+        world.next_in = STEP;
+        assert!(world.tick(STEP, &mut rng, Some(baby)).is_some());
+        assert!((MIN_GAP..MAX_GAP).contains(&world.next_in));
+    }
+
+    #[test]
+    fn rain_and_unseen_happenings_pass() {
+        let mut world = World {
+            next_in: STEP,
+            ..Default::default()
+        };
+        let mut rng = Rng::new(1);
+        let conditions = conditions(None, LifeStage::Adult, 50);
+
+        while world.tick(STEP, &mut rng, Some(conditions)) != Some(HappeningKind::Rain) {
+            world.next_in = STEP;
+            world.see();
+        }
+        assert!(world.is_raining());
+        assert_eq!(world.current, Some(HappeningKind::Rain));
+
+        world.tick(HAPPENING_TIMEOUT, &mut rng, None);
+        assert_eq!(world.current, None);
+        assert!(world.is_raining());
+        world.tick(HappeningKind::Rain.happening().weather, &mut rng, None);
+        assert!(!world.is_raining());
+    }
+}
@@ -0,0 +1,13 @@
+use super::SeedSource;
+use esp_idf_svc::sys;
+
+/// The ESP32-S3 hardware RNG, truly random while the radio or the bootloader entropy source is on
+/// and pseudo random otherwise, which is still plenty for a seed.
+#[derive(Copy, Clone, Default)]
+pub struct HardwareSeed;
+
+impl SeedSource for HardwareSeed {
+    fn seed(&self) -> u32 {
+        unsafe { sys::esp_random() }
+    }
+}
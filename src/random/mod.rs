@@ -0,0 +1,156 @@
+//! Seeded randomness, everything random takes an [`Rng`] so a run can be replayed exactly from
+//! its seed.
+//!
+//! Seeds come from a [`SeedSource`], the hardware RNG on the device and [`FixedSeed`] on the host.
+
+#[cfg(target_os = "espidf")]
+pub mod esp;
+
+/// Where fresh seeds come from.
+pub trait SeedSource {
+    fn seed(&self) -> u32;
+}
+
+/// Always the same seed, for replaying a run.
+#[derive(Copy, Clone, Debug)]
+pub struct FixedSeed(pub u32);
+
+impl SeedSource for FixedSeed {
+    fn seed(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Xorshift, small and fast with a single word of state to save.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rng(u32);
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        // xorshift never leaves zero
+        Self(if seed == 0 { 0x9E37_79B9 } else { seed })
+    }
+
+    pub fn from_source(source: &dyn SeedSource) -> Self {
+        Self::new(source.seed())
+    }
+
+    /// Current state, [`Rng::new`] with it carries on from the same point.
+    pub fn state(&self) -> u32 {
+        self.0
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    /// Uniform-ish value in `low..high`.
+    pub fn range(&mut self, low: u32, high: u32) -> u32 {
+        low + self.next_u32() % (high - low).max(1)
+    }
+
+    /// True `percent` times out of a hundred.
+    pub fn chance(&mut self, percent: u8) -> bool {
+        self.range(0, 100) < percent as u32
+    }
+
+    /// Picks an item with probability proportional to its weight, `None` if they're all zero.
+    pub fn weighted<'a, T>(&mut self, items: &'a [(T, u32)]) -> Option<&'a T> {
+        let total: u32 = items.iter().map(|(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = self.range(0, total);
+        for (item, weight) in items {
+            if roll < *weight {
+                return Some(item);
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(rng: &mut Rng) -> Vec<u32> {
+        (0..16).map(|_| rng.next_u32()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        assert_eq!(sequence(&mut Rng::new(42)), sequence(&mut Rng::new(42)));
+        assert_ne!(sequence(&mut Rng::new(42)), sequence(&mut Rng::new(43)));
+
+        let source = FixedSeed(7);
+        assert_eq!(
+            sequence(&mut Rng::from_source(&source)),
+            sequence(&mut Rng::new(7))
+        );
+    }
+
+    #[test]
+    fn zero_seed_still_moves() {
+        let mut rng = Rng::new(0);
+        let values = sequence(&mut rng);
+        assert!(values.iter().all(|value| *value != 0));
+        assert_eq!(values, sequence(&mut Rng::new(0x9E37_79B9)));
+    }
+
+    #[test]
+    fn state_carries_on() {
+        let mut rng = Rng::new(1234);
+        sequence(&mut rng);
+
+        let mut resumed = Rng::new(rng.state());
+        assert_eq!(sequence(&mut resumed), sequence(&mut rng));
+    }
+
+    #[test]
+    fn range_stays_in_bounds() {
+        let mut rng = Rng::new(5);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let value = rng.range(10, 16);
+            assert!((10..16).contains(&value));
+            seen[value as usize - 10] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+
+        assert_eq!(rng.range(3, 3), 3);
+    }
+
+    #[test]
+    fn chance_extremes() {
+        let mut rng = Rng::new(9);
+        assert!((0..1000).all(|_| !rng.chance(0)));
+        assert!((0..1000).all(|_| rng.chance(100)));
+    }
+
+    #[test]
+    fn weighted_follows_the_weights() {
+        let mut rng = Rng::new(11);
+        let items = [('a', 1), ('b', 0), ('c', 3)];
+        let mut counts = [0u32; 3];
+        for _ in 0..4000 {
+            match rng.weighted(&items) {
+                Some('a') => counts[0] += 1,
+                Some('b') => counts[1] += 1,
+                Some('c') => counts[2] += 1,
+                _ => unreachable!(),
+            }
+        }
+
+        assert_eq!(counts[1], 0);
+        assert!((900..1100).contains(&counts[0]), "{:?}", counts);
+        assert!((2900..3100).contains(&counts[2]), "{:?}", counts);
+        assert_eq!(rng.weighted(&[('a', 0)]), None);
+        assert_eq!(rng.weighted::<char>(&[]), None);
+    }
+}
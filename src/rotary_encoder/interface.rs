@@ -1,4 +1,6 @@
+use crate::console::Console;
 use crate::rotary_encoder::{EncoderData, LatchMode, RotaryEncoder};
+use crate::supervisor::{Task, TaskSpec};
 use crate::{EventSet, Events};
 use esp_idf_svc::hal::delay::FreeRtos;
 use esp_idf_svc::hal::gpio::{AnyInputPin, Input, InterruptType, PinDriver};
@@ -74,22 +76,16 @@ impl PartialEq<u32> for REEventSet {
     }
 }
 
-fn encoder_task(mut encoder: RotaryEncoder) -> ! {
+fn encoder_task(encoder: &mut RotaryEncoder) -> Result<(), String> {
     loop {
-        match encoder.restart_isr() {
-            Ok(_) => {}
-            Err(e) => loop {
-                log::error!("Error resetting isr for Rotary Encoder, {}", e);
-                FreeRtos::delay_ms(100);
-                if encoder.restart_isr().is_ok() {
-                    break;
-                }
-            },
-        }
+        encoder
+            .restart_isr()
+            .map_err(|err| format!("Error resetting isr for Rotary Encoder, {}", err))?;
 
         let _ = ROTARY_EVENTS.wait_for_any();
 
         encoder.update();
+        crate::device::mark_activity();
 
         match ROTARY_ENCODER.write() {
             Ok(mut data) => data.set(&encoder.data),
@@ -103,6 +99,15 @@ static ROTARY_ENCODER: Lazy<RwLock<EncoderData>> =
 
 pub mod rotary_interface {
     use super::ROTARY_ENCODER;
+    use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+    static CONSUMED_DETENTS: AtomicI32 = AtomicI32::new(0);
+    static REVERSED: AtomicBool = AtomicBool::new(false);
+
+    /// Flips the sign of [`take_rotation`], for encoders wired the other way round.
+    pub fn set_reversed(reversed: bool) {
+        REVERSED.store(reversed, Ordering::Relaxed);
+    }
 
     pub fn get_position() -> Result<i8, String> {
         match ROTARY_ENCODER.read() {
@@ -110,6 +115,21 @@ pub mod rotary_interface {
             Err(_) => Err(String::from("Failed to gain read lock for rotary encoder")),
         }
     }
+
+    /// Detents turned since the last call, positive counts up like the position.
+    pub fn take_rotation() -> Result<i32, String> {
+        match ROTARY_ENCODER.read() {
+            Ok(data) => {
+                let detents = data.get_detents();
+                let turned = detents - CONSUMED_DETENTS.swap(detents, Ordering::Relaxed);
+                match REVERSED.load(Ordering::Relaxed) {
+                    true => Ok(-turned),
+                    false => Ok(turned),
+                }
+            }
+            Err(_) => Err(String::from("Failed to gain read lock for rotary encoder")),
+        }
+    }
 }
 
 fn on_pin_trigger() {
@@ -119,7 +139,8 @@ fn on_pin_trigger() {
 pub fn rotary_encoder_init(
     mut pin_a: PinDriver<'static, AnyInputPin, Input>,
     mut pin_b: PinDriver<'static, AnyInputPin, Input>,
-) {
+    mode: LatchMode,
+) -> Task {
     pin_a.set_interrupt_type(InterruptType::AnyEdge).unwrap();
     pin_b.set_interrupt_type(InterruptType::AnyEdge).unwrap();
 
@@ -128,11 +149,24 @@ pub fn rotary_encoder_init(
         pin_b.subscribe(on_pin_trigger).unwrap();
     }
 
-    let encoder = RotaryEncoder::new(pin_a, pin_b, LatchMode::TWO3, (-7, 0));
+    let mut encoder = RotaryEncoder::new(pin_a, pin_b, mode, (-7, 0));
+
+    Task::new(TaskSpec::new("encoder_task", 32 * 100), move |_| {
+        encoder_task(&mut encoder)
+    })
+}
 
-    std::thread::Builder::new()
-        .name("encoder_task".into())
-        .stack_size(32 * 100)
-        .spawn(move || encoder_task(encoder))
-        .unwrap();
+pub fn register_commands(console: &mut Console) {
+    console.register("enc", "", "show the encoder state", |_| {
+        let data = ROTARY_ENCODER
+            .read()
+            .map_err(|_| String::from("Failed to gain read lock for rotary encoder"))?;
+
+        Ok(format!(
+            "position {} detents {} rpm {:.1}",
+            data.get_position(),
+            data.get_detents(),
+            data.get_rpm()
+        ))
+    });
 }
@@ -31,6 +31,8 @@ pub struct EncoderData {
     position_ext_prev: i8,
     position_ext_time: SystemTime,
     position_ext_time_prev: SystemTime,
+    /// Raw steps moved since start, not limited to `range`.
+    steps: i32,
 }
 
 impl EncoderData {
@@ -50,6 +52,7 @@ impl Default for EncoderData {
             position_ext_prev: Default::default(),
             position_ext_time: SystemTime::now(),
             position_ext_time_prev: SystemTime::now(),
+            steps: 0,
         }
     }
 }
@@ -95,6 +98,7 @@ impl RotaryEncoder {
                 position_ext_prev: 0i8,
                 position_ext_time: SystemTime::now(),
                 position_ext_time_prev: SystemTime::now(),
+                steps: 0,
             },
         };
         encoder.prev_state = encoder.poll_state();
@@ -109,7 +113,9 @@ impl RotaryEncoder {
             return;
         }
 
-        self.data.position += ENCODER_DIRECTION[(self.prev_state | (curr_state << 2)) as usize];
+        let step = ENCODER_DIRECTION[(self.prev_state | (curr_state << 2)) as usize];
+        self.data.position += step;
+        self.data.steps += step as i32;
 
         if self.data.range.0 > self.data.position >> 1 {
             self.data.position = self.data.range.1 << 2;
@@ -155,6 +161,14 @@ impl EncoderData {
         self.position_ext
     }
 
+    /// Detents moved since start, unlike the position this never wraps.
+    pub fn get_detents(&self) -> i32 {
+        match &self.mode {
+            LatchMode::FOUR0 | LatchMode::FOUR3 => self.steps >> 2,
+            LatchMode::TWO3 => self.steps >> 1,
+        }
+    }
+
     pub fn get_direction(&self) -> Direction {
         let mut result = Direction::NoRotation;
 
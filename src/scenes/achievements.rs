@@ -0,0 +1,111 @@
+use crate::achievements::{Achievements, ACHIEVEMENTS};
+use crate::i18n::{self, Key};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+const DESCRIPTION_HEIGHT: u32 = 24;
+
+fn rows(achievements: &Achievements) -> Vec<String> {
+    ACHIEVEMENTS
+        .iter()
+        .enumerate()
+        .map(|(idx, achievement)| match achievements.is_unlocked(idx) {
+            true => format!("* {}", i18n::text(achievement.name)),
+            false => format!("  {}", i18n::text(achievement.name)),
+        })
+        .collect()
+}
+
+/// Every achievement with the unlocked ones starred, the selected one's description underneath.
+/// A long press goes back.
+pub struct AchievementsScene {
+    list: ListView,
+    described: Option<usize>,
+    dirty: bool,
+}
+
+impl AchievementsScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(
+                    Point::new(4, 32),
+                    Size::new(SCREEN.width - 8, SCREEN.height - 36 - DESCRIPTION_HEIGHT),
+                ),
+                20,
+                rows(&Achievements::default()),
+            ),
+            described: None,
+            dirty: true,
+        }
+    }
+
+    fn description_bounds() -> Rectangle {
+        Rectangle::new(
+            Point::new(0, (SCREEN.height - DESCRIPTION_HEIGHT) as i32),
+            Size::new(SCREEN.width, DESCRIPTION_HEIGHT),
+        )
+    }
+}
+
+impl Default for AchievementsScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for AchievementsScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        for (idx, row) in rows(&ctx.achievements.lock().unwrap())
+            .into_iter()
+            .enumerate()
+        {
+            self.list.set_item(idx, row);
+        }
+
+        match input.and_then(|input| self.list.handle_input(input)) {
+            Some(ListAction::Back) => Transition::Pop,
+            Some(ListAction::Select(_)) | None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                i18n::text(Key::AchievementsTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.list.redraw(display, theme);
+
+        let selected = self.list.selected();
+        if self.described != Some(selected) {
+            let bounds = Self::description_bounds();
+            let _ = display.fill_solid(&bounds, theme.background);
+            let _ = Text::with_baseline(
+                i18n::text(ACHIEVEMENTS[selected].description),
+                bounds.top_left + Point::new(4, 4),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.described = Some(selected);
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.described = None;
+        self.list.invalidate();
+    }
+}
@@ -0,0 +1,193 @@
+use crate::i18n::{self, Key, Language};
+use crate::scenes::set_time::{SetTimeScene, TimeTarget};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::clock_view::ClockView;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+const HINT_HEIGHT: u32 = 20;
+
+fn rows(ctx: &Context) -> Vec<String> {
+    let format = ctx.settings.lock().unwrap().clock_format;
+    let alarm = ctx.alarm.lock().unwrap();
+    let enabled = match alarm.enabled() {
+        true => Key::Yes,
+        false => Key::No,
+    };
+
+    vec![
+        i18n::format(Key::AlarmEnabled, &[("value", &i18n::text(enabled))]),
+        i18n::format(Key::AlarmTime, &[("time", &alarm.time().format(format))]),
+    ]
+}
+
+/// Turns the alarm on or off and sets when it goes off. A long press goes back.
+pub struct AlarmScene {
+    list: ListView,
+    dirty: bool,
+}
+
+impl AlarmScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(Point::new(4, 32), SCREEN - Size::new(8, 36)),
+                20,
+                vec![String::new(); 2],
+            ),
+            dirty: true,
+        }
+    }
+}
+
+impl Default for AlarmScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for AlarmScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let transition = match input.and_then(|input| self.list.handle_input(input)) {
+            Some(ListAction::Select(0)) => {
+                let mut alarm = ctx.alarm.lock().unwrap();
+                let enabled = alarm.enabled();
+                alarm.set_enabled(!enabled);
+                Transition::Stay
+            }
+            Some(ListAction::Select(_)) => {
+                let format = ctx.settings.lock().unwrap().clock_format;
+                let time = ctx.alarm.lock().unwrap().time();
+                Transition::Push(Box::new(SetTimeScene::new(TimeTarget::Alarm, time, format)))
+            }
+            Some(ListAction::Back) => Transition::Pop,
+            None => Transition::Stay,
+        };
+
+        for (idx, row) in rows(ctx).into_iter().enumerate() {
+            self.list.set_item(idx, row);
+        }
+
+        transition
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                i18n::text(Key::AlarmTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.list.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.list.invalidate();
+    }
+}
+
+/// Pushed over whatever is showing while the alarm rings, going back once it's been snoozed,
+/// stopped or has given up. A click snoozes and a long press stops it.
+pub struct RingingScene {
+    view: ClockView,
+    title: String,
+    /// Title and language last drawn.
+    shown: Option<(String, Language)>,
+}
+
+impl RingingScene {
+    pub fn new(ctx: &Context) -> Self {
+        let settings = ctx.settings.lock().unwrap();
+
+        Self {
+            view: ClockView::new(
+                Rectangle::new(
+                    Point::new(0, 30),
+                    Size::new(SCREEN.width, SCREEN.height - 30 - HINT_HEIGHT),
+                ),
+                settings.clock_face,
+                settings.clock_format,
+            ),
+            title: String::new(),
+            shown: None,
+        }
+    }
+}
+
+impl Scene for RingingScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let settings = ctx.settings.lock().unwrap();
+        let mut alarm = ctx.alarm.lock().unwrap();
+        match input {
+            Some(Input::Click) => alarm.snooze(&*ctx.clock),
+            Some(Input::LongPress) => alarm.stop(),
+            _ => {}
+        }
+        if !alarm.is_ringing() {
+            return Transition::Pop;
+        }
+
+        self.title = i18n::format(
+            Key::RingingTitle,
+            &[("time", &alarm.time().format(settings.clock_format))],
+        );
+        self.view
+            .set_style(settings.clock_face, settings.clock_format);
+        self.view
+            .set_time(ctx.clock.now().local_time(settings.utc_offset));
+
+        Transition::Stay
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        let showing = (self.title.clone(), i18n::language());
+        if self.shown.as_ref() != Some(&showing) {
+            let centred = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Top)
+                .build();
+            let _ = display.fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(SCREEN.width, 30)),
+                theme.background,
+            );
+            let _ = Text::with_text_style(
+                &self.title,
+                Point::new(SCREEN.width as i32 / 2, 4),
+                theme.title_style(),
+                centred,
+            )
+            .draw(display);
+
+            let hint = Rectangle::new(
+                Point::new(0, (SCREEN.height - HINT_HEIGHT) as i32),
+                Size::new(SCREEN.width, HINT_HEIGHT),
+            );
+            let _ = display.fill_solid(&hint, theme.background);
+            let _ = Text::with_text_style(
+                i18n::text(Key::RingingHint),
+                Point::new(SCREEN.width as i32 / 2, hint.top_left.y + 4),
+                theme.text_style(),
+                centred,
+            )
+            .draw(display);
+            self.shown = Some(showing);
+        }
+
+        let _ = self.view.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.shown = None;
+        self.view.invalidate();
+    }
+}
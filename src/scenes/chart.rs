@@ -0,0 +1,185 @@
+use crate::history::{downsample, Stat, SAMPLE_INTERVAL};
+use crate::i18n::{self, Key};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::chart::{Chart, ChartStyle};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+const AXIS_HEIGHT: u32 = 14;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Zoom {
+    Day,
+    Week,
+}
+
+impl Zoom {
+    fn span(&self) -> Duration {
+        match self {
+            Zoom::Day => Duration::from_secs(24 * 60 * 60),
+            Zoom::Week => Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+
+    fn samples(&self) -> usize {
+        (self.span().as_secs() / SAMPLE_INTERVAL.as_secs()) as usize
+    }
+
+    /// Points plotted, a week is shown as a bar per six hours.
+    fn buckets(&self) -> usize {
+        match self {
+            Zoom::Day => self.samples(),
+            Zoom::Week => 28,
+        }
+    }
+
+    fn style(&self) -> ChartStyle {
+        match self {
+            Zoom::Day => ChartStyle::Line,
+            Zoom::Week => ChartStyle::Bar,
+        }
+    }
+}
+
+fn hours_ago(samples: usize) -> u64 {
+    samples as u64 * SAMPLE_INTERVAL.as_secs() / 3600
+}
+
+/// Charts a stat over the last day or week. Turning the encoder scrolls back in time, a click
+/// zooms between a day and a week, a double click picks the next stat and a long press goes back.
+pub struct ChartScene {
+    chart: Chart,
+    stat: usize,
+    zoom: Zoom,
+    /// Samples between the newest and the right edge of the chart.
+    ago: usize,
+    header: String,
+    axis: (String, String),
+    dirty: bool,
+}
+
+impl ChartScene {
+    pub fn new() -> Self {
+        Self {
+            chart: Chart::new(
+                Rectangle::new(
+                    Point::new(4, 32),
+                    Size::new(SCREEN.width - 8, SCREEN.height - 36 - AXIS_HEIGHT),
+                ),
+                Zoom::Day.style(),
+            ),
+            stat: 0,
+            zoom: Zoom::Day,
+            ago: 0,
+            header: String::new(),
+            axis: (String::new(), String::new()),
+            dirty: true,
+        }
+    }
+}
+
+impl Default for ChartScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for ChartScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        match input {
+            Some(Input::Rotate(detents)) => {
+                let step = (self.zoom.samples() / 8) as i64;
+                self.ago = (self.ago as i64 - detents as i64 * step).max(0) as usize;
+            }
+            Some(Input::Click) => {
+                self.zoom = match self.zoom {
+                    Zoom::Day => Zoom::Week,
+                    Zoom::Week => Zoom::Day,
+                };
+                self.chart.set_style(self.zoom.style());
+            }
+            Some(Input::DoubleClick) => self.stat = (self.stat + 1) % Stat::ALL.len(),
+            Some(Input::LongPress) => return Transition::Pop,
+            None => {}
+        }
+
+        let history = ctx.history.lock().unwrap();
+        let count = self.zoom.samples();
+        self.ago = self.ago.min(history.samples.len().saturating_sub(count));
+
+        let stat = Stat::ALL[self.stat];
+        let window = history.window(count, self.ago);
+        drop(history);
+
+        self.chart.set_values(
+            downsample(&window, self.zoom.buckets(), stat),
+            stat.max(window.iter()),
+        );
+
+        let header = format!("{} ({})", stat.name(), stat.max(window.iter()));
+        let axis = (
+            format!("-{}h", hours_ago(self.ago + count)),
+            match self.ago {
+                0 => String::from(i18n::text(Key::ChartNow)),
+                ago => format!("-{}h", hours_ago(ago)),
+            },
+        );
+        if header != self.header || axis != self.axis {
+            self.header = header;
+            self.axis = axis;
+            self.dirty = true;
+        }
+
+        Transition::Stay
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = display.fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(SCREEN.width, 30)),
+                theme.background,
+            );
+            let _ = Text::with_baseline(
+                &self.header,
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+
+            let axis = Rectangle::new(
+                Point::new(0, (SCREEN.height - AXIS_HEIGHT) as i32),
+                Size::new(SCREEN.width, AXIS_HEIGHT),
+            );
+            let _ = display.fill_solid(&axis, theme.background);
+            let _ = Text::with_baseline(
+                &self.axis.0,
+                axis.top_left + Point::new(4, 2),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            let _ = Text::with_text_style(
+                &self.axis.1,
+                Point::new(SCREEN.width as i32 - 4, axis.top_left.y + 2),
+                theme.text_style(),
+                TextStyleBuilder::new()
+                    .alignment(Alignment::Right)
+                    .baseline(Baseline::Top)
+                    .build(),
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.chart.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.chart.invalidate();
+    }
+}
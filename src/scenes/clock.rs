@@ -0,0 +1,128 @@
+use crate::clock::{ClockFace, TimeOfDay};
+use crate::i18n::{self, Key, Language};
+use crate::scenes::set_time::{SetTimeScene, TimeTarget};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::clock_view::ClockView;
+use crate::ui::{wrap_index, Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+const STATUS_HEIGHT: u32 = 24;
+const HINT_HEIGHT: u32 = 20;
+
+/// Desk clock in the face and format from the settings, with the alarm or a reminder to set the
+/// clock above it. Turning switches the face, a click sets the time and a long press goes back.
+pub struct ClockScene {
+    view: ClockView,
+    status: String,
+    shown_status: Option<String>,
+    /// Language the hint was last drawn in.
+    shown_hint: Option<Language>,
+}
+
+impl ClockScene {
+    pub fn new(ctx: &Context) -> Self {
+        let settings = ctx.settings.lock().unwrap();
+
+        Self {
+            view: ClockView::new(
+                Rectangle::new(
+                    Point::new(0, STATUS_HEIGHT as i32),
+                    Size::new(SCREEN.width, SCREEN.height - STATUS_HEIGHT - HINT_HEIGHT),
+                ),
+                settings.clock_face,
+                settings.clock_format,
+            ),
+            status: String::new(),
+            shown_status: None,
+            shown_hint: None,
+        }
+    }
+}
+
+impl Scene for ClockScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let mut settings = ctx.settings.lock().unwrap();
+        let now = ctx.clock.now().local_time(settings.utc_offset);
+
+        match input {
+            Some(Input::Rotate(detents)) => {
+                let face = wrap_index(
+                    settings.clock_face.to_u8() as usize,
+                    detents,
+                    ClockFace::ALL.len(),
+                );
+                settings.clock_face = ClockFace::ALL[face];
+            }
+            Some(Input::Click) => {
+                return Transition::Push(Box::new(SetTimeScene::new(
+                    TimeTarget::Clock,
+                    now.unwrap_or(TimeOfDay::new(12, 0)),
+                    settings.clock_format,
+                )));
+            }
+            Some(Input::LongPress) => return Transition::Pop,
+            Some(Input::DoubleClick) | None => {}
+        }
+
+        self.view
+            .set_style(settings.clock_face, settings.clock_format);
+        self.view.set_time(now);
+
+        let alarm = ctx.alarm.lock().unwrap();
+        self.status = match (now, alarm.enabled()) {
+            (None, _) => String::from(i18n::text(Key::ClockUnset)),
+            (Some(_), true) => i18n::format(
+                Key::MoreAlarm,
+                &[("time", &alarm.time().format(settings.clock_format))],
+            ),
+            (Some(_), false) => String::new(),
+        };
+
+        Transition::Stay
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.shown_status.as_ref() != Some(&self.status) {
+            let _ = display.fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(SCREEN.width, STATUS_HEIGHT)),
+                theme.background,
+            );
+            let _ = Text::with_baseline(
+                &self.status,
+                Point::new(4, 4),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.shown_status = Some(self.status.clone());
+        }
+
+        if self.shown_hint != Some(i18n::language()) {
+            let bounds = Rectangle::new(
+                Point::new(0, (SCREEN.height - HINT_HEIGHT) as i32),
+                Size::new(SCREEN.width, HINT_HEIGHT),
+            );
+            let _ = display.fill_solid(&bounds, theme.background);
+            let _ = Text::with_baseline(
+                i18n::text(Key::ClockHint),
+                bounds.top_left + Point::new(4, 4),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.shown_hint = Some(i18n::language());
+        }
+
+        let _ = self.view.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.shown_status = None;
+        self.shown_hint = None;
+        self.view.invalidate();
+    }
+}
@@ -0,0 +1,97 @@
+use crate::i18n::{self, Key};
+use crate::pet::food::{FoodKind, Inventory};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::toast::Toasts;
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+fn rows(inventory: &Inventory) -> Vec<String> {
+    FoodKind::ALL
+        .iter()
+        .map(|kind| format!("{} x{}", kind.label(), inventory.stock(*kind)))
+        .collect()
+}
+
+/// Lists the food in stock, a click feeds the selected one and a long press goes back.
+pub struct FeedScene {
+    list: ListView,
+    toasts: Toasts,
+    dirty: bool,
+}
+
+impl FeedScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(
+                    Point::new(4, 32),
+                    Size::new(SCREEN.width - 8, SCREEN.height - 60),
+                ),
+                20,
+                rows(&Inventory::default()),
+            ),
+            toasts: Toasts::new(Rectangle::new(
+                Point::new(0, SCREEN.height as i32 - 24),
+                Size::new(SCREEN.width, 24),
+            )),
+            dirty: true,
+        }
+    }
+}
+
+impl Default for FeedScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for FeedScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, elapsed: Duration) -> Transition {
+        self.toasts.tick(elapsed);
+
+        let mut pet = ctx.pet.lock().unwrap();
+        let transition = match input.and_then(|input| self.list.handle_input(input)) {
+            Some(ListAction::Select(idx)) => {
+                let kind = FoodKind::ALL[idx];
+                let outcome = pet.feed(kind);
+                self.toasts.push(outcome.message(&pet.name, kind));
+                Transition::Stay
+            }
+            Some(ListAction::Back) => Transition::Pop,
+            None => Transition::Stay,
+        };
+
+        // stock refills in the background so the counts are refreshed every frame
+        for (idx, row) in rows(&pet.inventory).into_iter().enumerate() {
+            self.list.set_item(idx, row);
+        }
+
+        transition
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                i18n::text(Key::FeedTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.list.redraw(display, theme);
+        let _ = self.toasts.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.list.invalidate();
+        self.toasts.invalidate();
+    }
+}
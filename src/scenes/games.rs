@@ -0,0 +1,206 @@
+use crate::apa102::pattern::LedLayer;
+use crate::i18n::{self, Key};
+use crate::minigame::guess::GuessSide;
+use crate::minigame::needle::Needle;
+use crate::minigame::reflex::Reflex;
+use crate::minigame::{GameResult, MiniGame};
+use crate::scenes::more::MoreScene;
+use crate::scenes::{BoxedScene, Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::{Duration, Instant};
+
+const GAMES: [Key; 3] = [Key::GameGuess, Key::GameNeedle, Key::GameReflex];
+
+fn game_scene(idx: usize, seed: u32) -> BoxedScene {
+    let bounds = Rectangle::new(Point::zero(), SCREEN);
+
+    match idx {
+        0 => Box::new(GameScene::new(GuessSide::new(bounds), seed)),
+        1 => Box::new(GameScene::new(Needle::new(bounds), seed)),
+        _ => Box::new(GameScene::new(Reflex::new(bounds), seed)),
+    }
+}
+
+/// Picks a game, or the other apps from the last row, long press goes back.
+pub struct GameListScene {
+    list: ListView,
+    dirty: bool,
+}
+
+impl GameListScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(
+                    Point::new(4, 32),
+                    Size::new(SCREEN.width - 8, SCREEN.height - 36),
+                ),
+                20,
+                GAMES
+                    .iter()
+                    .chain([Key::MoreTitle].iter())
+                    .map(|row| String::from(i18n::text(*row)))
+                    .collect(),
+            ),
+            dirty: true,
+        }
+    }
+}
+
+impl Default for GameListScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for GameListScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        match input.and_then(|input| self.list.handle_input(input)) {
+            // the clock, alarm, timer, names and settings live past the games
+            Some(ListAction::Select(idx)) if idx == GAMES.len() => {
+                Transition::Push(Box::new(MoreScene::new()))
+            }
+            Some(ListAction::Select(idx)) => Transition::Replace(game_scene(idx, ctx.seeds.seed())),
+            Some(ListAction::Back) => Transition::Pop,
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                i18n::text(Key::PlayTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.list.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.list.invalidate();
+    }
+}
+
+/// Runs a game and hands the reward to the pet, a long press quits without one.
+pub struct GameScene<G: MiniGame> {
+    game: G,
+    result: Option<GameResult>,
+    dirty: bool,
+}
+
+impl<G: MiniGame> GameScene<G> {
+    pub fn new(mut game: G, seed: u32) -> Self {
+        game.start(seed);
+
+        Self {
+            game,
+            result: None,
+            dirty: true,
+        }
+    }
+}
+
+impl<G: MiniGame> Scene for GameScene<G> {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, elapsed: Duration) -> Transition {
+        if self.result.is_some() {
+            return match input {
+                Some(Input::Click | Input::LongPress) => Transition::Pop,
+                _ => Transition::Stay,
+            };
+        }
+
+        if input == Some(Input::LongPress) {
+            return Transition::Pop;
+        }
+
+        // tick first so a click lands after the time that passed before it
+        self.game.tick(elapsed);
+        if let Some(input) = input {
+            self.game.input(input);
+        }
+
+        let mut leds = ctx.leds.lock().unwrap();
+        match self.game.leds() {
+            Some(pattern) => leds.set(LedLayer::Ambient, pattern, Instant::now()),
+            None => leds.clear(LedLayer::Ambient),
+        }
+        drop(leds);
+
+        if let Some(result) = self.game.result() {
+            let mut pet = ctx.pet.lock().unwrap();
+            pet.play(result.happiness);
+            if let Some(food) = result.food_reward() {
+                pet.inventory.add(food, 1);
+            }
+            ctx.achievements.lock().unwrap().record_game(&pet, &result);
+            drop(pet);
+
+            ctx.leds.lock().unwrap().clear(LedLayer::Ambient);
+            self.result = Some(result);
+            self.dirty = true;
+        }
+
+        Transition::Stay
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        let Some(result) = self.result else {
+            let _ = self.game.redraw(display, theme);
+            return;
+        };
+
+        if self.dirty {
+            let centred = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Middle)
+                .build();
+            let centre = Rectangle::new(Point::zero(), SCREEN).center();
+
+            let _ = display.clear(theme.background);
+            let _ = Text::with_text_style(
+                &i18n::format(
+                    Key::FinalScore,
+                    &[("score", &result.score), ("max", &result.max_score)],
+                ),
+                centre - Point::new(0, 14),
+                theme.title_style(),
+                centred,
+            )
+            .draw(display);
+            let reward = match result.food_reward() {
+                Some(food) => i18n::format(
+                    Key::RewardFood,
+                    &[("happiness", &result.happiness), ("food", &food.label())],
+                ),
+                None => i18n::format(Key::Reward, &[("happiness", &result.happiness)]),
+            };
+            let _ = Text::with_text_style(
+                &reward,
+                centre + Point::new(0, 14),
+                theme.text_style(),
+                centred,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.game.invalidate();
+        self.dirty = true;
+    }
+
+    fn leave(&mut self, ctx: &Context) {
+        ctx.leds.lock().unwrap().clear(LedLayer::Ambient);
+    }
+}
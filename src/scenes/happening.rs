@@ -0,0 +1,128 @@
+use crate::apa102::pattern::{Colour, LedLayer, Pattern};
+use crate::i18n::{self, Key};
+use crate::pet::world::HappeningKind;
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::{Input, Theme};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::{Duration, Instant};
+
+fn pattern(kind: HappeningKind) -> Pattern {
+    match kind {
+        HappeningKind::Visitor => Pattern::Pulse {
+            colour: Colour::GREEN,
+            period: Duration::from_secs(1),
+        },
+        HappeningKind::Present => Pattern::Chase {
+            colour: Colour::new(200, 0, 120),
+            period: Duration::from_millis(100),
+        },
+        HappeningKind::Rain => Pattern::Chase {
+            colour: Colour::new(0, 40, 160),
+            period: Duration::from_millis(250),
+        },
+        HappeningKind::ShootingStar => Pattern::Chase {
+            colour: Colour::WHITE,
+            period: Duration::from_millis(40),
+        },
+        HappeningKind::Butterfly => Pattern::Pulse {
+            colour: Colour::AMBER,
+            period: Duration::from_millis(700),
+        },
+    }
+}
+
+/// The picture in the middle of the screen, a present shows what was inside once unwrapped.
+fn art(kind: HappeningKind, unwrapped: bool) -> String {
+    match kind {
+        HappeningKind::Visitor => String::from("(^_^)/  \\(o_o)"),
+        HappeningKind::Present if unwrapped => match kind.happening().gift {
+            Some((food, count)) => format!("{} x{}", food.label(), count),
+            None => String::from(i18n::text(Key::PresentEmpty)),
+        },
+        HappeningKind::Present => String::from(i18n::text(Key::PresentWrapped)),
+        HappeningKind::Rain => String::from("' , ' , ' , '"),
+        HappeningKind::ShootingStar => String::from(".    *  ~~~*"),
+        HappeningKind::Butterfly => String::from("}{   (^o^)"),
+    }
+}
+
+/// Shows a happening with its led effect, a click goes back once there's nothing left to open.
+pub struct HappeningScene {
+    kind: HappeningKind,
+    unwrapped: bool,
+    started: bool,
+    dirty: bool,
+}
+
+impl HappeningScene {
+    pub fn new(kind: HappeningKind) -> Self {
+        Self {
+            kind,
+            unwrapped: false,
+            started: false,
+            dirty: true,
+        }
+    }
+}
+
+impl Scene for HappeningScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        if !self.started {
+            ctx.leds
+                .lock()
+                .unwrap()
+                .set(LedLayer::Ambient, pattern(self.kind), Instant::now());
+            self.started = true;
+        }
+
+        match input {
+            Some(Input::Click) if self.kind == HappeningKind::Present && !self.unwrapped => {
+                self.unwrapped = true;
+                self.dirty = true;
+                Transition::Stay
+            }
+            Some(Input::Click | Input::LongPress) => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if !self.dirty {
+            return;
+        }
+
+        let centred = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build();
+        let centre = Rectangle::new(Point::zero(), SCREEN).center();
+
+        let _ = display.clear(theme.background);
+        let _ = Text::with_baseline(
+            i18n::text(self.kind.happening().title),
+            Point::new(4, 4),
+            theme.title_style(),
+            Baseline::Top,
+        )
+        .draw(display);
+        let _ = Text::with_text_style(
+            &art(self.kind, self.unwrapped),
+            centre,
+            theme.title_style(),
+            centred,
+        )
+        .draw(display);
+        self.dirty = false;
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn leave(&mut self, ctx: &Context) {
+        ctx.leds.lock().unwrap().clear(LedLayer::Ambient);
+    }
+}
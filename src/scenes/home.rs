@@ -0,0 +1,373 @@
+use crate::battery::interface::BatteryInterface;
+use crate::i18n::{self, Key};
+use crate::pet::calls::CallKind;
+use crate::pet::discipline::DisciplineOutcome;
+use crate::pet::food::FoodKind;
+use crate::pet::generations::{self, Farewell};
+use crate::pet::personality::IdleAnimation;
+use crate::pet::{LifeStage, Pet, STAT_MAX};
+use crate::random::Rng;
+use crate::scenes::feed::FeedScene;
+use crate::scenes::games::GameListScene;
+use crate::scenes::happening::HappeningScene;
+use crate::scenes::memorial::MemorialScene;
+use crate::scenes::stats::StatsScene;
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::battery::BatteryIndicator;
+use crate::ui::menu::{IconMenuBar, MenuAction};
+use crate::ui::meter::{MeterStyle, StatMeter};
+use crate::ui::toast::Toasts;
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::mono_font::MonoTextStyleBuilder;
+use embedded_graphics::pixelcolor::{Rgb565, Rgb888};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+const MENU_SCALE: u32 = 2;
+const MENU_HEIGHT: u32 = 34;
+/// Quiet time between idle animations.
+const IDLE_PAUSE: Duration = Duration::from_secs(6);
+const IDLE_FRAME: Duration = Duration::from_millis(400);
+
+/// The pet, its main stats and the icon menu.
+pub struct HomeScene {
+    menu: IconMenuBar,
+    battery: BatteryIndicator,
+    hunger: StatMeter,
+    happiness: StatMeter,
+    health: StatMeter,
+    toasts: Toasts,
+    call: Option<CallKind>,
+    /// Whether the pet was already gone on the last update.
+    gone: bool,
+    title: String,
+    face: String,
+    /// The pet's inherited colour.
+    colour: Rgb565,
+    /// Seeded from the context on the first update.
+    rng: Option<Rng>,
+    /// Animation and frame playing, if any.
+    idle: Option<(IdleAnimation, usize)>,
+    idle_timer: Duration,
+    dirty: bool,
+}
+
+impl HomeScene {
+    pub fn new() -> Self {
+        let meter = |x: i32| Rectangle::new(Point::new(x, 48), Size::new(90, 28));
+
+        Self {
+            menu: IconMenuBar::new(
+                Point::new(8, (SCREEN.height - MENU_HEIGHT) as i32),
+                MENU_SCALE,
+                6,
+            ),
+            battery: BatteryIndicator::new(Point::new(SCREEN.width as i32 - 54, 6)),
+            hunger: StatMeter::new(
+                meter(4),
+                String::from(i18n::text(Key::MeterHunger)),
+                MeterStyle::Hearts(4),
+                STAT_MAX,
+            ),
+            happiness: StatMeter::new(
+                meter(112),
+                String::from(i18n::text(Key::MeterHappy)),
+                MeterStyle::Hearts(4),
+                STAT_MAX,
+            ),
+            health: StatMeter::new(
+                meter(220),
+                String::from(i18n::text(Key::MeterHealth)),
+                MeterStyle::Bar,
+                STAT_MAX,
+            ),
+            toasts: Toasts::new(Rectangle::new(
+                Point::zero(),
+                Size::new(SCREEN.width - 60, 30),
+            )),
+            call: None,
+            gone: false,
+            title: String::new(),
+            face: String::new(),
+            colour: Rgb565::WHITE,
+            rng: None,
+            idle: None,
+            idle_timer: IDLE_PAUSE,
+            dirty: true,
+        }
+    }
+
+    fn discipline(&mut self, ctx: &Context, action: fn(&mut Pet) -> DisciplineOutcome) {
+        let mut pet = ctx.pet.lock().unwrap();
+        let outcome = action(&mut pet);
+        self.toasts.push(outcome.message(&pet.name));
+    }
+
+    /// Moves the idle animation on, only an awake, healthy pet with nothing to say fidgets.
+    fn idle_step(&mut self, ctx: &Context, pet: &Pet, elapsed: Duration) {
+        let fidgeting = !pet.care.dead
+            && pet.stage != LifeStage::Egg
+            && !pet.sleep.asleep
+            && !pet.care.is_sick()
+            && pet.calls.current().is_none();
+        if !fidgeting {
+            self.idle = None;
+            self.idle_timer = IDLE_PAUSE;
+            return;
+        }
+
+        self.idle_timer = self.idle_timer.saturating_sub(elapsed);
+        if !self.idle_timer.is_zero() {
+            return;
+        }
+
+        self.idle = match self.idle {
+            Some((animation, frame)) if frame + 1 < animation.frames().len() => {
+                Some((animation, frame + 1))
+            }
+            Some(_) => None,
+            None => {
+                let rng = self
+                    .rng
+                    .get_or_insert_with(|| Rng::from_source(ctx.seeds.as_ref()));
+                Some((pet.personality.idle_animation(rng), 0))
+            }
+        };
+        self.idle_timer = if self.idle.is_some() {
+            IDLE_FRAME
+        } else {
+            IDLE_PAUSE
+        };
+    }
+
+    fn face_bounds() -> Rectangle {
+        Rectangle::new(Point::new(0, 80), Size::new(SCREEN.width, 50))
+    }
+}
+
+impl Default for HomeScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The pet's face with a cross when it's sick and the poops lying next to it, or a frame of
+/// whatever idle animation is playing.
+fn face(pet: &Pet, idle: Option<(IdleAnimation, usize)>) -> String {
+    let face = match (pet.stage, pet.stats.happiness) {
+        _ if pet.care.farewell == Some(Farewell::OldAge) => "~ bye ~",
+        _ if pet.care.dead => "(x_x)",
+        (LifeStage::Egg, _) => "(    )",
+        _ if pet.sleep.asleep => "(-.-)zZ",
+        _ if pet.care.is_sick() => "(+_+)",
+        (_, 50..) => "(^_^)",
+        (_, 20..) => "(-_-)",
+        _ => "(;_;)",
+    };
+    let face = idle.map_or(face, |(animation, frame)| animation.frames()[frame]);
+
+    format!("{}{}", face, " @".repeat(pet.care.poops as usize))
+}
+
+impl Scene for HomeScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, elapsed: Duration) -> Transition {
+        let showing = self.toasts.is_showing();
+        self.toasts.tick(elapsed);
+        if showing && !self.toasts.is_showing() {
+            self.dirty = true;
+        }
+
+        let happening = {
+            let mut pet = ctx.pet.lock().unwrap();
+            self.hunger.set_value(pet.stats.hunger);
+            self.happiness.set_value(pet.stats.happiness);
+            self.health.set_value(pet.stats.health);
+            self.hunger.set_label(i18n::text(Key::MeterHunger));
+            self.happiness.set_label(i18n::text(Key::MeterHappy));
+            self.health.set_label(i18n::text(Key::MeterHealth));
+
+            let title = i18n::format(
+                match pet.world.is_raining() {
+                    true => Key::HomeTitleRaining,
+                    false => Key::HomeTitle,
+                },
+                &[("name", &pet.name), ("stage", &pet.stage.name())],
+            );
+            self.idle_step(ctx, &pet, elapsed);
+            let face = face(&pet, self.idle);
+            let [r, g, b] = pet.genes.palette().1;
+            let colour: Rgb565 = Rgb888::new(r, g, b).into();
+
+            let call = pet.calls.current();
+            if call != self.call {
+                self.call = call;
+                self.menu.set_attention(call.is_some());
+                if let Some(kind) = call {
+                    self.toasts.push(kind.message(&pet.name));
+                }
+            }
+            if pet.care.dead != self.gone {
+                self.gone = pet.care.dead;
+                if self.gone {
+                    self.toasts.push(String::from(i18n::text(Key::NewEggHint)));
+                }
+            }
+            if title != self.title || face != self.face || colour != self.colour {
+                self.title = title;
+                self.face = face;
+                self.colour = colour;
+                self.dirty = true;
+            }
+
+            // calls come first, a happening waits until they're answered
+            match call {
+                Some(_) => None,
+                None => pet.world.see(),
+            }
+        };
+        if ctx.has_battery {
+            self.battery.set_status(BatteryInterface::status());
+        }
+
+        for message in ctx.toasts.lock().unwrap().drain(..) {
+            self.toasts.push(message);
+        }
+
+        if let Some(achievement) = ctx.achievements.lock().unwrap().take_unseen() {
+            self.toasts.push(i18n::format(
+                Key::Unlocked,
+                &[("achievement", &i18n::text(achievement.name))],
+            ));
+        }
+
+        if let Some(kind) = happening {
+            return Transition::Push(Box::new(HappeningScene::new(kind)));
+        }
+
+        // once the pet is gone a click remembers it and brings the next egg
+        if input == Some(Input::Click) {
+            let mut pet = ctx.pet.lock().unwrap();
+            if generations::pass_on(&mut pet, &mut ctx.graveyard.lock().unwrap()) {
+                self.toasts.push(i18n::format(
+                    Key::NewEgg,
+                    &[("generation", &pet.generation)],
+                ));
+                return Transition::Push(Box::new(MemorialScene::new()));
+            }
+        }
+
+        // a click while the pet is calling answers it instead of picking from the menu
+        if input == Some(Input::Click) && ctx.pet.lock().unwrap().acknowledge_call().is_some() {
+            return Transition::Stay;
+        }
+
+        // a long press scolds and a double click praises, whatever is selected
+        match input {
+            Some(Input::LongPress) => {
+                self.discipline(ctx, Pet::scold);
+                return Transition::Stay;
+            }
+            Some(Input::DoubleClick) => {
+                self.discipline(ctx, Pet::praise);
+                return Transition::Stay;
+            }
+            _ => {}
+        }
+
+        match input.and_then(|input| self.menu.handle_input(input)) {
+            Some(MenuAction::Feed) => Transition::Push(Box::new(FeedScene::new())),
+            Some(MenuAction::Play) => Transition::Push(Box::new(GameListScene::new())),
+            Some(MenuAction::Medicine) => {
+                let mut pet = ctx.pet.lock().unwrap();
+                let outcome = pet.feed(FoodKind::Medicine);
+                self.toasts
+                    .push(outcome.message(&pet.name, FoodKind::Medicine));
+                Transition::Stay
+            }
+            Some(MenuAction::Bath) => {
+                let mut pet = ctx.pet.lock().unwrap();
+                let key = match pet.clean() {
+                    true => Key::Cleaned,
+                    false => Key::AlreadyClean,
+                };
+                let message = i18n::format(key, &[("name", &pet.name)]);
+                self.toasts.push(message);
+                Transition::Stay
+            }
+            Some(MenuAction::Light) => {
+                let mut pet = ctx.pet.lock().unwrap();
+                let on = !pet.sleep.lights_on;
+                pet.set_lights(on);
+                self.toasts.push(String::from(i18n::text(match on {
+                    true => Key::LightsOn,
+                    false => Key::LightsOff,
+                })));
+                Transition::Stay
+            }
+            Some(MenuAction::Discipline) => {
+                self.discipline(ctx, Pet::scold);
+                Transition::Stay
+            }
+            Some(MenuAction::Stats) => Transition::Push(Box::new(StatsScene::new())),
+            Some(action) => {
+                log::info!("{:?} isn't available yet", action);
+                Transition::Stay
+            }
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = display.fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(SCREEN.width - 60, 30)),
+                theme.background,
+            );
+            let _ = Text::with_baseline(
+                &self.title,
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+
+            let _ = display.fill_solid(&Self::face_bounds(), theme.background);
+            let _ = Text::with_text_style(
+                &self.face,
+                Self::face_bounds().center(),
+                MonoTextStyleBuilder::new()
+                    .font(theme.title_font)
+                    .text_color(self.colour)
+                    .background_color(theme.background)
+                    .build(),
+                TextStyleBuilder::new()
+                    .alignment(Alignment::Center)
+                    .baseline(Baseline::Middle)
+                    .build(),
+            )
+            .draw(display);
+            self.toasts.invalidate();
+            self.dirty = false;
+        }
+
+        let _ = self.battery.redraw(display, theme);
+        let _ = self.hunger.redraw(display, theme);
+        let _ = self.happiness.redraw(display, theme);
+        let _ = self.health.redraw(display, theme);
+        let _ = self.menu.redraw(display, theme);
+        let _ = self.toasts.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.menu.invalidate();
+        self.battery.invalidate();
+        self.hunger.invalidate();
+        self.happiness.invalidate();
+        self.health.invalidate();
+        self.toasts.invalidate();
+    }
+}
@@ -0,0 +1,131 @@
+use crate::i18n::{self, Key};
+use crate::pet::generations::{Graveyard, Memorial};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+const CAUSE_HEIGHT: u32 = 24;
+
+fn row(memorial: &Memorial) -> String {
+    i18n::format(
+        Key::MemorialRow,
+        &[
+            ("name", &memorial.name),
+            ("generation", &memorial.generation),
+            ("days", &(memorial.age.as_secs() / (24 * 60 * 60))),
+            ("form", &memorial.form.name()),
+            ("stage", &memorial.stage.name()),
+        ],
+    )
+}
+
+fn rows(graveyard: &Graveyard) -> Vec<String> {
+    match graveyard.memorials.is_empty() {
+        true => vec![String::from(i18n::text(Key::MemorialEmpty))],
+        false => graveyard.memorials.iter().rev().map(row).collect(),
+    }
+}
+
+/// Past pets with the most recent first, how the selected one left underneath. A long press goes
+/// back.
+pub struct MemorialScene {
+    list: ListView,
+    cause: Option<String>,
+    shown: Option<Option<String>>,
+    dirty: bool,
+}
+
+impl MemorialScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(
+                    Point::new(4, 32),
+                    Size::new(SCREEN.width - 8, SCREEN.height - 36 - CAUSE_HEIGHT),
+                ),
+                20,
+                vec![],
+            ),
+            cause: None,
+            shown: None,
+            dirty: true,
+        }
+    }
+
+    fn cause_bounds() -> Rectangle {
+        Rectangle::new(
+            Point::new(0, (SCREEN.height - CAUSE_HEIGHT) as i32),
+            Size::new(SCREEN.width, CAUSE_HEIGHT),
+        )
+    }
+}
+
+impl Default for MemorialScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for MemorialScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let action = input.and_then(|input| self.list.handle_input(input));
+
+        let graveyard = ctx.graveyard.lock().unwrap();
+        let rows = rows(&graveyard);
+        if rows != self.list.items() {
+            self.list.set_items(rows);
+        }
+        self.cause = graveyard
+            .memorials
+            .iter()
+            .rev()
+            .nth(self.list.selected())
+            .map(|memorial| memorial.farewell.message(&memorial.name));
+        drop(graveyard);
+
+        match action {
+            Some(ListAction::Back) => Transition::Pop,
+            Some(ListAction::Select(_)) | None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                i18n::text(Key::MemorialTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.list.redraw(display, theme);
+
+        if self.shown.as_ref() != Some(&self.cause) {
+            let bounds = Self::cause_bounds();
+            let _ = display.fill_solid(&bounds, theme.background);
+            if let Some(cause) = &self.cause {
+                let _ = Text::with_baseline(
+                    cause,
+                    bounds.top_left + Point::new(4, 4),
+                    theme.text_style(),
+                    Baseline::Top,
+                )
+                .draw(display);
+            }
+            self.shown = Some(self.cause.clone());
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.shown = None;
+        self.list.invalidate();
+    }
+}
@@ -0,0 +1,161 @@
+//! Screens of the pet app, kept on a stack so a scene can open another and get back to where it
+//! was.
+
+pub mod achievements;
+pub mod alarm;
+pub mod chart;
+pub mod clock;
+pub mod feed;
+pub mod games;
+pub mod happening;
+pub mod home;
+pub mod memorial;
+pub mod more;
+pub mod name;
+pub mod set_time;
+pub mod settings;
+pub mod stats;
+pub mod timer;
+
+use crate::achievements::Achievements;
+use crate::apa102::pattern::PatternPlayer;
+use crate::clock::alarm::Alarm;
+use crate::clock::timer::Timer;
+use crate::clock::Clock;
+use crate::history::History;
+use crate::pet::generations::Graveyard;
+use crate::pet::Pet;
+use crate::random::SeedSource;
+use crate::scenes::alarm::RingingScene;
+use crate::settings::Settings;
+use crate::tft::{App, ST7789};
+use crate::ui::{poll_input, Input, Theme};
+use embedded_graphics::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const SCREEN: Size = Size::new(320, 170);
+
+/// State shared by every scene.
+pub struct Context {
+    pub pet: Arc<Mutex<Pet>>,
+    pub leds: Arc<Mutex<PatternPlayer>>,
+    pub theme: Theme,
+    /// Seeds for games and anything else a scene wants to randomise.
+    pub seeds: Arc<dyn SeedSource + Send + Sync>,
+    pub achievements: Arc<Mutex<Achievements>>,
+    pub history: Arc<Mutex<History>>,
+    pub graveyard: Arc<Mutex<Graveyard>>,
+    /// Saved by the main loop whenever a scene changes them.
+    pub settings: Arc<Mutex<Settings>>,
+    pub clock: Arc<dyn Clock + Send + Sync>,
+    /// Kept ringing and saved by the main loop.
+    pub alarm: Arc<Mutex<Alarm>>,
+    /// Kept running and shown on the strip by the main loop.
+    pub timer: Arc<Mutex<Timer>>,
+    /// Messages waiting to be shown as toasts on the home screen, like what happened while the
+    /// device was off.
+    pub toasts: Arc<Mutex<Vec<String>>>,
+    /// Whether the board monitors a battery, the indicator is left empty without one.
+    pub has_battery: bool,
+}
+
+pub type BoxedScene = Box<dyn Scene + Send>;
+
+pub enum Transition {
+    Stay,
+    Push(BoxedScene),
+    Replace(BoxedScene),
+    Pop,
+}
+
+pub trait Scene {
+    /// Called every frame with the time since the last frame and the newest input, if any.
+    fn update(&mut self, ctx: &Context, input: Option<Input>, elapsed: Duration) -> Transition;
+
+    /// Draws whatever changed, the screen has been cleared if [`Scene::invalidate`] was called.
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme);
+
+    fn invalidate(&mut self);
+
+    /// Called before the scene is popped or replaced.
+    fn leave(&mut self, _ctx: &Context) {}
+}
+
+pub struct SceneManager {
+    ctx: Context,
+    stack: Vec<BoxedScene>,
+    last_update: Option<Instant>,
+    clear: bool,
+    /// Whether the alarm was ringing last frame.
+    ringing: bool,
+}
+
+impl SceneManager {
+    pub fn new(ctx: Context, root: BoxedScene) -> Self {
+        Self {
+            ctx,
+            stack: vec![root],
+            last_update: None,
+            clear: true,
+            ringing: false,
+        }
+    }
+
+    fn apply(&mut self, transition: Transition) {
+        match transition {
+            Transition::Stay => return,
+            Transition::Push(scene) => self.stack.push(scene),
+            Transition::Replace(scene) => {
+                if let Some(mut old) = self.stack.pop() {
+                    old.leave(&self.ctx);
+                }
+                self.stack.push(scene);
+            }
+            // the root scene stays
+            Transition::Pop if self.stack.len() > 1 => {
+                if let Some(mut old) = self.stack.pop() {
+                    old.leave(&self.ctx);
+                }
+            }
+            Transition::Pop => return,
+        }
+
+        self.clear = true;
+    }
+}
+
+impl App for SceneManager {
+    fn update(&mut self, display: &mut ST7789) {
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map_or(Duration::ZERO, |last| now.duration_since(last));
+        self.last_update = Some(now);
+
+        // the alarm takes over the screen when it starts ringing, and gets the clicks while it
+        // does
+        let ringing = self.ctx.alarm.lock().unwrap().is_ringing();
+        if ringing && !self.ringing {
+            let scene = RingingScene::new(&self.ctx);
+            self.apply(Transition::Push(Box::new(scene)));
+        }
+        self.ringing = ringing;
+
+        let input = poll_input();
+        if let Some(scene) = self.stack.last_mut() {
+            let transition = scene.update(&self.ctx, input, elapsed);
+            self.apply(transition);
+        }
+
+        let Some(scene) = self.stack.last_mut() else {
+            return;
+        };
+        if self.clear {
+            let _ = display.clear(self.ctx.theme.background);
+            scene.invalidate();
+            self.clear = false;
+        }
+        scene.draw(display, &self.ctx.theme);
+    }
+}
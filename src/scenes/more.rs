@@ -0,0 +1,140 @@
+use crate::i18n::{self, Key};
+use crate::scenes::alarm::AlarmScene;
+use crate::scenes::clock::ClockScene;
+use crate::scenes::memorial::MemorialScene;
+use crate::scenes::name::{NameScene, NameTarget};
+use crate::scenes::settings::SettingsScene;
+use crate::scenes::timer::TimerScene;
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Entry {
+    RenamePet,
+    Owner,
+    Memorial,
+    Clock,
+    Alarm,
+    Timer,
+    Settings,
+}
+
+const ENTRIES: [Entry; 7] = [
+    Entry::RenamePet,
+    Entry::Owner,
+    Entry::Memorial,
+    Entry::Clock,
+    Entry::Alarm,
+    Entry::Timer,
+    Entry::Settings,
+];
+
+fn rows(ctx: &Context) -> Vec<String> {
+    ENTRIES
+        .iter()
+        .map(|entry| match entry {
+            Entry::RenamePet => {
+                i18n::format(Key::MoreRename, &[("name", &ctx.pet.lock().unwrap().name)])
+            }
+            Entry::Owner => match ctx.settings.lock().unwrap().owner.as_str() {
+                "" => String::from(i18n::text(Key::MoreOwnerUnset)),
+                owner => i18n::format(Key::MoreOwner, &[("name", &owner)]),
+            },
+            Entry::Memorial => String::from(i18n::text(Key::MoreMemorial)),
+            Entry::Clock => String::from(i18n::text(Key::MoreClock)),
+            Entry::Alarm => {
+                let format = ctx.settings.lock().unwrap().clock_format;
+                let alarm = ctx.alarm.lock().unwrap();
+                match alarm.enabled() {
+                    true => i18n::format(Key::MoreAlarm, &[("time", &alarm.time().format(format))]),
+                    false => String::from(i18n::text(Key::MoreAlarmOff)),
+                }
+            }
+            Entry::Timer => String::from(i18n::text(Key::MoreTimer)),
+            Entry::Settings => String::from(i18n::text(Key::MoreSettings)),
+        })
+        .collect()
+}
+
+/// Everything that doesn't fit in the home menu, opened from the end of the game list, a long
+/// press goes back.
+pub struct MoreScene {
+    list: ListView,
+    dirty: bool,
+}
+
+impl MoreScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(Point::new(4, 32), SCREEN - Size::new(8, 36)),
+                20,
+                vec![String::new(); ENTRIES.len()],
+            ),
+            dirty: true,
+        }
+    }
+}
+
+impl Default for MoreScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for MoreScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        for (idx, row) in rows(ctx).into_iter().enumerate() {
+            self.list.set_item(idx, row);
+        }
+
+        match input.and_then(|input| self.list.handle_input(input)) {
+            Some(ListAction::Select(idx)) => match ENTRIES[idx] {
+                Entry::RenamePet => {
+                    let name = ctx.pet.lock().unwrap().name.clone();
+                    Transition::Push(Box::new(NameScene::new(NameTarget::Pet, name)))
+                }
+                Entry::Owner => {
+                    let owner = ctx.settings.lock().unwrap().owner.clone();
+                    Transition::Push(Box::new(NameScene::new(NameTarget::Owner, owner)))
+                }
+                Entry::Memorial => Transition::Push(Box::new(MemorialScene::new())),
+                Entry::Clock => Transition::Push(Box::new(ClockScene::new(ctx))),
+                Entry::Alarm => Transition::Push(Box::new(AlarmScene::new())),
+                Entry::Timer => Transition::Push(Box::new(TimerScene::new())),
+                Entry::Settings => Transition::Push(Box::new(SettingsScene::new())),
+            },
+            Some(ListAction::Back) => Transition::Pop,
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = display.fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(SCREEN.width, 30)),
+                theme.background,
+            );
+            let _ = Text::with_baseline(
+                i18n::text(Key::MoreTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.list.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.list.invalidate();
+    }
+}
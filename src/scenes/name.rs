@@ -0,0 +1,86 @@
+use crate::i18n::{self, Key};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::text_entry::{TextEntry, TextEntryAction, NAMES};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+/// Longest name that still fits in the home screen's title.
+pub const NAME_MAX_LEN: usize = 12;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NameTarget {
+    Pet,
+    Owner,
+}
+
+impl NameTarget {
+    fn title(&self) -> &'static str {
+        i18n::text(match self {
+            NameTarget::Pet => Key::NamePet,
+            NameTarget::Owner => Key::NameOwner,
+        })
+    }
+}
+
+/// Names the pet or the owner on the character wheel, going back once the name is confirmed or
+/// the entry is cancelled.
+pub struct NameScene {
+    target: NameTarget,
+    entry: TextEntry,
+    dirty: bool,
+}
+
+impl NameScene {
+    /// `current` is what the entry starts with.
+    pub fn new(target: NameTarget, current: String) -> Self {
+        Self {
+            target,
+            entry: TextEntry::new(
+                Rectangle::new(Point::new(4, 50), Size::new(SCREEN.width - 8, 100)),
+                current,
+                NAME_MAX_LEN,
+                NAMES,
+            ),
+            dirty: true,
+        }
+    }
+}
+
+impl Scene for NameScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        match input.and_then(|input| self.entry.handle_input(input)) {
+            Some(TextEntryAction::Confirm(name)) => {
+                match self.target {
+                    NameTarget::Pet => ctx.pet.lock().unwrap().name = name,
+                    NameTarget::Owner => ctx.settings.lock().unwrap().owner = name,
+                }
+                Transition::Pop
+            }
+            Some(TextEntryAction::Cancel) => Transition::Pop,
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                self.target.title(),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.entry.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.entry.invalidate();
+    }
+}
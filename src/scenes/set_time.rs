@@ -0,0 +1,91 @@
+use crate::clock::{ClockFormat, TimeOfDay};
+use crate::i18n::{self, Key};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::time_entry::{TimeEntry, TimeEntryAction};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TimeTarget {
+    /// The wall clock, keeping today's date.
+    Clock,
+    /// Also turns the alarm on.
+    Alarm,
+}
+
+impl TimeTarget {
+    fn title(&self) -> &'static str {
+        i18n::text(match self {
+            TimeTarget::Clock => Key::SetClockTitle,
+            TimeTarget::Alarm => Key::SetAlarmTitle,
+        })
+    }
+}
+
+/// Sets the clock or the alarm with the encoder, going back once the time is confirmed or the
+/// entry is cancelled.
+pub struct SetTimeScene {
+    target: TimeTarget,
+    entry: TimeEntry,
+    dirty: bool,
+}
+
+impl SetTimeScene {
+    /// `current` is what the entry starts at.
+    pub fn new(target: TimeTarget, current: TimeOfDay, format: ClockFormat) -> Self {
+        Self {
+            target,
+            entry: TimeEntry::new(
+                Rectangle::new(Point::new(4, 50), Size::new(SCREEN.width - 8, 100)),
+                current,
+                format,
+            ),
+            dirty: true,
+        }
+    }
+}
+
+impl Scene for SetTimeScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        match input.and_then(|input| self.entry.handle_input(input)) {
+            Some(TimeEntryAction::Confirm(time)) => {
+                match self.target {
+                    TimeTarget::Clock => {
+                        let utc_offset = ctx.settings.lock().unwrap().utc_offset;
+                        let now = ctx.clock.now().with_local_time(time, utc_offset);
+                        if let Err(err) = ctx.clock.set(now) {
+                            log::error!("{}", err);
+                        }
+                    }
+                    TimeTarget::Alarm => ctx.alarm.lock().unwrap().set(time),
+                }
+                Transition::Pop
+            }
+            Some(TimeEntryAction::Cancel) => Transition::Pop,
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if self.dirty {
+            let _ = Text::with_baseline(
+                self.target.title(),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.dirty = false;
+        }
+        let _ = self.entry.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+        self.entry.invalidate();
+    }
+}
@@ -0,0 +1,134 @@
+use crate::i18n::{self, Key, Language};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::settings::{Control, Settings, ITEMS};
+use crate::tft::ST7789;
+use crate::ui::list::{ListAction, ListView};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use std::time::Duration;
+
+const HINT_HEIGHT: u32 = 24;
+
+fn rows(settings: &Settings, editing: Option<usize>) -> Vec<String> {
+    ITEMS
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let label = i18n::text(item.label);
+            let value = item.value_text(settings);
+            match editing == Some(idx) {
+                true => format!("{}: < {} >", label, value),
+                false => format!("{}: {}", label, value),
+            }
+        })
+        .collect()
+}
+
+/// Every item in [`ITEMS`] as a row. Clicking flips a toggle or starts editing anything else,
+/// which the encoder then adjusts until the next click. A long press goes back.
+///
+/// Changes are written straight into the shared settings, the main loop applies them as they
+/// happen.
+pub struct SettingsScene {
+    list: ListView,
+    editing: Option<usize>,
+    /// Language and hint last drawn, the title and hint are redrawn when either changes.
+    shown: Option<(Language, bool)>,
+}
+
+impl SettingsScene {
+    pub fn new() -> Self {
+        Self {
+            list: ListView::new(
+                Rectangle::new(
+                    Point::new(4, 32),
+                    Size::new(SCREEN.width - 8, SCREEN.height - 36 - HINT_HEIGHT),
+                ),
+                20,
+                vec![String::new(); ITEMS.len()],
+            ),
+            editing: None,
+            shown: None,
+        }
+    }
+
+    fn hint_bounds() -> Rectangle {
+        Rectangle::new(
+            Point::new(0, (SCREEN.height - HINT_HEIGHT) as i32),
+            Size::new(SCREEN.width, HINT_HEIGHT),
+        )
+    }
+}
+
+impl Default for SettingsScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for SettingsScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let mut settings = ctx.settings.lock().unwrap();
+        let mut transition = Transition::Stay;
+
+        match (self.editing, input) {
+            (Some(idx), Some(Input::Rotate(detents))) => ITEMS[idx].adjust(&mut settings, detents),
+            (Some(_), Some(_)) => self.editing = None,
+            (None, Some(input)) => match self.list.handle_input(input) {
+                Some(ListAction::Select(idx)) => match ITEMS[idx].control {
+                    Control::Toggle => ITEMS[idx].adjust(&mut settings, 1),
+                    Control::Range { .. } | Control::Choice { .. } => self.editing = Some(idx),
+                },
+                Some(ListAction::Back) => transition = Transition::Pop,
+                None => {}
+            },
+            (_, None) => {}
+        }
+
+        for (idx, row) in rows(&settings, self.editing).into_iter().enumerate() {
+            self.list.set_item(idx, row);
+        }
+
+        transition
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        let showing = (i18n::language(), self.editing.is_some());
+        if self.shown != Some(showing) {
+            let _ = display.fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(SCREEN.width, 30)),
+                theme.background,
+            );
+            let _ = Text::with_baseline(
+                i18n::text(Key::SettingsTitle),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+
+            let bounds = Self::hint_bounds();
+            let hint = match self.editing {
+                Some(_) => Key::SettingsEditHint,
+                None => Key::SettingsHint,
+            };
+            let _ = display.fill_solid(&bounds, theme.background);
+            let _ = Text::with_baseline(
+                i18n::text(hint),
+                bounds.top_left + Point::new(4, 4),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            self.shown = Some(showing);
+        }
+        let _ = self.list.redraw(display, theme);
+    }
+
+    fn invalidate(&mut self) {
+        self.shown = None;
+        self.list.invalidate();
+    }
+}
@@ -0,0 +1,155 @@
+use crate::i18n::{self, Key};
+use crate::pet::Pet;
+use crate::scenes::achievements::AchievementsScene;
+use crate::scenes::chart::ChartScene;
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::{Input, Theme};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+/// Recent care mistakes listed under the summary.
+const SHOWN_MISTAKES: usize = 5;
+
+fn lines(pet: &Pet) -> Vec<String> {
+    let mut lines = vec![
+        i18n::format(
+            Key::StatsSummary,
+            &[
+                ("generation", &pet.generation),
+                ("stage", &pet.stage.name()),
+                ("form", &pet.form.name()),
+                ("hours", &(pet.age.as_secs() / 3600)),
+            ],
+        ),
+        i18n::format(
+            Key::StatsPersonality,
+            &[
+                ("traits", &pet.personality),
+                ("colour", &pet.genes.palette().0),
+            ],
+        ),
+        i18n::format(
+            Key::StatsBody,
+            &[
+                ("weight", &pet.stats.weight),
+                ("hygiene", &pet.stats.hygiene),
+                ("poops", &pet.care.poops),
+            ],
+        ),
+        match (pet.care.dead, pet.care.doses_needed) {
+            (true, _) => pet
+                .care
+                .farewell
+                .map_or(String::from(i18n::text(Key::StatsPassedAway)), |farewell| {
+                    farewell.message(&pet.name)
+                }),
+            (false, 0) => String::from(i18n::text(Key::StatsHealthy)),
+            (false, doses) => i18n::format_count(Key::StatsSick, doses as u32, &[]),
+        },
+        i18n::format(
+            match pet.discipline.misbehaving {
+                Some(_) => Key::StatsMisbehaving,
+                None => Key::StatsDiscipline,
+            },
+            &[("level", &pet.discipline.level)],
+        ),
+        i18n::format(
+            Key::StatsMistakes,
+            &[
+                ("total", &pet.care.log.total),
+                ("stage", &pet.care.log.this_stage),
+            ],
+        ),
+    ];
+
+    for mistake in pet.care.log.recent().take(SHOWN_MISTAKES) {
+        lines.push(i18n::format(
+            Key::StatsMistakeAt,
+            &[
+                ("hours", &(mistake.age.as_secs() / 3600)),
+                ("mistake", &mistake.kind.description()),
+            ],
+        ));
+    }
+
+    lines
+}
+
+/// Details about the pet's health and upbringing, a click opens the achievements, a double click
+/// the charts, turning the encoder everything else and a long press goes back.
+pub struct StatsScene {
+    lines: Vec<String>,
+    dirty: bool,
+}
+
+impl StatsScene {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![],
+            dirty: true,
+        }
+    }
+}
+
+impl Default for StatsScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for StatsScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let lines = lines(&ctx.pet.lock().unwrap());
+        if lines != self.lines {
+            self.lines = lines;
+            self.dirty = true;
+        }
+
+        match input {
+            Some(Input::Click) => Transition::Push(Box::new(AchievementsScene::new())),
+            Some(Input::DoubleClick) => Transition::Push(Box::new(ChartScene::new())),
+            Some(Input::LongPress) => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        if !self.dirty {
+            return;
+        }
+
+        let body = Rectangle::new(Point::new(0, 30), SCREEN - Size::new(0, 30));
+        let _ = display.fill_solid(&body, theme.background);
+        let _ = Text::with_baseline(
+            i18n::text(Key::StatsTitle),
+            Point::new(4, 4),
+            theme.title_style(),
+            Baseline::Top,
+        )
+        .draw(display);
+        let _ = Text::with_text_style(
+            i18n::text(Key::StatsHint),
+            Point::new(SCREEN.width as i32 - 4, 8),
+            theme.text_style(),
+            TextStyleBuilder::new()
+                .alignment(Alignment::Right)
+                .baseline(Baseline::Top)
+                .build(),
+        )
+        .draw(display);
+
+        for (idx, line) in self.lines.iter().enumerate() {
+            let y = body.top_left.y + 2 + (idx as u32 * theme.line_height()) as i32;
+            let _ = Text::with_baseline(line, Point::new(4, y), theme.text_style(), Baseline::Top)
+                .draw(display);
+        }
+        self.dirty = false;
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,220 @@
+use crate::clock::timer::{Phase, TimerMode};
+use crate::i18n::{self, Key, Language};
+use crate::scenes::{Context, Scene, Transition, SCREEN};
+use crate::tft::ST7789;
+use crate::ui::{Input, Theme};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::time::Duration;
+
+const TIME_TOP: i32 = 44;
+const GAUGE: Rectangle = Rectangle::new(Point::new(20, 76), Size::new(SCREEN.width - 40, 16));
+const POMODOROS_TOP: i32 = 104;
+const HINT_HEIGHT: u32 = 20;
+
+/// Everything on screen, each part is drawn again only when it changed.
+#[derive(Clone, PartialEq, Debug)]
+struct View {
+    language: Language,
+    mode: TimerMode,
+    status: Option<Key>,
+    left: String,
+    remaining: u8,
+    pomodoros: Option<String>,
+    hint: Key,
+}
+
+/// Countdown or pomodoro timer. The main loop keeps it running and shows it on the strip, so
+/// leaving the scene doesn't stop it.
+///
+/// While stopped, turning sets the countdown in minutes, a double click switches between a
+/// countdown and a pomodoro and a long press goes back. A click starts and pauses it, a long
+/// press resets it.
+pub struct TimerScene {
+    view: Option<View>,
+    shown: Option<View>,
+}
+
+impl TimerScene {
+    pub fn new() -> Self {
+        Self {
+            view: None,
+            shown: None,
+        }
+    }
+
+    fn row(top: i32, height: u32) -> Rectangle {
+        Rectangle::new(Point::new(0, top), Size::new(SCREEN.width, height))
+    }
+}
+
+impl Default for TimerScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for TimerScene {
+    fn update(&mut self, ctx: &Context, input: Option<Input>, _elapsed: Duration) -> Transition {
+        let clock = &*ctx.clock;
+        let mut timer = ctx.timer.lock().unwrap();
+        let stopped = !timer.is_running() && !timer.is_paused() && !timer.is_finished();
+
+        match input {
+            Some(Input::Click) if timer.is_running() => timer.pause(clock),
+            Some(Input::Click) => timer.start(clock),
+            Some(Input::Rotate(detents)) => timer.adjust_length(detents),
+            Some(Input::DoubleClick) if !timer.is_running() && !timer.is_paused() => {
+                let mode = match timer.mode() {
+                    TimerMode::Countdown => TimerMode::Pomodoro,
+                    TimerMode::Pomodoro => TimerMode::Countdown,
+                };
+                timer.set_mode(mode);
+            }
+            Some(Input::LongPress) if stopped => return Transition::Pop,
+            Some(Input::LongPress) => timer.reset(),
+            Some(Input::DoubleClick) | None => {}
+        }
+
+        let left = timer.left(clock).as_secs();
+        let status = match (timer.is_finished(), timer.is_paused(), timer.mode()) {
+            (true, _, _) => Some(Key::TimerDone),
+            (_, true, _) => Some(Key::TimerPaused),
+            (_, _, TimerMode::Pomodoro) if timer.phase() == Phase::Work => Some(Key::TimerWork),
+            (_, _, TimerMode::Pomodoro) => Some(Key::TimerBreak),
+            (_, _, TimerMode::Countdown) => None,
+        };
+        let hint = match timer.is_running() || timer.is_paused() {
+            true => Key::TimerHintRunning,
+            false => Key::TimerHintStopped,
+        };
+        self.view = Some(View {
+            language: i18n::language(),
+            mode: timer.mode(),
+            status,
+            left: format!("{:02}:{:02}", left / 60, left % 60),
+            remaining: timer.remaining(clock),
+            pomodoros: match timer.mode() {
+                TimerMode::Pomodoro => Some(i18n::format_count(
+                    Key::TimerPomodoros,
+                    timer.pomodoros(),
+                    &[],
+                )),
+                TimerMode::Countdown => None,
+            },
+            hint,
+        });
+
+        Transition::Stay
+    }
+
+    fn draw(&mut self, display: &mut ST7789, theme: &Theme) {
+        let Some(view) = self.view.clone() else {
+            return;
+        };
+        let shown = match self.shown.take() {
+            Some(shown) if shown.language == view.language && shown.mode == view.mode => {
+                Some(shown)
+            }
+            _ => None,
+        };
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        let centred = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+
+        if shown.is_none() {
+            let _ = display.fill_solid(&Self::row(0, 30), theme.background);
+            let title = match view.mode {
+                TimerMode::Countdown => Key::TimerTitle,
+                TimerMode::Pomodoro => Key::PomodoroTitle,
+            };
+            let _ = Text::with_baseline(
+                i18n::text(title),
+                Point::new(4, 4),
+                theme.title_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+            let _ = GAUGE
+                .into_styled(PrimitiveStyle::with_stroke(theme.foreground, 1))
+                .draw(display);
+        }
+
+        if shown.as_ref().map(|shown| &shown.status) != Some(&view.status) {
+            let _ = display.fill_solid(
+                &Rectangle::new(
+                    Point::new(SCREEN.width as i32 / 2, 0),
+                    Size::new(SCREEN.width / 2, 30),
+                ),
+                theme.background,
+            );
+            if let Some(status) = view.status {
+                let _ = Text::with_text_style(
+                    i18n::text(status),
+                    Point::new(SCREEN.width as i32 - 4, 8),
+                    theme.text_style(),
+                    right,
+                )
+                .draw(display);
+            }
+        }
+
+        if shown.as_ref().map(|shown| &shown.left) != Some(&view.left) {
+            let _ = display.fill_solid(&Self::row(TIME_TOP, 24), theme.background);
+            let _ = Text::with_text_style(
+                &view.left,
+                Point::new(SCREEN.width as i32 / 2, TIME_TOP),
+                theme.title_style(),
+                centred,
+            )
+            .draw(display);
+        }
+
+        if shown.as_ref().map(|shown| &shown.remaining) != Some(&view.remaining) {
+            let inner = GAUGE.offset(-2);
+            let filled = inner.size.width * view.remaining as u32 / 255;
+            let _ = display.fill_solid(&inner, theme.background);
+            let _ = display.fill_solid(
+                &Rectangle::new(inner.top_left, Size::new(filled, inner.size.height)),
+                theme.accent,
+            );
+        }
+
+        if shown.as_ref().map(|shown| &shown.pomodoros) != Some(&view.pomodoros) {
+            let _ = display.fill_solid(&Self::row(POMODOROS_TOP, 14), theme.background);
+            if let Some(pomodoros) = &view.pomodoros {
+                let _ = Text::with_text_style(
+                    pomodoros,
+                    Point::new(SCREEN.width as i32 / 2, POMODOROS_TOP),
+                    theme.text_style(),
+                    centred,
+                )
+                .draw(display);
+            }
+        }
+
+        if shown.as_ref().map(|shown| &shown.hint) != Some(&view.hint) {
+            let bounds = Self::row((SCREEN.height - HINT_HEIGHT) as i32, HINT_HEIGHT);
+            let _ = display.fill_solid(&bounds, theme.background);
+            let _ = Text::with_baseline(
+                i18n::text(view.hint),
+                bounds.top_left + Point::new(4, 4),
+                theme.text_style(),
+                Baseline::Top,
+            )
+            .draw(display);
+        }
+
+        self.shown = Some(view);
+    }
+
+    fn invalidate(&mut self) {
+        self.shown = None;
+    }
+}
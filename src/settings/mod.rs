@@ -0,0 +1,367 @@
+//! User adjustable settings, kept separate from the pet so they survive starting a new one.
+//!
+//! Everything the settings screen can change is described once in [`ITEMS`], which the screen
+//! draws from and [`Settings::validated`] uses to keep loaded values in range.
+
+use crate::clock::{ClockFace, ClockFormat};
+use crate::i18n::{self, Key, Language};
+use std::time::Duration;
+
+/// Choices for [`Settings::sleep_timeout`], `None` never turns the screen off.
+pub const SLEEP_TIMEOUTS: [Option<Duration>; 6] = [
+    Some(Duration::from_secs(15)),
+    Some(Duration::from_secs(30)),
+    Some(Duration::from_secs(60)),
+    Some(Duration::from_secs(2 * 60)),
+    Some(Duration::from_secs(5 * 60)),
+    None,
+];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Settings {
+    /// Backlight level in percent.
+    pub backlight: u8,
+    /// Brightness ceiling for the LED strip, same scale as `apa102::Brightness`.
+    pub led_brightness: u8,
+    /// Minutes added to UTC to get local time.
+    pub utc_offset: i16,
+    /// Empty until the owner has entered their name.
+    pub owner: String,
+    pub language: Language,
+    /// Turning clockwise counts down instead of up, for encoders wired the other way round.
+    pub encoder_reversed: bool,
+    /// How long without input before the screen turns off, one of [`SLEEP_TIMEOUTS`].
+    pub sleep_timeout: Option<Duration>,
+    pub clock_format: ClockFormat,
+    pub clock_face: ClockFace,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            backlight: 100,
+            led_brightness: 0b11111,
+            utc_offset: 0,
+            owner: String::new(),
+            language: Language::default(),
+            encoder_reversed: false,
+            sleep_timeout: Some(Duration::from_secs(30)),
+            clock_format: ClockFormat::default(),
+            clock_face: ClockFace::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Pulls every item back into its range, e.g. after loading something older or corrupt.
+    pub fn validated(mut self) -> Self {
+        for item in &ITEMS {
+            let value = item.control.clamp(item.value(&self));
+            (item.set)(&mut self, value);
+        }
+
+        self
+    }
+}
+
+/// How an item is changed from the settings screen.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Control {
+    /// Flipped with a click.
+    Toggle,
+    /// Stepped with the encoder, stopping at both ends.
+    Range { min: i32, max: i32, step: i32 },
+    /// One of `count` options, wrapping round.
+    Choice { count: i32 },
+}
+
+impl Control {
+    pub fn clamp(&self, value: i32) -> i32 {
+        match *self {
+            Control::Toggle => value.clamp(0, 1),
+            Control::Range { min, max, .. } => value.clamp(min, max),
+            Control::Choice { count } => value.clamp(0, count - 1),
+        }
+    }
+}
+
+/// One row of the settings screen, reading and writing its field as a plain number.
+pub struct Item {
+    pub label: Key,
+    pub control: Control,
+    get: fn(&Settings) -> i32,
+    set: fn(&mut Settings, i32),
+    show: fn(i32) -> String,
+}
+
+impl Item {
+    pub fn value(&self, settings: &Settings) -> i32 {
+        (self.get)(settings)
+    }
+
+    /// The current value as shown on screen.
+    pub fn value_text(&self, settings: &Settings) -> String {
+        (self.show)(self.value(settings))
+    }
+
+    /// Moves the value by `delta` steps, a toggle flips on every odd step.
+    pub fn adjust(&self, settings: &mut Settings, delta: i32) {
+        let value = self.value(settings);
+        let value = match self.control {
+            Control::Toggle => (value + delta).rem_euclid(2),
+            Control::Range { min, max, step } => (value + delta * step).clamp(min, max),
+            Control::Choice { count } => (value + delta).rem_euclid(count),
+        };
+        (self.set)(settings, value);
+    }
+}
+
+fn sleep_timeout_index(timeout: Option<Duration>) -> i32 {
+    SLEEP_TIMEOUTS
+        .iter()
+        .position(|choice| *choice == timeout)
+        .unwrap_or(1) as i32
+}
+
+pub const ITEMS: [Item; 8] = [
+    Item {
+        label: Key::SettingsBacklight,
+        control: Control::Range {
+            min: 10,
+            max: 100,
+            step: 10,
+        },
+        get: |settings| settings.backlight as i32,
+        set: |settings, value| settings.backlight = value as u8,
+        show: |value| format!("{}%", value),
+    },
+    Item {
+        label: Key::SettingsLeds,
+        control: Control::Range {
+            min: 0,
+            max: 0b11111,
+            step: 1,
+        },
+        get: |settings| settings.led_brightness as i32,
+        set: |settings, value| settings.led_brightness = value as u8,
+        show: |value| match value {
+            0 => String::from(i18n::text(Key::SettingsOff)),
+            value => format!("{}%", value * 100 / 0b11111),
+        },
+    },
+    Item {
+        label: Key::SettingsScreenOff,
+        control: Control::Choice {
+            count: SLEEP_TIMEOUTS.len() as i32,
+        },
+        get: |settings| sleep_timeout_index(settings.sleep_timeout),
+        set: |settings, value| settings.sleep_timeout = SLEEP_TIMEOUTS[value as usize],
+        show: |value| match SLEEP_TIMEOUTS[value as usize] {
+            None => String::from(i18n::text(Key::SettingsNever)),
+            Some(timeout) if timeout.as_secs() < 60 => format!("{} s", timeout.as_secs()),
+            Some(timeout) => format!("{} min", timeout.as_secs() / 60),
+        },
+    },
+    Item {
+        label: Key::SettingsReverseDial,
+        control: Control::Toggle,
+        get: |settings| settings.encoder_reversed as i32,
+        set: |settings, value| settings.encoder_reversed = value != 0,
+        show: |value| String::from(i18n::text(if value != 0 { Key::Yes } else { Key::No })),
+    },
+    Item {
+        label: Key::SettingsClock,
+        control: Control::Choice {
+            count: ClockFormat::ALL.len() as i32,
+        },
+        get: |settings| settings.clock_format.to_u8() as i32,
+        set: |settings, value| settings.clock_format = ClockFormat::ALL[value as usize],
+        show: |value| {
+            String::from(i18n::text(match ClockFormat::ALL[value as usize] {
+                ClockFormat::TwentyFourHour => Key::Clock24,
+                ClockFormat::TwelveHour => Key::Clock12,
+            }))
+        },
+    },
+    Item {
+        label: Key::SettingsFace,
+        control: Control::Choice {
+            count: ClockFace::ALL.len() as i32,
+        },
+        get: |settings| settings.clock_face.to_u8() as i32,
+        set: |settings, value| settings.clock_face = ClockFace::ALL[value as usize],
+        show: |value| {
+            String::from(i18n::text(match ClockFace::ALL[value as usize] {
+                ClockFace::Digital => Key::FaceDigital,
+                ClockFace::Analogue => Key::FaceAnalogue,
+            }))
+        },
+    },
+    Item {
+        label: Key::SettingsTimeZone,
+        control: Control::Range {
+            min: -12 * 60,
+            max: 14 * 60,
+            step: 15,
+        },
+        get: |settings| settings.utc_offset as i32,
+        set: |settings, value| settings.utc_offset = value as i16,
+        show: |value| {
+            let sign = if value < 0 { '-' } else { '+' };
+            format!("UTC{}{}:{:02}", sign, value.abs() / 60, value.abs() % 60)
+        },
+    },
+    Item {
+        label: Key::SettingsLanguage,
+        control: Control::Choice {
+            count: Language::ALL.len() as i32,
+        },
+        get: |settings| settings.language.to_u8() as i32,
+        set: |settings, value| settings.language = Language::ALL[value as usize],
+        show: |value| String::from(Language::ALL[value as usize].name()),
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(label: Key) -> &'static Item {
+        ITEMS.iter().find(|item| item.label == label).unwrap()
+    }
+
+    #[test]
+    fn defaults_are_already_valid() {
+        assert_eq!(Settings::default().validated(), Settings::default());
+    }
+
+    #[test]
+    fn validated_clamps_ranges() {
+        let settings = Settings {
+            backlight: 0,
+            led_brightness: 200,
+            utc_offset: 2000,
+            ..Settings::default()
+        }
+        .validated();
+        assert_eq!(settings.backlight, 10);
+        assert_eq!(settings.led_brightness, 0b11111);
+        assert_eq!(settings.utc_offset, 14 * 60);
+
+        let settings = Settings {
+            backlight: 250,
+            utc_offset: -2000,
+            ..Settings::default()
+        }
+        .validated();
+        assert_eq!(settings.backlight, 100);
+        assert_eq!(settings.utc_offset, -12 * 60);
+    }
+
+    #[test]
+    fn validated_resets_an_unknown_sleep_timeout() {
+        let settings = Settings {
+            sleep_timeout: Some(Duration::from_secs(45)),
+            ..Settings::default()
+        }
+        .validated();
+        assert_eq!(settings.sleep_timeout, Settings::default().sleep_timeout);
+    }
+
+    #[test]
+    fn validated_keeps_values_in_range() {
+        let settings = Settings {
+            backlight: 40,
+            led_brightness: 0,
+            utc_offset: -5 * 60,
+            owner: String::from("Sam"),
+            language: Language::French,
+            encoder_reversed: true,
+            sleep_timeout: None,
+            clock_format: ClockFormat::TwelveHour,
+            clock_face: ClockFace::Analogue,
+        };
+        assert_eq!(settings.clone().validated(), settings);
+    }
+
+    #[test]
+    fn ranges_stop_at_both_ends() {
+        let backlight = item(Key::SettingsBacklight);
+        let mut settings = Settings::default();
+
+        backlight.adjust(&mut settings, 1);
+        assert_eq!(settings.backlight, 100);
+        backlight.adjust(&mut settings, -3);
+        assert_eq!(settings.backlight, 70);
+        backlight.adjust(&mut settings, -20);
+        assert_eq!(settings.backlight, 10);
+
+        let offset = item(Key::SettingsTimeZone);
+        offset.adjust(&mut settings, 1000);
+        assert_eq!(settings.utc_offset, 14 * 60);
+        offset.adjust(&mut settings, -1);
+        assert_eq!(settings.utc_offset, 14 * 60 - 15);
+    }
+
+    #[test]
+    fn choices_wrap_round() {
+        let timeout = item(Key::SettingsScreenOff);
+        let mut settings = Settings {
+            sleep_timeout: None,
+            ..Settings::default()
+        };
+
+        timeout.adjust(&mut settings, 1);
+        assert_eq!(settings.sleep_timeout, SLEEP_TIMEOUTS[0]);
+        timeout.adjust(&mut settings, -1);
+        assert_eq!(settings.sleep_timeout, None);
+        timeout.adjust(&mut settings, SLEEP_TIMEOUTS.len() as i32 + 2);
+        assert_eq!(settings.sleep_timeout, SLEEP_TIMEOUTS[1]);
+
+        let language = item(Key::SettingsLanguage);
+        language.adjust(&mut settings, -1);
+        assert_eq!(settings.language, Language::French);
+        language.adjust(&mut settings, 1);
+        assert_eq!(settings.language, Language::English);
+
+        let face = item(Key::SettingsFace);
+        face.adjust(&mut settings, 3);
+        assert_eq!(settings.clock_face, ClockFace::Analogue);
+    }
+
+    #[test]
+    fn toggles_flip_on_odd_steps() {
+        let reversed = item(Key::SettingsReverseDial);
+        let mut settings = Settings::default();
+
+        reversed.adjust(&mut settings, 1);
+        assert!(settings.encoder_reversed);
+        reversed.adjust(&mut settings, 2);
+        assert!(settings.encoder_reversed);
+        reversed.adjust(&mut settings, -1);
+        assert!(!settings.encoder_reversed);
+    }
+
+    #[test]
+    fn sleep_timeouts_grow_and_end_with_never() {
+        let (never, timeouts) = SLEEP_TIMEOUTS.split_last().unwrap();
+        assert_eq!(*never, None);
+        assert!(timeouts
+            .windows(2)
+            .all(|pair| pair[0].is_some() && pair[0] < pair[1]));
+        // stored as whole seconds in a u16, 0 meaning never
+        assert!(timeouts
+            .iter()
+            .flatten()
+            .all(|timeout| timeout.as_secs() > 0 && timeout.as_secs() <= u16::MAX as u64));
+        assert!(SLEEP_TIMEOUTS.contains(&Settings::default().sleep_timeout));
+    }
+
+    #[test]
+    fn sleep_timeouts_show_in_seconds_then_minutes() {
+        let timeout = item(Key::SettingsScreenOff);
+        assert_eq!((timeout.show)(0), "15 s");
+        assert_eq!((timeout.show)(2), "1 min");
+        assert_eq!((timeout.show)(4), "5 min");
+    }
+}
@@ -0,0 +1,65 @@
+use super::{Backend, TaskSpec};
+use esp_idf_svc::hal::cpu::Core;
+use esp_idf_svc::hal::task::thread::ThreadSpawnConfiguration;
+use esp_idf_svc::sys;
+use std::collections::HashMap;
+
+/// Pthreads configured with the FreeRTOS task name, priority and core from the spec, watched
+/// tasks are subscribed to the ESP-IDF task watchdog.
+#[derive(Default)]
+pub struct EspBackend {
+    /// FreeRTOS wants nul terminated names that outlive the spawn.
+    names: HashMap<&'static str, &'static [u8]>,
+}
+
+impl Backend for EspBackend {
+    fn spawn(&mut self, spec: &TaskSpec, run: Box<dyn FnOnce() + Send>) -> Result<(), String> {
+        let name = *self.names.entry(spec.name).or_insert_with(|| {
+            Box::leak(format!("{}\0", spec.name).into_bytes().into_boxed_slice())
+        });
+        let core = match spec.core {
+            None => None,
+            Some(0) => Some(Core::Core0),
+            Some(1) => Some(Core::Core1),
+            Some(core) => return Err(format!("No core {} for {}", core, spec.name)),
+        };
+
+        let previous = ThreadSpawnConfiguration::get().unwrap_or_default();
+        ThreadSpawnConfiguration {
+            name: Some(name),
+            stack_size: spec.stack_size,
+            priority: spec.priority,
+            pin_to_core: core,
+            ..previous
+        }
+        .set()
+        .map_err(|err| format!("Failed to configure {}, {}", spec.name, err))?;
+
+        let spawned = std::thread::Builder::new()
+            .name(spec.name.into())
+            .stack_size(spec.stack_size)
+            .spawn(run);
+
+        previous
+            .set()
+            .map_err(|err| format!("Failed to restore thread config, {}", err))?;
+        spawned
+            .map(|_| ())
+            .map_err(|err| format!("Failed to spawn {}, {}", spec.name, err))
+    }
+
+    fn watch() {
+        let err = unsafe { sys::esp_task_wdt_add(std::ptr::null_mut()) };
+        if err != sys::ESP_OK {
+            log::error!("Failed to subscribe to the task watchdog, {}", err);
+        }
+    }
+
+    fn unwatch() {
+        unsafe { sys::esp_task_wdt_delete(std::ptr::null_mut()) };
+    }
+
+    fn feed() {
+        unsafe { sys::esp_task_wdt_reset() };
+    }
+}
@@ -0,0 +1,198 @@
+use super::{Backend, TaskSpec};
+
+/// Plain `std` threads, for running the supervisor off the device.
+#[derive(Default)]
+pub struct ThreadBackend;
+
+impl Backend for ThreadBackend {
+    fn spawn(&mut self, spec: &TaskSpec, run: Box<dyn FnOnce() + Send>) -> Result<(), String> {
+        std::thread::Builder::new()
+            .name(spec.name.into())
+            .stack_size(spec.stack_size)
+            .spawn(run)
+            .map(|_| ())
+            .map_err(|err| format!("Failed to spawn {}, {}", spec.name, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supervisor::{
+        Exit, RestartCounts, RestartPolicy, Supervisor, SupervisorEvent, Task,
+    };
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    const POLICY: RestartPolicy = RestartPolicy {
+        initial_backoff: Duration::from_secs(1),
+        max_backoff: Duration::from_secs(8),
+        healthy_after: Duration::from_secs(30),
+        max_failures: None,
+    };
+
+    fn supervisor(policy: RestartPolicy) -> Supervisor<ThreadBackend> {
+        Supervisor::new(ThreadBackend, policy, RestartCounts::default())
+    }
+
+    fn failing() -> Task {
+        Task::new(TaskSpec::new("failing", 64 * 1024), |_| {
+            Err(String::from("broken"))
+        })
+    }
+
+    /// Polls at `now` until the task's thread has exited.
+    fn exit_at(supervisor: &mut Supervisor<ThreadBackend>, now: Instant) -> Vec<SupervisorEvent> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let events = supervisor.poll(now);
+            if !events.is_empty() {
+                return events;
+            }
+            assert!(Instant::now() < deadline, "task didn't exit");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn exited() -> SupervisorEvent {
+        SupervisorEvent::Exited {
+            name: "failing",
+            exit: Exit::Failed(String::from("broken")),
+        }
+    }
+
+    /// Lets the failed task wait out its backoff, checking it isn't restarted any earlier.
+    fn restart_after(
+        supervisor: &mut Supervisor<ThreadBackend>,
+        failed: Instant,
+        backoff: Duration,
+        restarts: u32,
+    ) -> Instant {
+        let now = failed + backoff;
+        assert!(supervisor.poll(now - Duration::from_millis(1)).is_empty());
+        assert_eq!(
+            supervisor.poll(now),
+            vec![SupervisorEvent::Restarted {
+                name: "failing",
+                restarts
+            }]
+        );
+        now
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_limit() {
+        let backoffs: Vec<u64> = (1..=6).map(|n| POLICY.backoff(n).as_secs()).collect();
+        assert_eq!(backoffs, vec![1, 2, 4, 8, 8, 8]);
+        assert_eq!(POLICY.backoff(100), POLICY.max_backoff);
+    }
+
+    #[test]
+    fn failing_task_is_restarted_with_growing_backoff() {
+        let mut supervisor = supervisor(POLICY);
+        let mut now = Instant::now();
+        supervisor.spawn(failing(), now).unwrap();
+
+        for (restarts, backoff) in [1, 2, 4, 8, 8].into_iter().enumerate() {
+            assert_eq!(exit_at(&mut supervisor, now), vec![exited()]);
+            now = restart_after(
+                &mut supervisor,
+                now,
+                Duration::from_secs(backoff),
+                restarts as u32 + 1,
+            );
+        }
+        assert_eq!(supervisor.counts().get("failing"), 5);
+    }
+
+    #[test]
+    fn running_long_enough_resets_the_backoff() {
+        let mut supervisor = supervisor(POLICY);
+        let mut now = Instant::now();
+        supervisor.spawn(failing(), now).unwrap();
+
+        for (restarts, backoff) in [1, 2, 4].into_iter().enumerate() {
+            exit_at(&mut supervisor, now);
+            now = restart_after(
+                &mut supervisor,
+                now,
+                Duration::from_secs(backoff),
+                restarts as u32 + 1,
+            );
+        }
+
+        // failing just short of healthy keeps growing the backoff
+        let failed = now + POLICY.healthy_after - Duration::from_millis(1);
+        exit_at(&mut supervisor, failed);
+        now = restart_after(&mut supervisor, failed, Duration::from_secs(8), 4);
+
+        let failed = now + POLICY.healthy_after;
+        exit_at(&mut supervisor, failed);
+        restart_after(&mut supervisor, failed, Duration::from_secs(1), 5);
+    }
+
+    #[test]
+    fn gives_up_after_max_failures() {
+        let mut supervisor = supervisor(RestartPolicy {
+            max_failures: Some(2),
+            ..POLICY
+        });
+        let mut now = Instant::now();
+        supervisor.spawn(failing(), now).unwrap();
+
+        for (restarts, backoff) in [1, 2].into_iter().enumerate() {
+            exit_at(&mut supervisor, now);
+            now = restart_after(
+                &mut supervisor,
+                now,
+                Duration::from_secs(backoff),
+                restarts as u32 + 1,
+            );
+        }
+
+        assert_eq!(
+            exit_at(&mut supervisor, now),
+            vec![exited(), SupervisorEvent::GaveUp { name: "failing" }]
+        );
+        assert!(supervisor.poll(now + Duration::from_secs(3600)).is_empty());
+        assert_eq!(supervisor.counts().get("failing"), 2);
+    }
+
+    #[test]
+    fn stalls_are_reported_once_and_not_counted_as_restarts() {
+        let (release, released) = mpsc::channel::<()>();
+        let released = std::sync::Mutex::new(released);
+        let spec = TaskSpec::new("stuck", 64 * 1024).stall_timeout(Duration::from_secs(2));
+        let task = Task::new(spec, move |_| {
+            let _ = released.lock().unwrap().recv();
+            Ok(())
+        });
+
+        let mut supervisor = supervisor(POLICY);
+        let now = Instant::now();
+        supervisor.spawn(task, now).unwrap();
+
+        assert!(supervisor
+            .poll(now + Duration::from_millis(1900))
+            .is_empty());
+        let late = now + Duration::from_secs(3);
+        assert_eq!(
+            supervisor.poll(late),
+            vec![SupervisorEvent::Stalled {
+                name: "stuck",
+                stalls: 1
+            }]
+        );
+        assert!(supervisor.poll(late + Duration::from_secs(1)).is_empty());
+        assert_eq!(supervisor.counts().get("stuck"), 0);
+
+        release.send(()).unwrap();
+        assert_eq!(
+            exit_at(&mut supervisor, late),
+            vec![SupervisorEvent::Exited {
+                name: "stuck",
+                exit: Exit::Returned
+            }]
+        );
+    }
+}
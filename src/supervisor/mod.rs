@@ -0,0 +1,368 @@
+//! Starts every subsystem task and keeps it running.
+//!
+//! Tasks are bodies that can be run again, when one returns, errors or panics (host only, the
+//! firmware aborts on panic) it's restarted after a growing backoff. Tasks with a stall timeout
+//! have to call [`Heartbeat::beat`] more often than that, on the ESP32 they're also subscribed to
+//! the task watchdog which resets the chip if they never come back.
+//! [`Supervisor::poll`] is driven from the main loop like the rest of the state machines.
+
+#[cfg(target_os = "espidf")]
+pub mod esp;
+pub mod host;
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+pub type TaskBody = Box<dyn FnMut(&Heartbeat) -> Result<(), String> + Send>;
+
+#[derive(Clone, Debug)]
+pub struct TaskSpec {
+    pub name: &'static str,
+    pub stack_size: usize,
+    pub priority: u8,
+    /// Core to pin to, `None` lets the scheduler pick.
+    pub core: Option<u8>,
+    /// Longest the task may go without a heartbeat, `None` for tasks that block waiting on events.
+    pub stall_timeout: Option<Duration>,
+}
+
+impl TaskSpec {
+    pub fn new(name: &'static str, stack_size: usize) -> Self {
+        Self {
+            name,
+            stack_size,
+            priority: 5,
+            core: None,
+            stall_timeout: None,
+        }
+    }
+
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn core(mut self, core: u8) -> Self {
+        self.core = Some(core);
+        self
+    }
+
+    pub fn stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+        self
+    }
+}
+
+/// A subsystem task as returned by the `*_init` functions.
+pub struct Task {
+    pub spec: TaskSpec,
+    pub body: TaskBody,
+}
+
+impl Task {
+    pub fn new<F>(spec: TaskSpec, body: F) -> Self
+    where
+        F: FnMut(&Heartbeat) -> Result<(), String> + Send + 'static,
+    {
+        Self {
+            spec,
+            body: Box::new(body),
+        }
+    }
+}
+
+/// Lets a task show it's still making progress.
+#[derive(Clone)]
+pub struct Heartbeat {
+    epoch: Instant,
+    last: Arc<AtomicU64>,
+    feed: fn(),
+}
+
+impl Heartbeat {
+    fn new(epoch: Instant, feed: fn()) -> Self {
+        Self {
+            epoch,
+            last: Arc::new(AtomicU64::new(0)),
+            feed,
+        }
+    }
+
+    pub fn beat(&self) {
+        self.beat_at(Instant::now());
+        (self.feed)();
+    }
+
+    fn beat_at(&self, now: Instant) {
+        let millis = now.saturating_duration_since(self.epoch).as_millis() as u64;
+        self.last.store(millis, Ordering::Relaxed);
+    }
+
+    fn age(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.epoch)
+            .saturating_sub(Duration::from_millis(self.last.load(Ordering::Relaxed)))
+    }
+}
+
+/// Threads the supervisor runs tasks on.
+pub trait Backend {
+    fn spawn(&mut self, spec: &TaskSpec, run: Box<dyn FnOnce() + Send>) -> Result<(), String>;
+
+    /// Subscribes the calling task to a hardware watchdog.
+    fn watch() {}
+    fn unwatch() {}
+    /// Called with every heartbeat of a watched task.
+    fn feed() {}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Exit {
+    Returned,
+    Failed(String),
+    Panicked,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SupervisorEvent {
+    Exited {
+        name: &'static str,
+        exit: Exit,
+    },
+    Restarted {
+        name: &'static str,
+        restarts: u32,
+    },
+    /// The task missed its heartbeat, on the ESP32 the watchdog resets the chip soon after. A
+    /// thread can't be stopped from outside so it isn't restarted, `stalls` counts since boot.
+    Stalled {
+        name: &'static str,
+        stalls: u32,
+    },
+    GaveUp {
+        name: &'static str,
+    },
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct RestartPolicy {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Running this long without exiting resets the backoff.
+    pub healthy_after: Duration,
+    /// Consecutive failures before the task is left stopped, `None` retries forever.
+    pub max_failures: Option<u32>,
+}
+
+impl RestartPolicy {
+    /// Doubles for every consecutive failure, up to `max_backoff`.
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(failures.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+            healthy_after: Duration::from_secs(30),
+            max_failures: Some(10),
+        }
+    }
+}
+
+/// Restarts per task name, saved so they survive the watchdog resetting the chip.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RestartCounts(pub BTreeMap<String, u32>);
+
+impl RestartCounts {
+    pub fn get(&self, name: &str) -> u32 {
+        self.0.get(name).copied().unwrap_or(0)
+    }
+
+    fn increment(&mut self, name: &str) -> u32 {
+        let count = self.0.entry(name.into()).or_insert(0);
+        *count = count.saturating_add(1);
+        *count
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum State {
+    Running { since: Instant, stalled: bool },
+    Backoff { until: Instant },
+    Stopped,
+}
+
+struct Slot {
+    spec: TaskSpec,
+    body: Arc<Mutex<TaskBody>>,
+    heartbeat: Heartbeat,
+    state: State,
+    failures: u32,
+    stalls: u32,
+}
+
+pub struct Supervisor<B: Backend> {
+    backend: B,
+    policy: RestartPolicy,
+    counts: RestartCounts,
+    epoch: Instant,
+    slots: Vec<Slot>,
+    exit_sender: Sender<(usize, Exit)>,
+    exits: Receiver<(usize, Exit)>,
+}
+
+impl<B: Backend> Supervisor<B> {
+    pub fn new(backend: B, policy: RestartPolicy, counts: RestartCounts) -> Self {
+        let (exit_sender, exits) = mpsc::channel();
+
+        Self {
+            backend,
+            policy,
+            counts,
+            epoch: Instant::now(),
+            slots: vec![],
+            exit_sender,
+            exits,
+        }
+    }
+
+    pub fn counts(&self) -> &RestartCounts {
+        &self.counts
+    }
+
+    /// Registers and starts a task.
+    pub fn spawn(&mut self, task: Task, now: Instant) -> Result<(), String> {
+        crate::diagnostics::register_stack(task.spec.name, task.spec.stack_size as u32);
+
+        let restarts = self.counts.get(task.spec.name);
+        if restarts > 0 {
+            log::warn!("{} has been restarted {} times", task.spec.name, restarts);
+        }
+
+        self.slots.push(Slot {
+            spec: task.spec,
+            body: Arc::new(Mutex::new(task.body)),
+            heartbeat: Heartbeat::new(self.epoch, B::feed),
+            state: State::Stopped,
+            failures: 0,
+            stalls: 0,
+        });
+        self.start(self.slots.len() - 1, now)
+    }
+
+    fn start(&mut self, index: usize, now: Instant) -> Result<(), String> {
+        let slot = &mut self.slots[index];
+        let body = slot.body.clone();
+        let heartbeat = slot.heartbeat.clone();
+        let watched = slot.spec.stall_timeout.is_some();
+        let exits = self.exit_sender.clone();
+
+        heartbeat.beat_at(now);
+        let run = Box::new(move || {
+            if watched {
+                B::watch();
+            }
+
+            // a panicking task poisons its body, it's still fine to run again
+            let mut body = body.lock().unwrap_or_else(PoisonError::into_inner);
+            let exit = match panic::catch_unwind(AssertUnwindSafe(|| body(&heartbeat))) {
+                Ok(Ok(())) => Exit::Returned,
+                Ok(Err(err)) => Exit::Failed(err),
+                Err(_) => Exit::Panicked,
+            };
+            drop(body);
+
+            if watched {
+                B::unwatch();
+            }
+            let _ = exits.send((index, exit));
+        });
+
+        self.backend.spawn(&slot.spec, run)?;
+        slot.state = State::Running {
+            since: now,
+            stalled: false,
+        };
+        Ok(())
+    }
+
+    /// Restarts tasks whose backoff has run out and looks for exits and stalls.
+    pub fn poll(&mut self, now: Instant) -> Vec<SupervisorEvent> {
+        let mut events = vec![];
+
+        while let Ok((index, exit)) = self.exits.try_recv() {
+            let policy = self.policy;
+            let slot = &mut self.slots[index];
+            let name = slot.spec.name;
+
+            if let State::Running { since, .. } = slot.state {
+                if now.saturating_duration_since(since) >= policy.healthy_after {
+                    slot.failures = 0;
+                }
+            }
+            slot.failures += 1;
+            events.push(SupervisorEvent::Exited { name, exit });
+
+            slot.state = if policy.max_failures.is_some_and(|max| slot.failures > max) {
+                events.push(SupervisorEvent::GaveUp { name });
+                State::Stopped
+            } else {
+                State::Backoff {
+                    until: now + policy.backoff(slot.failures),
+                }
+            };
+        }
+
+        for index in 0..self.slots.len() {
+            let slot = &mut self.slots[index];
+            let name = slot.spec.name;
+
+            match slot.state {
+                State::Backoff { until } if now >= until => {
+                    let restarts = self.counts.increment(name);
+                    match self.start(index, now) {
+                        Ok(()) => events.push(SupervisorEvent::Restarted { name, restarts }),
+                        Err(err) => {
+                            log::error!("Failed to restart {}, {}", name, err);
+                            self.slots[index].state = State::Backoff {
+                                until: now + self.policy.max_backoff,
+                            };
+                        }
+                    }
+                }
+                State::Running { since, stalled } => {
+                    let Some(timeout) = slot.spec.stall_timeout else {
+                        continue;
+                    };
+                    let late = slot.heartbeat.age(now) > timeout;
+
+                    if late && !stalled {
+                        slot.stalls += 1;
+                        events.push(SupervisorEvent::Stalled {
+                            name,
+                            stalls: slot.stalls,
+                        });
+                    }
+                    slot.state = State::Running {
+                        since,
+                        stalled: late,
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
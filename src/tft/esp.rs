@@ -0,0 +1,210 @@
+use super::panel::{Panel, ST7789Instructions};
+use super::{
+    backlight, display_power, DisplayPower, Orientation, TearingEffect, DISPLAY_POWER,
+    REQUESTED_DISPLAY_POWER,
+};
+use crate::supervisor::{Heartbeat, Task, TaskSpec};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::*;
+use esp_idf_svc::hal::delay::FreeRtos;
+use esp_idf_svc::hal::gpio::{AnyIOPin, AnyOutputPin, Output, PinDriver};
+use esp_idf_svc::hal::ledc::config::TimerConfig;
+use esp_idf_svc::hal::ledc::{LedcDriver, LedcTimerDriver, CHANNEL0, TIMER0};
+use esp_idf_svc::hal::peripheral::Peripheral;
+use esp_idf_svc::hal::prelude::FromValueType;
+use esp_idf_svc::hal::spi::config::Config;
+use esp_idf_svc::hal::spi::{SpiAnyPins, SpiDeviceDriver, SpiDriver, SpiDriverConfig};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+type SpiPanel =
+    Panel<SpiDeviceDriver<'static, SpiDriver<'static>>, PinDriver<'static, AnyOutputPin, Output>>;
+
+pub struct ST7789 {
+    panel: SpiPanel,
+    rst: PinDriver<'static, AnyOutputPin, Output>, // Reset pin
+    bl: LedcDriver<'static>,                       // Backlight
+    backlight: u8,
+}
+
+impl ST7789 {
+    fn init(
+        panel: SpiPanel,
+        rst: PinDriver<'static, AnyOutputPin, Output>,
+        bl: LedcDriver<'static>,
+        orientation: Orientation,
+    ) -> Self {
+        let mut lcd = Self {
+            panel,
+            rst,
+            bl,
+            backlight: 100,
+        };
+
+        lcd.startup_sequence();
+        lcd.panel.set_orientation(orientation);
+        lcd.panel.set_tearing_effect(TearingEffect::Vertical);
+        lcd.clear(Rgb565::BLACK).unwrap();
+        lcd
+    }
+
+    fn startup_sequence(&mut self) {
+        self.hard_rst();
+
+        self.set_backlight_duty(0);
+        self.set_backlight_duty(self.backlight);
+
+        self.panel.send_command(ST7789Instructions::SWRESET); // reset display
+        FreeRtos::delay_ms(150);
+        self.panel.send_command(ST7789Instructions::SLPOUT); // turn off sleep
+        FreeRtos::delay_ms(10);
+        self.panel.send_command(ST7789Instructions::INVOFF); // turn off invert
+        self.panel.send_command(ST7789Instructions::VSCRDEF); // vertical scroll definition
+        self.panel.send_data_u8(&[0u8, 0u8, 0x14u8, 0u8, 0u8, 0u8]); // 0 TSA, 320 VSA, 0 BSA
+        self.panel.send_command(ST7789Instructions::MADCTL); // left -> right, bottom -> top RGB
+        self.panel.send_data_u8(&[0b00000]);
+        self.panel.send_command(ST7789Instructions::COLMOD); // 16bit 65k colors
+        self.panel.send_data_u8(&[0b0101_0101]);
+        self.panel.send_command(ST7789Instructions::INVON); // hack?
+        FreeRtos::delay_ms(10);
+        self.panel.send_command(ST7789Instructions::NORON); // turn on display
+        FreeRtos::delay_ms(10);
+        self.panel.send_command(ST7789Instructions::DISPON); // turn on display
+        FreeRtos::delay_ms(10);
+    }
+
+    /// Puts the panel to sleep with the backlight off, the frame memory is kept.
+    pub fn sleep(&mut self) {
+        self.set_backlight_duty(0);
+        self.panel.send_command(ST7789Instructions::SLPIN);
+        FreeRtos::delay_ms(5);
+    }
+
+    pub fn wake(&mut self) {
+        self.panel.send_command(ST7789Instructions::SLPOUT);
+        FreeRtos::delay_ms(120); // needed before the next sleep command
+        self.set_backlight_duty(self.backlight);
+    }
+
+    /// Sets the backlight level in percent, kept across sleep.
+    pub fn set_backlight(&mut self, percent: u8) {
+        self.backlight = percent.min(100);
+        self.set_backlight_duty(self.backlight);
+    }
+
+    fn set_backlight_duty(&mut self, percent: u8) {
+        let duty = self.bl.get_max_duty() * percent.min(100) as u32 / 100;
+        self.bl.set_duty(duty).unwrap();
+    }
+
+    fn hard_rst(&mut self) {
+        self.rst.set_high().unwrap();
+        FreeRtos::delay_ms(1);
+        self.rst.set_low().unwrap();
+        FreeRtos::delay_ms(1);
+        self.rst.set_high().unwrap();
+        FreeRtos::delay_ms(1);
+    }
+}
+
+impl Dimensions for ST7789 {
+    fn bounding_box(&self) -> Rectangle {
+        self.panel.bounding_box()
+    }
+}
+
+impl DrawTarget for ST7789 {
+    type Color = Rgb565;
+    type Error = ();
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.panel.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.panel.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.panel.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.panel.clear(color)
+    }
+}
+
+fn tft_task(lcd: &mut ST7789, app: &mut Box<dyn App + Send>, heartbeat: &Heartbeat) -> ! {
+    loop {
+        heartbeat.beat();
+
+        let requested = DisplayPower::from_u8(REQUESTED_DISPLAY_POWER.load(Ordering::Relaxed));
+        if requested != display_power() {
+            match requested {
+                DisplayPower::On => lcd.wake(),
+                DisplayPower::Off => lcd.sleep(),
+            }
+            DISPLAY_POWER.store(requested as u8, Ordering::Relaxed);
+        }
+
+        if requested == DisplayPower::On {
+            let backlight = backlight();
+            if backlight != lcd.backlight {
+                lcd.set_backlight(backlight);
+            }
+
+            app.update(lcd);
+        }
+        FreeRtos::delay_ms(5);
+    }
+}
+
+pub type AppSpawner = Box<dyn FnOnce() -> Box<dyn App + Send>>;
+
+pub fn tft_init<SPI>(
+    spi: impl Peripheral<P = SPI> + 'static,
+    clk: AnyOutputPin,
+    sdo: AnyOutputPin,
+    cs: AnyOutputPin,
+    bl: AnyOutputPin,
+    bl_timer: TIMER0,
+    bl_channel: CHANNEL0,
+    dc: AnyOutputPin,
+    rst: AnyOutputPin,
+    app_spawner: AppSpawner,
+) -> Task
+where
+    SPI: SpiAnyPins,
+{
+    let spi_drv = SpiDriver::new(spi, clk, sdo, None::<AnyIOPin>, &SpiDriverConfig::new()).unwrap();
+
+    let config = Config::new().baudrate(27.MHz().into());
+
+    let spi = SpiDeviceDriver::new(spi_drv, Some(cs), &config).unwrap();
+
+    let dc = PinDriver::output(dc).unwrap();
+    let panel = Panel::new(spi, dc, 320, 170, Orientation::Landscape);
+
+    let rst = PinDriver::output(rst).unwrap();
+    let bl_timer =
+        LedcTimerDriver::new(bl_timer, &TimerConfig::new().frequency(5.kHz().into())).unwrap();
+    let bl = LedcDriver::new(bl_channel, bl_timer, bl).unwrap();
+    let mut lcd = ST7789::init(panel, rst, bl, Orientation::Landscape);
+    let mut app = app_spawner();
+
+    Task::new(
+        TaskSpec::new("tft_task", 32 * 300).stall_timeout(Duration::from_secs(2)),
+        move |heartbeat| tft_task(&mut lcd, &mut app, heartbeat),
+    )
+}
+
+pub trait App {
+    fn update(&mut self, display: &mut ST7789);
+}
@@ -0,0 +1,663 @@
+//! ST7789 commands and drawing, generic over the SPI device and data/command pin so the
+//! clipping can be checked against a mock on the host.
+
+use byte_slice_cast::*;
+use embedded_graphics::pixelcolor::raw::RawU16;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::*;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::SpiDevice;
+
+const DISPLAY_OFFSET_X: u16 = 0;
+const DISPLAY_OFFSET_Y: u16 = 35; // hardware bug?
+
+#[repr(u8)]
+#[derive(Copy, Clone)]
+#[allow(dead_code, clippy::upper_case_acronyms)]
+pub(super) enum ST7789Instructions {
+    /// No operation
+    NOP = 0x00,
+    /// Software reset
+    SWRESET = 0x01,
+    /// Read display ID
+    RDDID = 0x04,
+    /// Read display status
+    RDDST = 0x09,
+    /// Read display power
+    RDDPM = 0x0A,
+    /// Read display
+    RDDMADCTL = 0x0B,
+    /// Read display pixel
+    RDDCOLMOD = 0x0C,
+    /// Read display image
+    RDDIM = 0x0D,
+    /// Read display signal
+    RDDSM = 0x0E,
+    /// Read display self-diagnostic result
+    RDDSDR = 0x0F,
+    /// Sleep in
+    SLPIN = 0x10,
+    /// Sleep out
+    SLPOUT = 0x11,
+    /// Partial mode on
+    PTLON = 0x12,
+    /// Partial off (Normal)
+    NORON = 0x13,
+    /// Display inversion off
+    INVOFF = 0x20,
+    /// Display inversion on
+    INVON = 0x21,
+    /// Gamma set
+    GAMSET = 0x26,
+    /// Display off
+    DISPOFF = 0x28,
+    /// Display on
+    DISPON = 0x29,
+    /// Column address set
+    CASET = 0x2A,
+    /// Row address set
+    RASET = 0x2B,
+    /// Memory write
+    RAMWR = 0x2C,
+    /// Memory read
+    RAMRD = 0x2E,
+    /// Partial start/end address set
+    PTLAR = 0x30,
+    /// Vertical scrolling definition
+    VSCRDEF = 0x33,
+    /// Tearing effect line off
+    TEOFF = 0x34,
+    /// Tearing effect line on
+    TEON = 0x35,
+    /// Memory data access control
+    MADCTL = 0x36,
+    /// Vertical scrolling start address
+    VSCRSADD = 0x37,
+    /// Idle mode off
+    IDMOFF = 0x38,
+    /// Idle mode on
+    IDMON = 0x39,
+    /// Interface pixel format
+    COLMOD = 0x3A,
+    /// Memory write continue
+    RAMWRC = 0x3C,
+    /// Memory read continue
+    RAMRDC = 0x3E,
+    /// Set tear scanline
+    TESCAN = 0x44,
+    /// Get scanline
+    RDTESCAN = 0x45,
+    /// Write display brightness
+    WRDISBV = 0x51,
+    /// Read display brightness value
+    RDDISBV = 0x52,
+    /// Write CTRL display
+    WRCTRLD = 0x53,
+    /// Read CTRL value display
+    RDCTRLD = 0x54,
+    /// Write content adaptive brightness control and Color enhancement
+    WRCACE = 0x55,
+    /// Read content adaptive brightness control
+    RDCABC = 0x56,
+    /// Write CABC minimum brightness
+    WRCABCMB = 0x5E,
+    /// Read CABC minimum brightness
+    RDCABCMB = 0x5F,
+    /// Read Automatic Brightness Control Self-Diagnostic Result
+    RDABCSDR = 0x68,
+    /// Read ID1
+    RDID1 = 0xDA,
+    /// Read ID2
+    RDID2 = 0xDB,
+    /// Read ID3
+    RDID3 = 0xDC,
+}
+#[repr(u8)]
+#[derive(Copy, Clone)]
+pub enum Orientation {
+    Portrait = 0b0000_0000,         // no inverting
+    Landscape = 0b0110_0000,        // invert column and page/column order
+    PortraitSwapped = 0b1100_0000,  // invert page and column order
+    LandscapeSwapped = 0b1010_0000, // invert page and page/column order
+}
+
+impl Orientation {
+    pub fn is_landscape(&self) -> bool {
+        matches!(self, Orientation::Landscape | Orientation::LandscapeSwapped)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum TearingEffect {
+    /// Disable output.
+    Off,
+    /// Output vertical blanking information.
+    Vertical,
+    /// Output horizontal and vertical blanking information.
+    HorizontalAndVertical,
+}
+
+struct DisplaySpiInterface<SPI, DC> {
+    spi: SPI,
+    dc: DC,
+}
+
+impl<SPI, DC> DisplaySpiInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    fn new(spi: SPI, dc: DC) -> Self {
+        Self { spi, dc }
+    }
+
+    fn send_command(&mut self, cmd: ST7789Instructions) {
+        self.dc.set_low().unwrap();
+        self.spi.write(&[cmd as u8]).unwrap();
+    }
+
+    fn send_data_u8(&mut self, data: &[u8]) {
+        self.dc.set_high().unwrap();
+        self.spi.write(data).unwrap();
+    }
+
+    fn send_data_u16iter(&mut self, iter: &mut dyn Iterator<Item = u16>) {
+        self.dc.set_high().unwrap();
+
+        let mut buf = [0; 64];
+        let mut i = 0;
+        let len = buf.len();
+
+        for v in iter.map(u16::to_be) {
+            buf[i] = v;
+            i += 1;
+
+            if i == len {
+                self.spi.write(buf.as_byte_slice()).unwrap();
+                i = 0;
+            }
+        }
+
+        if i > 0 {
+            self.spi.write(buf[..i].as_byte_slice()).unwrap();
+        }
+    }
+}
+
+/// Address windows, orientation and clipping, the reset pin and backlight are left to the
+/// driver.
+pub struct Panel<SPI, DC> {
+    interface: DisplaySpiInterface<SPI, DC>,
+    size_x: u16,
+    size_y: u16,
+    orientation: Orientation,
+}
+
+impl<SPI, DC> Panel<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// `size_x` and `size_y` are in `orientation`, nothing is sent until the panel is used.
+    pub fn new(spi: SPI, dc: DC, size_x: u16, size_y: u16, orientation: Orientation) -> Self {
+        Self {
+            interface: DisplaySpiInterface::new(spi, dc),
+            size_x,
+            size_y,
+            orientation,
+        }
+    }
+
+    pub(super) fn send_command(&mut self, cmd: ST7789Instructions) {
+        self.interface.send_command(cmd);
+    }
+
+    pub(super) fn send_data_u8(&mut self, data: &[u8]) {
+        self.interface.send_data_u8(data);
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.send_command(ST7789Instructions::MADCTL);
+        self.send_data_u8(&[orientation as u8]);
+
+        // size is stored in the current orientation, swap when rotating by 90 degrees
+        if self.orientation.is_landscape() != orientation.is_landscape() {
+            core::mem::swap(&mut self.size_x, &mut self.size_y);
+        }
+        self.orientation = orientation;
+    }
+
+    /// Panel offset for the current orientation, the offsets are measured in landscape.
+    fn offset(&self) -> (u16, u16) {
+        if self.orientation.is_landscape() {
+            (DISPLAY_OFFSET_X, DISPLAY_OFFSET_Y)
+        } else {
+            (DISPLAY_OFFSET_Y, DISPLAY_OFFSET_X)
+        }
+    }
+
+    pub fn set_pixels<T>(&mut self, start: (u16, u16), end: (u16, u16), colours: T)
+    where
+        T: IntoIterator<Item = u16>,
+    {
+        self.set_address_window(start.0, start.1, end.0, end.1);
+        self.send_command(ST7789Instructions::RAMWR);
+
+        self.interface.send_data_u16iter(&mut colours.into_iter());
+    }
+
+    pub fn set_pixel(&mut self, position: (u16, u16), colour: u16) {
+        self.set_address_window(position.0, position.1, position.0, position.1);
+        self.send_command(ST7789Instructions::RAMWR);
+
+        self.send_data_u8(&colour.to_le().to_be_bytes());
+    }
+
+    fn set_address_window(&mut self, start_x: u16, start_y: u16, end_x: u16, end_y: u16) {
+        let (offset_x, offset_y) = self.offset();
+
+        self.send_command(ST7789Instructions::CASET);
+        self.send_data_u8(&(start_x + offset_x).to_be_bytes());
+        self.send_data_u8(&(end_x + offset_x).to_be_bytes());
+        self.send_command(ST7789Instructions::RASET);
+        self.send_data_u8(&(start_y + offset_y).to_be_bytes());
+        self.send_data_u8(&(end_y + offset_y).to_be_bytes());
+    }
+
+    /// Sends a rectangle that has already been clipped to the screen.
+    fn fill_clipped<T>(&mut self, area: &Rectangle, colours: T)
+    where
+        T: IntoIterator<Item = u16>,
+    {
+        if let Some(bottom_right) = area.bottom_right() {
+            let max = (area.size.width * area.size.height) as usize;
+
+            let start_x = area.top_left.x as u16;
+            let start_y = area.top_left.y as u16;
+            let end_x = bottom_right.x as u16;
+            let end_y = bottom_right.y as u16;
+            self.set_pixels(
+                (start_x, start_y),
+                (end_x, end_y),
+                colours.into_iter().take(max),
+            );
+        };
+    }
+
+    pub fn set_tearing_effect(&mut self, tearing_effect: TearingEffect) {
+        match tearing_effect {
+            TearingEffect::Off => self.send_command(ST7789Instructions::TEOFF),
+            TearingEffect::Vertical => {
+                self.send_command(ST7789Instructions::TEON);
+                self.send_data_u8(&[0]);
+            }
+            TearingEffect::HorizontalAndVertical => {
+                self.send_command(ST7789Instructions::TEON);
+                self.send_data_u8(&[1]);
+            }
+        }
+    }
+}
+
+impl<SPI, DC> Dimensions for Panel<SPI, DC> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            Point::new(0, 0),
+            Size::new(self.size_x as u32, self.size_y as u32),
+        )
+    }
+}
+
+impl<SPI, DC> DrawTarget for Panel<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    type Color = Rgb565;
+    type Error = ();
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+
+        for Pixel(point, colour) in pixels {
+            if !bounding_box.contains(point) {
+                continue;
+            }
+
+            let colour = RawU16::from(colour).into_inner();
+            self.set_pixel((point.x as u16, point.y as u16), colour);
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+
+        if clipped.is_zero_sized() {
+            return Ok(());
+        }
+
+        let colours = colors
+            .into_iter()
+            .map(|colour| RawU16::from(colour).into_inner());
+
+        if clipped == *area {
+            self.fill_clipped(&clipped, colours);
+        } else {
+            // colours are laid out over the whole area, drop the ones for clipped pixels
+            let colours = area
+                .points()
+                .zip(colours)
+                .filter(|(point, _)| clipped.contains(*point))
+                .map(|(_, colour)| colour);
+            self.fill_clipped(&clipped, colours);
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+
+        self.fill_clipped(&area, core::iter::repeat(color.into_storage()));
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box();
+
+        self.fill_clipped(&area, core::iter::repeat(color.into_storage()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::pixelcolor::raw::RawU16;
+    use embedded_hal::spi::Operation;
+    use std::cell::RefCell;
+    use std::convert::Infallible;
+    use std::rc::Rc;
+
+    /// Every byte written to the bus with the level of the data/command pin at the time.
+    #[derive(Default)]
+    struct Bus {
+        data: bool,
+        sent: Vec<(bool, u8)>,
+    }
+
+    struct MockSpi(Rc<RefCell<Bus>>);
+
+    impl embedded_hal::spi::ErrorType for MockSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice for MockSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+            let mut bus = self.0.borrow_mut();
+            for operation in operations {
+                if let Operation::Write(bytes) = operation {
+                    let data = bus.data;
+                    bus.sent.extend(bytes.iter().map(|byte| (data, *byte)));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    struct MockDc(Rc<RefCell<Bus>>);
+
+    impl embedded_hal::digital::ErrorType for MockDc {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockDc {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.0.borrow_mut().data = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.0.borrow_mut().data = true;
+            Ok(())
+        }
+    }
+
+    /// A memory write with the window set before it, in panel coordinates.
+    #[derive(PartialEq, Debug)]
+    struct Write {
+        columns: (u16, u16),
+        rows: (u16, u16),
+        pixels: Vec<u16>,
+    }
+
+    /// Each orientation with the screen size and panel offset it should have.
+    const ORIENTATIONS: [(Orientation, u16, u16, (u16, u16)); 4] = [
+        (Orientation::Landscape, 320, 170, (0, 35)),
+        (Orientation::LandscapeSwapped, 320, 170, (0, 35)),
+        (Orientation::Portrait, 170, 320, (35, 0)),
+        (Orientation::PortraitSwapped, 170, 320, (35, 0)),
+    ];
+
+    fn panel(orientation: Orientation) -> (Panel<MockSpi, MockDc>, Rc<RefCell<Bus>>) {
+        let bus = Rc::new(RefCell::new(Bus::default()));
+        let mut panel = Panel::new(
+            MockSpi(bus.clone()),
+            MockDc(bus.clone()),
+            320,
+            170,
+            Orientation::Landscape,
+        );
+        panel.set_orientation(orientation);
+        bus.borrow_mut().sent.clear();
+        (panel, bus)
+    }
+
+    /// Splits the bus into commands and pairs each memory write with its window, checking the
+    /// write fills the window exactly.
+    fn writes(bus: &RefCell<Bus>) -> Vec<Write> {
+        let mut commands: Vec<(u8, Vec<u8>)> = Vec::new();
+        for &(data, byte) in &bus.borrow().sent {
+            match commands.last_mut() {
+                Some((_, bytes)) if data => bytes.push(byte),
+                None if data => panic!("data sent before any command"),
+                _ => commands.push((byte, Vec::new())),
+            }
+        }
+
+        let range = |bytes: &[u8]| {
+            assert_eq!(bytes.len(), 4);
+            (
+                u16::from_be_bytes([bytes[0], bytes[1]]),
+                u16::from_be_bytes([bytes[2], bytes[3]]),
+            )
+        };
+        let mut columns = None;
+        let mut rows = None;
+        let mut writes = Vec::new();
+        for (command, bytes) in commands {
+            if command == ST7789Instructions::CASET as u8 {
+                columns = Some(range(&bytes));
+            } else if command == ST7789Instructions::RASET as u8 {
+                rows = Some(range(&bytes));
+            } else if command == ST7789Instructions::RAMWR as u8 {
+                let write = Write {
+                    columns: columns.take().expect("no column window"),
+                    rows: rows.take().expect("no row window"),
+                    pixels: bytes
+                        .chunks(2)
+                        .map(|pixel| u16::from_be_bytes([pixel[0], pixel[1]]))
+                        .collect(),
+                };
+                let width = (write.columns.1 - write.columns.0 + 1) as usize;
+                let height = (write.rows.1 - write.rows.0 + 1) as usize;
+                assert_eq!(write.pixels.len(), width * height, "{:?}", write);
+                writes.push(write);
+            }
+        }
+        writes
+    }
+
+    fn colour(raw: u16) -> Rgb565 {
+        Rgb565::from(RawU16::new(raw))
+    }
+
+    #[test]
+    fn orientation_swaps_the_size() {
+        for (orientation, width, height, _) in ORIENTATIONS {
+            let (panel, _) = panel(orientation);
+            assert_eq!(
+                panel.bounding_box(),
+                Rectangle::new(Point::zero(), Size::new(width as u32, height as u32))
+            );
+        }
+    }
+
+    #[test]
+    fn clear_fills_the_screen_and_no_more() {
+        for (orientation, width, height, (x, y)) in ORIENTATIONS {
+            let (mut panel, bus) = panel(orientation);
+            panel.clear(colour(0x1234)).unwrap();
+
+            assert_eq!(
+                writes(&bus),
+                [Write {
+                    columns: (x, x + width - 1),
+                    rows: (y, y + height - 1),
+                    pixels: vec![0x1234; width as usize * height as usize],
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn fill_solid_is_clipped_to_the_screen() {
+        for (orientation, width, height, (x, y)) in ORIENTATIONS {
+            let (mut panel, bus) = panel(orientation);
+            let top_left = Rectangle::new(Point::new(-10, -5), Size::new(20, 10));
+            let bottom_right = Rectangle::new(
+                Point::new(width as i32 - 5, height as i32 - 3),
+                Size::new(10, 10),
+            );
+            panel.fill_solid(&top_left, colour(1)).unwrap();
+            panel.fill_solid(&bottom_right, colour(2)).unwrap();
+
+            assert_eq!(
+                writes(&bus),
+                [
+                    Write {
+                        columns: (x, x + 9),
+                        rows: (y, y + 4),
+                        pixels: vec![1; 50],
+                    },
+                    Write {
+                        columns: (x + width - 5, x + width - 1),
+                        rows: (y + height - 3, y + height - 1),
+                        pixels: vec![2; 15],
+                    },
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn off_screen_draws_send_nothing() {
+        for (orientation, width, height, _) in ORIENTATIONS {
+            let (mut panel, bus) = panel(orientation);
+            let above = Rectangle::new(Point::new(-20, -20), Size::new(10, 10));
+            let beyond = Rectangle::new(Point::new(width as i32, height as i32), Size::new(4, 4));
+
+            panel.fill_solid(&above, colour(1)).unwrap();
+            panel.fill_contiguous(&beyond, (0..16).map(colour)).unwrap();
+            panel
+                .draw_iter([
+                    Pixel(Point::new(-1, 0), colour(1)),
+                    Pixel(Point::new(0, height as i32), colour(1)),
+                ])
+                .unwrap();
+
+            assert!(bus.borrow().sent.is_empty());
+        }
+    }
+
+    #[test]
+    fn fill_contiguous_skips_colours_of_clipped_pixels() {
+        for (orientation, width, height, (x, y)) in ORIENTATIONS {
+            let (mut panel, bus) = panel(orientation);
+            // 4 by 3 with the first two columns and the top row off screen
+            let top_left = Rectangle::new(Point::new(-2, -1), Size::new(4, 3));
+            // 3 by 2 with the last column and bottom row off screen
+            let bottom_right = Rectangle::new(
+                Point::new(width as i32 - 2, height as i32 - 1),
+                Size::new(3, 2),
+            );
+            let inside = Rectangle::new(Point::new(10, 10), Size::new(2, 2));
+            panel
+                .fill_contiguous(&top_left, (0..12).map(colour))
+                .unwrap();
+            panel
+                .fill_contiguous(&bottom_right, (20..26).map(colour))
+                .unwrap();
+            panel
+                .fill_contiguous(&inside, (30..34).map(colour))
+                .unwrap();
+
+            assert_eq!(
+                writes(&bus),
+                [
+                    Write {
+                        columns: (x, x + 1),
+                        rows: (y, y + 1),
+                        pixels: vec![6, 7, 10, 11],
+                    },
+                    Write {
+                        columns: (x + width - 2, x + width - 1),
+                        rows: (y + height - 1, y + height - 1),
+                        pixels: vec![20, 21],
+                    },
+                    Write {
+                        columns: (x + 10, x + 11),
+                        rows: (y + 10, y + 11),
+                        pixels: vec![30, 31, 32, 33],
+                    },
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn draw_iter_sends_on_screen_pixels_one_at_a_time() {
+        for (orientation, width, height, (x, y)) in ORIENTATIONS {
+            let (mut panel, bus) = panel(orientation);
+            panel
+                .draw_iter([
+                    Pixel(Point::new(0, 0), colour(1)),
+                    Pixel(Point::new(-3, 4), colour(2)),
+                    Pixel(Point::new(width as i32, 0), colour(3)),
+                    Pixel(Point::new(5, -1), colour(4)),
+                    Pixel(Point::new(width as i32 - 1, height as i32 - 1), colour(5)),
+                ])
+                .unwrap();
+
+            let pixel = |column: u16, row: u16, raw: u16| Write {
+                columns: (column, column),
+                rows: (row, row),
+                pixels: vec![raw],
+            };
+            assert_eq!(
+                writes(&bus),
+                [pixel(x, y, 1), pixel(x + width - 1, y + height - 1, 5)]
+            );
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::battery::{BatteryLevel, BatteryStatus, ChargeState};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Baseline, Text};
+
+const BODY: Size = Size::new(22, 11);
+
+/// Small battery outline filled to the charge, with the percentage beside it.
+pub struct BatteryIndicator {
+    top_left: Point,
+    status: Option<BatteryStatus>,
+    dirty: bool,
+}
+
+impl BatteryIndicator {
+    pub fn new(top_left: Point) -> Self {
+        Self {
+            top_left,
+            status: None,
+            dirty: true,
+        }
+    }
+
+    pub fn set_status(&mut self, status: Option<BatteryStatus>) {
+        // the voltage moves all the time, only redraw for what is shown
+        let shown = |status: &Option<BatteryStatus>| {
+            status.map(|status| (status.percent, status.charge, status.level))
+        };
+
+        if shown(&self.status) != shown(&status) {
+            self.dirty = true;
+        }
+        self.status = status;
+    }
+}
+
+impl Widget for BatteryIndicator {
+    type Output = ();
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(self.top_left, Size::new(BODY.width + 2 + 4 * 6 + 2, 12))
+    }
+
+    fn handle_input(&mut self, _input: Input) -> Option<Self::Output> {
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds(), theme.background)?;
+
+        let Some(status) = self.status else {
+            self.dirty = false;
+            return Ok(());
+        };
+
+        let colour = match (status.charge, status.level) {
+            (ChargeState::Charging | ChargeState::External, _) => theme.accent,
+            (_, BatteryLevel::Low | BatteryLevel::Critical) => theme.warning,
+            _ => theme.foreground,
+        };
+
+        Rectangle::new(self.top_left, BODY)
+            .into_styled(PrimitiveStyle::with_stroke(theme.foreground, 1))
+            .draw(target)?;
+        Rectangle::new(
+            self.top_left + Point::new(BODY.width as i32, 3),
+            Size::new(2, 5),
+        )
+        .into_styled(PrimitiveStyle::with_fill(theme.foreground))
+        .draw(target)?;
+
+        let filled = (BODY.width - 4) * status.percent as u32 / 100;
+        Rectangle::new(
+            self.top_left + Point::new(2, 2),
+            Size::new(filled, BODY.height - 4),
+        )
+        .into_styled(PrimitiveStyle::with_fill(colour))
+        .draw(target)?;
+
+        let label = match status.charge {
+            ChargeState::External => String::from("USB"),
+            _ => format!("{}%", status.percent),
+        };
+        Text::with_baseline(
+            &label,
+            self.top_left + Point::new(BODY.width as i32 + 4, 1),
+            theme.text_style(),
+            Baseline::Top,
+        )
+        .draw(target)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
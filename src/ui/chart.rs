@@ -0,0 +1,136 @@
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ChartStyle {
+    Line,
+    Bar,
+}
+
+/// Read only plot of values, oldest on the left. Missing values leave a gap.
+pub struct Chart {
+    bounds: Rectangle,
+    style: ChartStyle,
+    values: Vec<Option<u16>>,
+    max: u16,
+    dirty: bool,
+}
+
+impl Chart {
+    pub fn new(bounds: Rectangle, style: ChartStyle) -> Self {
+        Self {
+            bounds,
+            style,
+            values: vec![],
+            max: 1,
+            dirty: true,
+        }
+    }
+
+    pub fn set_style(&mut self, style: ChartStyle) {
+        if self.style != style {
+            self.style = style;
+            self.dirty = true;
+        }
+    }
+
+    /// Values are scaled so `max` reaches the top.
+    pub fn set_values(&mut self, values: Vec<Option<u16>>, max: u16) {
+        let max = max.max(1);
+        if self.values != values || self.max != max {
+            self.values = values;
+            self.max = max;
+            self.dirty = true;
+        }
+    }
+
+    /// `value` scaled to `0..=range`.
+    fn scale(&self, value: u16, range: u32) -> u32 {
+        value.min(self.max) as u32 * range / self.max as u32
+    }
+
+    /// Where a line passes through, kept a pixel inside the bounds so the stroke fits.
+    fn point(&self, idx: usize, value: u16) -> Point {
+        let inner = self.bounds.size.saturating_sub(Size::new(3, 3));
+        let steps = (self.values.len() as u32).saturating_sub(1).max(1);
+        let x = 1 + idx as u32 * inner.width / steps;
+        let y = 1 + inner.height - self.scale(value, inner.height);
+
+        self.bounds.top_left + Point::new(x as i32, y as i32)
+    }
+}
+
+impl Widget for Chart {
+    type Output = ();
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, _input: Input) -> Option<Self::Output> {
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+
+        let bottom = self.bounds.top_left.y + self.bounds.size.height as i32 - 1;
+        Line::new(
+            Point::new(self.bounds.top_left.x, bottom),
+            Point::new(
+                self.bounds.top_left.x + self.bounds.size.width as i32 - 1,
+                bottom,
+            ),
+        )
+        .into_styled(PrimitiveStyle::with_stroke(theme.inactive, 1))
+        .draw(target)?;
+
+        match self.style {
+            ChartStyle::Line => {
+                let stroke = PrimitiveStyle::with_stroke(theme.accent, 2);
+                for (idx, pair) in self.values.windows(2).enumerate() {
+                    if let [Some(from), Some(to)] = pair {
+                        Line::new(self.point(idx, *from), self.point(idx + 1, *to))
+                            .into_styled(stroke)
+                            .draw(target)?;
+                    }
+                }
+            }
+            ChartStyle::Bar => {
+                let count = self.values.len().max(1) as u32;
+                let slot = self.bounds.size.width / count;
+                for (idx, value) in self.values.iter().enumerate() {
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    let height = self.scale(*value, self.bounds.size.height - 1).max(1);
+                    let top_left = Point::new(
+                        self.bounds.top_left.x + (idx as u32 * slot) as i32,
+                        bottom + 1 - height as i32,
+                    );
+                    target.fill_solid(
+                        &Rectangle::new(top_left, Size::new(slot.saturating_sub(1).max(1), height)),
+                        theme.accent,
+                    )?;
+                }
+            }
+        }
+
+        self.dirty = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+use crate::clock::{ClockFace, ClockFormat, TimeOfDay};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::f32::consts::TAU;
+
+const DIGIT: Size = Size::new(44, 80);
+const SEGMENT: u32 = 8;
+const DIGIT_GAP: u32 = 10;
+const COLON_WIDTH: u32 = 32;
+
+/// Lit segments of 0 to 9, bit 0 is the top one going clockwise with the middle one last.
+const DIGITS: [u8; 10] = [0x3f, 0x06, 0x5b, 0x4f, 0x66, 0x6d, 0x7d, 0x07, 0x7f, 0x6f];
+/// Shown instead of the digits while the clock isn't set.
+const DASH: u8 = 0x40;
+
+/// Segment rectangles of a digit, in the same order as the bits of [`DIGITS`].
+fn segments(top_left: Point) -> [Rectangle; 7] {
+    let t = SEGMENT;
+    let long = DIGIT.width - 2 * t;
+    let side = (DIGIT.height - 3 * t) / 2;
+    let at = |x: u32, y: u32, width: u32, height: u32| {
+        Rectangle::new(
+            top_left + Point::new(x as i32, y as i32),
+            Size::new(width, height),
+        )
+    };
+
+    [
+        at(t, 0, long, t),
+        at(DIGIT.width - t, t, t, side),
+        at(DIGIT.width - t, 2 * t + side, t, side),
+        at(t, DIGIT.height - t, long, t),
+        at(0, 2 * t + side, t, side),
+        at(0, t, t, side),
+        at(t, t + side, long, t),
+    ]
+}
+
+/// Line out from the centre `length` long, pointing `turn` of the way round from twelve.
+fn hand(centre: Point, turn: f32, length: f32) -> Line {
+    let (sin, cos) = (turn * TAU).sin_cos();
+    let end = centre
+        + Point::new(
+            (sin * length).round() as i32,
+            (-cos * length).round() as i32,
+        );
+    Line::new(centre, end)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Shown {
+    face: ClockFace,
+    format: ClockFormat,
+    time: Option<TimeOfDay>,
+}
+
+/// Read only clock, either seven segment digits or hands on a dial. Only what moved is drawn
+/// again as the time changes, the whole face after an invalidate or a change of style.
+pub struct ClockView {
+    bounds: Rectangle,
+    face: ClockFace,
+    format: ClockFormat,
+    /// `None` while the clock isn't set.
+    time: Option<TimeOfDay>,
+    shown: Option<Shown>,
+}
+
+impl ClockView {
+    pub fn new(bounds: Rectangle, face: ClockFace, format: ClockFormat) -> Self {
+        Self {
+            bounds,
+            face,
+            format,
+            time: None,
+            shown: None,
+        }
+    }
+
+    pub fn set_style(&mut self, face: ClockFace, format: ClockFormat) {
+        self.face = face;
+        self.format = format;
+    }
+
+    pub fn set_time(&mut self, time: Option<TimeOfDay>) {
+        self.time = time;
+    }
+
+    fn current(&self) -> Shown {
+        Shown {
+            face: self.face,
+            format: self.format,
+            time: self.time,
+        }
+    }
+
+    /// Off segments are drawn dim rather than cleared so the digits never flicker.
+    fn draw_digital<D>(&self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let digits = match self.time {
+            Some(time) => {
+                let hour = match self.format {
+                    ClockFormat::TwentyFourHour => time.hour(),
+                    ClockFormat::TwelveHour if time.hour() % 12 == 0 => 12,
+                    ClockFormat::TwelveHour => time.hour() % 12,
+                };
+                // a 12 hour clock leaves the leading zero off
+                let tens = match (self.format, hour / 10) {
+                    (ClockFormat::TwelveHour, 0) => 0,
+                    (_, tens) => DIGITS[tens as usize],
+                };
+                [
+                    tens,
+                    DIGITS[hour as usize % 10],
+                    DIGITS[time.minute() as usize / 10],
+                    DIGITS[time.minute() as usize % 10],
+                ]
+            }
+            None => [DASH; 4],
+        };
+
+        let width = 4 * DIGIT.width + 2 * DIGIT_GAP + COLON_WIDTH;
+        let left = self.bounds.center().x - width as i32 / 2;
+        let top = self.bounds.center().y - DIGIT.height as i32 / 2;
+        let lefts = [
+            0,
+            DIGIT.width + DIGIT_GAP,
+            2 * DIGIT.width + DIGIT_GAP + COLON_WIDTH,
+            3 * DIGIT.width + 2 * DIGIT_GAP + COLON_WIDTH,
+        ];
+
+        for (digit, x) in digits.iter().zip(lefts) {
+            for (bit, segment) in segments(Point::new(left + x as i32, top))
+                .iter()
+                .enumerate()
+            {
+                let colour = match digit & (1 << bit) != 0 {
+                    true => theme.accent,
+                    false => theme.inactive,
+                };
+                target.fill_solid(segment, colour)?;
+            }
+        }
+
+        // the colon blinks with the seconds
+        let colon = match self.time {
+            Some(time) if time.second() % 2 == 1 => theme.inactive,
+            _ => theme.accent,
+        };
+        let colon_x = left + (2 * DIGIT.width + DIGIT_GAP + (COLON_WIDTH - SEGMENT) / 2) as i32;
+        for y in [DIGIT.height / 3, 2 * DIGIT.height / 3] {
+            let dot = Rectangle::new(
+                Point::new(colon_x, top + (y - SEGMENT / 2) as i32),
+                Size::new_equal(SEGMENT),
+            );
+            target.fill_solid(&dot, colon)?;
+        }
+
+        // seconds and am or pm in the corner under the minutes
+        if let Some(time) = self.time {
+            let suffix = match self.format {
+                ClockFormat::TwentyFourHour => "",
+                ClockFormat::TwelveHour if time.hour() < 12 => " am",
+                ClockFormat::TwelveHour => " pm",
+            };
+            Text::with_text_style(
+                &format!("{:02}{}", time.second(), suffix),
+                Point::new(left + width as i32, top + DIGIT.height as i32 + 4),
+                theme.text_style(),
+                TextStyleBuilder::new()
+                    .alignment(Alignment::Right)
+                    .baseline(Baseline::Top)
+                    .build(),
+            )
+            .draw(target)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_hands<D>(
+        &self,
+        target: &mut D,
+        theme: &Theme,
+        time: TimeOfDay,
+        erase: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let centre = self.bounds.center();
+        let radius = self.bounds.size.width.min(self.bounds.size.height) as f32 / 2.0 - 4.0;
+        let colour = |colour: Rgb565| match erase {
+            true => theme.background,
+            false => colour,
+        };
+
+        let seconds = time.as_secs() as f32;
+        let hands = [
+            (
+                seconds / (12.0 * 3600.0),
+                radius * 0.5,
+                5,
+                colour(theme.foreground),
+            ),
+            (
+                seconds % 3600.0 / 3600.0,
+                radius * 0.75,
+                3,
+                colour(theme.foreground),
+            ),
+            (
+                seconds % 60.0 / 60.0,
+                radius - 12.0,
+                1,
+                colour(theme.warning),
+            ),
+        ];
+        for (turn, length, width, colour) in hands {
+            hand(centre, turn % 1.0, length)
+                .into_styled(PrimitiveStyle::with_stroke(colour, width))
+                .draw(target)?;
+        }
+
+        Circle::with_center(centre, 9)
+            .into_styled(PrimitiveStyle::with_fill(theme.accent))
+            .draw(target)
+    }
+
+    fn draw_dial<D>(&self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let centre = self.bounds.center();
+        let radius = self.bounds.size.width.min(self.bounds.size.height) as f32 / 2.0 - 4.0;
+
+        for hour in 0..12 {
+            let (length, width) = match hour % 3 {
+                0 => (10.0, 3),
+                _ => (6.0, 1),
+            };
+            let outer = hand(centre, hour as f32 / 12.0, radius);
+            let inner = hand(centre, hour as f32 / 12.0, radius - length);
+            Line::new(inner.end, outer.end)
+                .into_styled(PrimitiveStyle::with_stroke(theme.foreground, width))
+                .draw(target)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Widget for ClockView {
+    type Output = ();
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, _input: Input) -> Option<Self::Output> {
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.shown != Some(self.current())
+    }
+
+    fn invalidate(&mut self) {
+        self.shown = None;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        // anything but the time moving on starts from a blank face
+        let current = self.current();
+        let restyled = match self.shown {
+            Some(shown) => {
+                shown.face != current.face
+                    || shown.format != current.format
+                    || shown.time.is_some() != current.time.is_some()
+            }
+            None => true,
+        };
+        if restyled {
+            target.fill_solid(&self.bounds, theme.background)?;
+        }
+
+        match self.face {
+            ClockFace::Digital => self.draw_digital(target, theme)?,
+            ClockFace::Analogue => {
+                match self.shown.and_then(|shown| shown.time) {
+                    Some(old) if !restyled => self.draw_hands(target, theme, old, true)?,
+                    _ => {}
+                }
+                self.draw_dial(target, theme)?;
+                if let Some(time) = self.time {
+                    self.draw_hands(target, theme, time, false)?;
+                }
+            }
+        }
+
+        self.shown = Some(current);
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use crate::diagnostics::Report;
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::MonoTextStyleBuilder;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+
+/// Free heap and per task stack and cpu use, tasks close to overflowing are drawn in the warning
+/// colour.
+pub struct DiagnosticsOverlay {
+    bounds: Rectangle,
+    report: Option<Report>,
+    dirty: bool,
+}
+
+impl DiagnosticsOverlay {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            report: None,
+            dirty: true,
+        }
+    }
+
+    pub fn set_report(&mut self, report: Option<Report>) {
+        if self.report != report {
+            self.report = report;
+            self.dirty = true;
+        }
+    }
+}
+
+impl Widget for DiagnosticsOverlay {
+    type Output = ();
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, _input: Input) -> Option<Self::Output> {
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+        self.dirty = false;
+
+        let Some(report) = &self.report else {
+            return Ok(());
+        };
+
+        let warning_style = MonoTextStyleBuilder::new()
+            .font(theme.font)
+            .text_color(theme.warning)
+            .background_color(theme.background)
+            .build();
+
+        let mut lines = vec![(
+            format!(
+                "heap {}k min {}k psram {}k",
+                report.heap.free / 1024,
+                report.heap.min_free / 1024,
+                report.heap.free_psram / 1024
+            ),
+            false,
+        )];
+        lines.extend(report.tasks.iter().map(|task| {
+            let cpu = task
+                .cpu_percent
+                .map_or(String::from("-"), |cpu| format!("{:.0}%", cpu));
+            (
+                format!("{:<12.12} {:>5} {:>4}", task.name, task.stack_free_min, cpu),
+                task.near_overflow,
+            )
+        }));
+
+        let line_height = theme.line_height();
+        let rows = (self.bounds.size.height / line_height) as usize;
+        for (row, (line, warning)) in lines.iter().take(rows).enumerate() {
+            Text::with_baseline(
+                line,
+                self.bounds.top_left + Point::new(1, (row as u32 * line_height) as i32),
+                if *warning {
+                    warning_style
+                } else {
+                    theme.text_style()
+                },
+                Baseline::Top,
+            )
+            .draw(target)?;
+        }
+
+        Ok(())
+    }
+}
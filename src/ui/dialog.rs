@@ -0,0 +1,110 @@
+use crate::i18n::{self, Key};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyleBuilder, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+
+/// Modal yes/no question, turning the encoder picks the answer and a click confirms it.
+///
+/// A long press always answers no. Whatever the dialog was drawn over needs invalidating once
+/// it has been answered.
+pub struct Dialog {
+    bounds: Rectangle,
+    message: String,
+    yes: bool,
+    dirty: bool,
+}
+
+impl Dialog {
+    pub fn new(bounds: Rectangle, message: String) -> Self {
+        Self {
+            bounds,
+            message,
+            yes: false,
+            dirty: true,
+        }
+    }
+}
+
+impl Widget for Dialog {
+    type Output = bool;
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output> {
+        match input {
+            // turning right moves to yes, left to no, however many detents it took
+            Input::Rotate(detents) if detents != 0 => {
+                let yes = detents > 0;
+                if self.yes != yes {
+                    self.yes = yes;
+                    self.dirty = true;
+                }
+                None
+            }
+            Input::Click => Some(self.yes),
+            Input::LongPress => Some(false),
+            _ => None,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        self.bounds
+            .into_styled(
+                PrimitiveStyleBuilder::new()
+                    .stroke_color(theme.foreground)
+                    .stroke_width(2)
+                    .fill_color(theme.background)
+                    .build(),
+            )
+            .draw(target)?;
+
+        let centred = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+        let centre_x = self.bounds.center().x;
+        let top = self.bounds.top_left.y;
+
+        Text::with_text_style(
+            &self.message,
+            Point::new(centre_x, top + 8),
+            theme.text_style(),
+            centred,
+        )
+        .draw(target)?;
+
+        let button_y = top + self.bounds.size.height as i32 - theme.line_height() as i32 - 6;
+        let offset = self.bounds.size.width as i32 / 4;
+
+        for (label, x, selected) in [
+            (i18n::text(Key::No), centre_x - offset, !self.yes),
+            (i18n::text(Key::Yes), centre_x + offset, self.yes),
+        ] {
+            let style = if selected {
+                theme.selected_text_style()
+            } else {
+                theme.text_style()
+            };
+            Text::with_text_style(label, Point::new(x, button_y), style, centred).draw(target)?;
+        }
+
+        self.dirty = false;
+        Ok(())
+    }
+}
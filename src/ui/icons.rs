@@ -0,0 +1,245 @@
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+pub const ICON_SIZE: u32 = 16;
+
+/// 16x16 one bit icon, each row is stored with the leftmost pixel in the top bit.
+#[derive(Copy, Clone, PartialEq)]
+pub struct Icon([u16; ICON_SIZE as usize]);
+
+impl Icon {
+    pub const fn new(rows: [u16; ICON_SIZE as usize]) -> Self {
+        Self(rows)
+    }
+
+    pub fn is_set(&self, x: u32, y: u32) -> bool {
+        self.0[y as usize] & (0x8000 >> x) != 0
+    }
+
+    /// Draws the icon with every pixel scaled up to a `scale` by `scale` block.
+    pub fn draw<D>(
+        &self,
+        target: &mut D,
+        top_left: Point,
+        scale: u32,
+        foreground: Rgb565,
+        background: Rgb565,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let area = Rectangle::new(top_left, Size::new_equal(ICON_SIZE * scale));
+        let colours = area.points().map(|point| {
+            let offset = point - top_left;
+            if self.is_set(offset.x as u32 / scale, offset.y as u32 / scale) {
+                foreground
+            } else {
+                background
+            }
+        });
+
+        target.fill_contiguous(&area, colours)
+    }
+}
+
+/// Bowl, feeding.
+pub const FEED: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000011_11000000,
+    0b00001100_00110000,
+    0b00010000_00001000,
+    0b00100000_00000100,
+    0b00100000_00000100,
+    0b01111111_11111110,
+    0b01000000_00000010,
+    0b01000000_00000010,
+    0b00100000_00000100,
+    0b00100000_00000100,
+    0b00010000_00001000,
+    0b00001111_11110000,
+    0b00000000_00000000,
+    0b00111111_11111100,
+    0b00000000_00000000,
+]);
+
+/// Light bulb, lights on and off.
+pub const LIGHT: Icon = Icon::new([
+    0b00000001_10000000,
+    0b00100001_10000100,
+    0b00010000_00001000,
+    0b00000011_11000000,
+    0b00000100_00100000,
+    0b00001000_00010000,
+    0b11001000_00010011,
+    0b00001000_00010000,
+    0b00000100_00100000,
+    0b00000010_01000000,
+    0b00010010_01001000,
+    0b00100011_11000100,
+    0b00000011_11000000,
+    0b00000011_11000000,
+    0b00000001_10000000,
+    0b00000000_00000000,
+]);
+
+/// Ball, games.
+pub const PLAY: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000111_11100000,
+    0b00011000_00011000,
+    0b00100000_00000100,
+    0b00100011_11000100,
+    0b01000100_00100010,
+    0b01001000_00010010,
+    0b01001000_00010010,
+    0b01001000_00010010,
+    0b01001000_00010010,
+    0b01000100_00100010,
+    0b00100011_11000100,
+    0b00100000_00000100,
+    0b00011000_00011000,
+    0b00000111_11100000,
+    0b00000000_00000000,
+]);
+
+/// Cross, medicine.
+pub const MEDICINE: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000111_11100000,
+    0b00000100_00100000,
+    0b00000100_00100000,
+    0b00000100_00100000,
+    0b00111100_00111100,
+    0b00100000_00000100,
+    0b00100000_00000100,
+    0b00100000_00000100,
+    0b00111100_00111100,
+    0b00000100_00100000,
+    0b00000100_00100000,
+    0b00000100_00100000,
+    0b00000111_11100000,
+    0b00000000_00000000,
+    0b00000000_00000000,
+]);
+
+/// Bath tub, cleaning up.
+pub const BATH: Icon = Icon::new([
+    0b00000000_00110000,
+    0b00000000_01001000,
+    0b00000000_01001000,
+    0b00000000_01000000,
+    0b00000000_01000000,
+    0b00000000_01000000,
+    0b01111111_11111110,
+    0b01000000_00000010,
+    0b01000000_00000010,
+    0b00100000_00000100,
+    0b00100000_00000100,
+    0b00010000_00001000,
+    0b00001111_11110000,
+    0b00000100_00100000,
+    0b00001100_00110000,
+    0b00000000_00000000,
+]);
+
+/// Bar chart, status screen.
+pub const STATS: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00100000_00000000,
+    0b00100000_00001100,
+    0b00100000_00001100,
+    0b00100000_00001100,
+    0b00100000_11001100,
+    0b00100000_11001100,
+    0b00100000_11001100,
+    0b00101100_11001100,
+    0b00101100_11001100,
+    0b00101100_11001100,
+    0b00101100_11001100,
+    0b00101100_11001100,
+    0b00111111_11111110,
+    0b00000000_00000000,
+    0b00000000_00000000,
+]);
+
+/// Hand, scold and praise.
+pub const DISCIPLINE: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000000_00000000,
+    0b00001110_00000000,
+    0b00010001_00000000,
+    0b00010001_00000000,
+    0b00001010_00000000,
+    0b00001010_00000000,
+    0b00001010_11100000,
+    0b00001011_00011000,
+    0b00111010_00000100,
+    0b01001000_00000100,
+    0b01000000_00000100,
+    0b00100000_00000100,
+    0b00010000_00001000,
+    0b00001111_11110000,
+    0b00000000_00000000,
+]);
+
+/// Warning sign, lit when the pet is calling.
+pub const ATTENTION: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000001_10000000,
+    0b00000011_11000000,
+    0b00000011_11000000,
+    0b00000110_01100000,
+    0b00000110_01100000,
+    0b00001101_10110000,
+    0b00001101_10110000,
+    0b00011001_10011000,
+    0b00011001_10011000,
+    0b00110000_00001100,
+    0b00110001_10001100,
+    0b01100001_10000110,
+    0b01111111_11111110,
+    0b11111111_11111111,
+    0b00000000_00000000,
+]);
+
+/// Filled heart for meters.
+pub const HEART: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000000_00000000,
+    0b00111100_00111100,
+    0b01111110_01111110,
+    0b11111111_11111111,
+    0b11111111_11111111,
+    0b11111111_11111111,
+    0b01111111_11111110,
+    0b00111111_11111100,
+    0b00011111_11111000,
+    0b00001111_11110000,
+    0b00000111_11100000,
+    0b00000011_11000000,
+    0b00000001_10000000,
+    0b00000000_00000000,
+    0b00000000_00000000,
+]);
+
+/// Heart outline for meters.
+pub const HEART_EMPTY: Icon = Icon::new([
+    0b00000000_00000000,
+    0b00000000_00000000,
+    0b00111100_00111100,
+    0b01000010_01000010,
+    0b10000001_10000001,
+    0b10000000_00000001,
+    0b10000000_00000001,
+    0b01000000_00000010,
+    0b00100000_00000100,
+    0b00010000_00001000,
+    0b00001000_00010000,
+    0b00000100_00100000,
+    0b00000010_01000000,
+    0b00000001_10000000,
+    0b00000000_00000000,
+    0b00000000_00000000,
+]);
@@ -0,0 +1,159 @@
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Baseline, Text};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ListAction {
+    Select(usize),
+    Back,
+}
+
+/// Vertical list that scrolls to keep the selected row visible.
+pub struct ListView {
+    bounds: Rectangle,
+    row_height: u32,
+    items: Vec<String>,
+    selected: usize,
+    scroll: usize,
+    dirty: bool,
+}
+
+impl ListView {
+    pub fn new(bounds: Rectangle, row_height: u32, items: Vec<String>) -> Self {
+        Self {
+            bounds,
+            row_height,
+            items,
+            selected: 0,
+            scroll: 0,
+            dirty: true,
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+        self.scroll_to_selected();
+        self.dirty = true;
+    }
+
+    /// Replaces one row, e.g. when a value shown in it changed, a missing row is ignored.
+    pub fn set_item(&mut self, idx: usize, item: String) {
+        if let Some(row) = self.items.get_mut(idx) {
+            if *row != item {
+                *row = item;
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn visible_rows(&self) -> usize {
+        (self.bounds.size.height / self.row_height).max(1) as usize
+    }
+
+    fn scroll_to_selected(&mut self) {
+        let rows = self.visible_rows();
+
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+    }
+}
+
+impl Widget for ListView {
+    type Output = ListAction;
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output> {
+        match input {
+            Input::Rotate(detents) if !self.items.is_empty() => {
+                let last = self.items.len() as i32 - 1;
+                let selected = (self.selected as i32 + detents).clamp(0, last) as usize;
+
+                if selected != self.selected {
+                    self.selected = selected;
+                    self.scroll_to_selected();
+                    self.dirty = true;
+                }
+                None
+            }
+            Input::Click if !self.items.is_empty() => Some(ListAction::Select(self.selected)),
+            Input::LongPress => Some(ListAction::Back),
+            _ => None,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+
+        let rows = self.visible_rows();
+        let end = (self.scroll + rows).min(self.items.len());
+
+        for (row, idx) in (self.scroll..end).enumerate() {
+            let top_left =
+                self.bounds.top_left + Point::new(0, (row as u32 * self.row_height) as i32);
+            let style = if idx == self.selected {
+                Rectangle::new(top_left, Size::new(self.bounds.size.width, self.row_height))
+                    .into_styled(PrimitiveStyle::with_fill(theme.accent))
+                    .draw(target)?;
+                theme.selected_text_style()
+            } else {
+                theme.text_style()
+            };
+
+            Text::with_baseline(
+                &self.items[idx],
+                top_left + Point::new(4, 1),
+                style,
+                Baseline::Top,
+            )
+            .draw(target)?;
+        }
+
+        // scroll bar when not everything fits
+        if self.items.len() > rows {
+            let height = self.bounds.size.height;
+            let bar_height = (height * rows as u32 / self.items.len() as u32).max(4);
+            let bar_top =
+                (height - bar_height) * self.scroll as u32 / (self.items.len() - rows) as u32;
+
+            Rectangle::new(
+                self.bounds.top_left
+                    + Point::new(self.bounds.size.width as i32 - 2, bar_top as i32),
+                Size::new(2, bar_height),
+            )
+            .into_styled(PrimitiveStyle::with_fill(theme.inactive))
+            .draw(target)?;
+        }
+
+        self.dirty = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use crate::ui::icons::{self, Icon, ICON_SIZE};
+use crate::ui::{wrap_index, Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+
+/// Actions on the classic eight icon strip, in display order.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MenuAction {
+    Feed,
+    Light,
+    Play,
+    Medicine,
+    Bath,
+    Stats,
+    Discipline,
+    Attention,
+}
+
+impl MenuAction {
+    pub const ALL: [MenuAction; 8] = [
+        MenuAction::Feed,
+        MenuAction::Light,
+        MenuAction::Play,
+        MenuAction::Medicine,
+        MenuAction::Bath,
+        MenuAction::Stats,
+        MenuAction::Discipline,
+        MenuAction::Attention,
+    ];
+
+    pub fn icon(&self) -> Icon {
+        match self {
+            MenuAction::Feed => icons::FEED,
+            MenuAction::Light => icons::LIGHT,
+            MenuAction::Play => icons::PLAY,
+            MenuAction::Medicine => icons::MEDICINE,
+            MenuAction::Bath => icons::BATH,
+            MenuAction::Stats => icons::STATS,
+            MenuAction::Discipline => icons::DISCIPLINE,
+            MenuAction::Attention => icons::ATTENTION,
+        }
+    }
+}
+
+/// Horizontal strip of icons, turning the encoder moves the selection and a click picks it.
+///
+/// The attention icon can't be selected, it only lights up while the pet is calling.
+pub struct IconMenuBar {
+    top_left: Point,
+    scale: u32,
+    spacing: u32,
+    selected: usize,
+    attention: bool,
+    dirty: bool,
+}
+
+impl IconMenuBar {
+    pub fn new(top_left: Point, scale: u32, spacing: u32) -> Self {
+        Self {
+            top_left,
+            scale,
+            spacing,
+            selected: 0,
+            attention: false,
+            dirty: true,
+        }
+    }
+
+    pub fn selected(&self) -> MenuAction {
+        MenuAction::ALL[self.selected]
+    }
+
+    pub fn set_attention(&mut self, attention: bool) {
+        if self.attention != attention {
+            self.attention = attention;
+            self.dirty = true;
+        }
+    }
+
+    fn icon_pitch(&self) -> u32 {
+        ICON_SIZE * self.scale + self.spacing
+    }
+
+    fn selectable(&self) -> usize {
+        MenuAction::ALL.len() - 1
+    }
+}
+
+impl Widget for IconMenuBar {
+    type Output = MenuAction;
+
+    fn bounds(&self) -> Rectangle {
+        let count = MenuAction::ALL.len() as u32;
+        Rectangle::new(
+            self.top_left,
+            Size::new(
+                self.icon_pitch() * count - self.spacing,
+                ICON_SIZE * self.scale + 2,
+            ),
+        )
+    }
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output> {
+        match input {
+            Input::Rotate(detents) => {
+                self.selected = wrap_index(self.selected, detents, self.selectable());
+                self.dirty = true;
+                None
+            }
+            Input::Click => Some(self.selected()),
+            _ => None,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds(), theme.background)?;
+
+        for (idx, action) in MenuAction::ALL.iter().enumerate() {
+            let top_left = self.top_left + Point::new((idx as u32 * self.icon_pitch()) as i32, 0);
+            let colour = match action {
+                MenuAction::Attention if self.attention => theme.warning,
+                MenuAction::Attention => theme.inactive,
+                _ => theme.foreground,
+            };
+            action
+                .icon()
+                .draw(target, top_left, self.scale, colour, theme.background)?;
+
+            if idx == self.selected {
+                let width = ICON_SIZE * self.scale;
+                Rectangle::new(top_left + Point::new(0, width as i32), Size::new(width, 2))
+                    .into_styled(PrimitiveStyle::with_fill(theme.accent))
+                    .draw(target)?;
+            }
+        }
+
+        self.dirty = false;
+        Ok(())
+    }
+}
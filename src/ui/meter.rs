@@ -0,0 +1,136 @@
+use crate::ui::icons::{self, ICON_SIZE};
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Baseline, Text};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum MeterStyle {
+    Bar,
+    /// Row of this many hearts, filled in proportion to the value.
+    Hearts(u8),
+}
+
+/// Labelled read only meter for a pet stat.
+pub struct StatMeter {
+    bounds: Rectangle,
+    label: String,
+    style: MeterStyle,
+    value: u8,
+    max: u8,
+    dirty: bool,
+}
+
+impl StatMeter {
+    pub fn new(bounds: Rectangle, label: String, style: MeterStyle, max: u8) -> Self {
+        Self {
+            bounds,
+            label,
+            style,
+            value: 0,
+            max: max.max(1),
+            dirty: true,
+        }
+    }
+
+    pub fn set_value(&mut self, value: u8) {
+        let value = value.min(self.max);
+
+        if self.value != value {
+            self.value = value;
+            self.dirty = true;
+        }
+    }
+
+    /// Changes the label, e.g. when the language changed.
+    pub fn set_label(&mut self, label: &str) {
+        if self.label != label {
+            self.label = String::from(label);
+            self.dirty = true;
+        }
+    }
+
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+
+    fn filled(&self, of: u32) -> u32 {
+        (self.value as u32 * of + self.max as u32 / 2) / self.max as u32
+    }
+}
+
+impl Widget for StatMeter {
+    type Output = ();
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, _input: Input) -> Option<Self::Output> {
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+
+        let top_left = self.bounds.top_left;
+        Text::with_baseline(&self.label, top_left, theme.text_style(), Baseline::Top)
+            .draw(target)?;
+
+        let meter_top = top_left + Point::new(0, theme.line_height() as i32);
+
+        match self.style {
+            MeterStyle::Bar => {
+                let size = Size::new(
+                    self.bounds.size.width,
+                    self.bounds.size.height.saturating_sub(theme.line_height()),
+                );
+                let filled = self.filled(size.width.saturating_sub(4));
+
+                Rectangle::new(meter_top, size)
+                    .into_styled(PrimitiveStyle::with_stroke(theme.foreground, 1))
+                    .draw(target)?;
+                Rectangle::new(
+                    meter_top + Point::new(2, 2),
+                    Size::new(filled, size.height.saturating_sub(4)),
+                )
+                .into_styled(PrimitiveStyle::with_fill(theme.accent))
+                .draw(target)?;
+            }
+            MeterStyle::Hearts(count) => {
+                let filled = self.filled(count as u32);
+
+                for heart in 0..count as u32 {
+                    let icon = if heart < filled {
+                        icons::HEART
+                    } else {
+                        icons::HEART_EMPTY
+                    };
+                    icon.draw(
+                        target,
+                        meter_top + Point::new((heart * (ICON_SIZE + 2)) as i32, 0),
+                        1,
+                        theme.warning,
+                        theme.background,
+                    )?;
+                }
+            }
+        }
+
+        self.dirty = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! Small retained widget set drawn on top of any `Rgb565` draw target.
+//!
+//! Widgets keep their own state and only redraw their own bounds when something changed, input is
+//! fed in through [`Input`] so they don't need to know about the encoder or button drivers.
+
+pub mod battery;
+pub mod chart;
+pub mod clock_view;
+pub mod diagnostics;
+pub mod dialog;
+pub mod icons;
+pub mod list;
+pub mod menu;
+pub mod meter;
+pub mod spinner;
+pub mod text_entry;
+pub mod theme;
+pub mod time_entry;
+pub mod toast;
+
+pub use theme::Theme;
+
+use crate::button_gesture::ButtonGesture;
+#[cfg(target_os = "espidf")]
+use crate::button_interface::ButtonInterface;
+#[cfg(target_os = "espidf")]
+use crate::rotary_encoder::interface::rotary_interface;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::primitives::Rectangle;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Input {
+    /// Encoder turned by a number of detents, positive counts up.
+    Rotate(i32),
+    Click,
+    DoubleClick,
+    LongPress,
+}
+
+impl From<ButtonGesture> for Input {
+    fn from(gesture: ButtonGesture) -> Self {
+        match gesture {
+            ButtonGesture::Click => Input::Click,
+            ButtonGesture::DoubleClick => Input::DoubleClick,
+            ButtonGesture::LongPress => Input::LongPress,
+        }
+    }
+}
+
+/// Reads the next input from the encoder and button, rotation is reported first.
+#[cfg(target_os = "espidf")]
+pub fn poll_input() -> Option<Input> {
+    match rotary_interface::take_rotation() {
+        Ok(0) => {}
+        Ok(detents) => return Some(Input::Rotate(detents)),
+        Err(err) => log::error!("{}", err),
+    }
+
+    ButtonInterface::take_gesture().map(Input::from)
+}
+
+pub trait Widget {
+    /// What the widget reports back once the user has made a choice.
+    type Output;
+
+    fn bounds(&self) -> Rectangle;
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output>;
+
+    fn is_dirty(&self) -> bool;
+
+    /// Forces a redraw, e.g. after something was drawn over the widget.
+    fn invalidate(&mut self);
+
+    /// Draws the whole widget inside its bounds and marks it clean.
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>;
+
+    fn redraw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        if self.is_dirty() {
+            self.draw(target, theme)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Moves `index` by `delta` inside `0..len`, wrapping at both ends.
+pub(crate) fn wrap_index(index: usize, delta: i32, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+
+    (index as i64 + delta as i64).rem_euclid(len as i64) as usize
+}
@@ -0,0 +1,110 @@
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+
+/// Number picker, every detent moves the value by `step` and a click confirms it.
+pub struct NumberSpinner {
+    bounds: Rectangle,
+    label: String,
+    value: i32,
+    range: (i32, i32),
+    step: i32,
+    wrap: bool,
+    dirty: bool,
+}
+
+impl NumberSpinner {
+    pub fn new(bounds: Rectangle, label: String, value: i32, range: (i32, i32), step: i32) -> Self {
+        Self {
+            bounds,
+            label,
+            value: value.clamp(range.0, range.1),
+            range,
+            step,
+            wrap: false,
+            dirty: true,
+        }
+    }
+
+    /// Wrap around at the ends of the range instead of stopping.
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        let value = value.clamp(self.range.0, self.range.1);
+
+        if self.value != value {
+            self.value = value;
+            self.dirty = true;
+        }
+    }
+
+    fn stepped(&self, detents: i32) -> i32 {
+        let value = self.value + detents * self.step;
+
+        if self.wrap {
+            let span = self.range.1 - self.range.0 + 1;
+            self.range.0 + (value - self.range.0).rem_euclid(span)
+        } else {
+            value.clamp(self.range.0, self.range.1)
+        }
+    }
+}
+
+impl Widget for NumberSpinner {
+    type Output = i32;
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output> {
+        match input {
+            Input::Rotate(detents) => {
+                let value = self.stepped(detents);
+                self.set_value(value);
+                None
+            }
+            Input::Click => Some(self.value),
+            _ => None,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+
+        let top_left = self.bounds.top_left;
+        Text::with_baseline(&self.label, top_left, theme.text_style(), Baseline::Top)
+            .draw(target)?;
+        Text::with_baseline(
+            &format!("< {} >", self.value),
+            top_left + Point::new(0, theme.line_height() as i32),
+            theme.title_style(),
+            Baseline::Top,
+        )
+        .draw(target)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::i18n::{self, Key};
+use crate::ui::{wrap_index, Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::MonoTextStyleBuilder;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+
+/// Characters on the wheel in the order the encoder scrolls through them, all of them in the
+/// Latin-1 fonts the theme uses.
+const CHARACTERS: &str = concat!(
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    "abcdefghijklmnopqrstuvwxyz",
+    "0123456789",
+    " .,-'!?&",
+    "ÀÁÂÄÅÇÈÉÊËÌÍÎÏÑÒÓÔÖØÙÚÛÜÝ",
+    "àáâäåçèéêëìíîïñòóôöøùúûüýÿß",
+);
+
+const _: () = assert!(
+    i18n::covered(CHARACTERS),
+    "Wheel has a glyph the fonts don't"
+);
+
+/// Shown on the wheel's first slot, clicking it takes the suggestion.
+const SUGGESTION_SLOT: &str = "»";
+
+/// Offered as the text is typed, the first one starting with it wins.
+pub const NAMES: &[&str] = &[
+    "Alex", "Bean", "Biscuit", "Chloé", "Coco", "Dumpling", "Émile", "Fig", "Gizmo", "Hazel",
+    "Jaza", "José", "Juniper", "Kiwi", "Léa", "Luna", "Maple", "Mochi", "Noor", "Nori", "Olive",
+    "Pebble", "Pip", "Pixel", "Rumi", "Sam", "Sprout", "Sunny", "Tofu", "Zoë",
+];
+
+const _: () = {
+    let mut idx = 0;
+    while idx < NAMES.len() {
+        assert!(
+            i18n::covered(NAMES[idx]),
+            "Name has a glyph the fonts don't"
+        );
+        idx += 1;
+    }
+};
+
+const WHEEL_TOP: i32 = 30;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TextEntryAction {
+    /// The text with surrounding spaces trimmed, never empty.
+    Confirm(String),
+    /// Long press with nothing left to delete.
+    Cancel,
+}
+
+/// Single line of text typed on a character wheel. Turning the encoder scrolls the wheel, a
+/// click inserts the selected character, a long press deletes the last one and a double click
+/// confirms.
+///
+/// The wheel's first slot completes the text to the first matching name, which is shown greyed
+/// out after the cursor.
+pub struct TextEntry {
+    bounds: Rectangle,
+    text: String,
+    max_len: usize,
+    names: &'static [&'static str],
+    wheel: Vec<char>,
+    /// 0 is the suggestion slot, the characters follow.
+    selected: usize,
+    dirty: bool,
+}
+
+impl TextEntry {
+    pub fn new(
+        bounds: Rectangle,
+        text: String,
+        max_len: usize,
+        names: &'static [&'static str],
+    ) -> Self {
+        Self {
+            bounds,
+            text: text.chars().take(max_len).collect(),
+            max_len,
+            names,
+            wheel: CHARACTERS.chars().collect(),
+            selected: 1,
+            dirty: true,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// First name longer than the text that starts with it, ignoring case.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        if self.text.is_empty() {
+            return None;
+        }
+
+        let typed = self.text.to_lowercase();
+        self.names.iter().copied().find(|name| {
+            name.chars().count() > self.text.chars().count()
+                && name.chars().count() <= self.max_len
+                && name.to_lowercase().starts_with(&typed)
+        })
+    }
+
+    fn label(&self, slot: usize) -> String {
+        match slot {
+            0 => String::from(SUGGESTION_SLOT),
+            slot => String::from(self.wheel[slot - 1]),
+        }
+    }
+
+    fn insert(&mut self) {
+        if self.selected == 0 {
+            if let Some(name) = self.suggestion() {
+                self.text = String::from(name);
+            }
+            return;
+        }
+
+        if self.text.chars().count() >= self.max_len {
+            return;
+        }
+
+        let character = self.wheel[self.selected - 1];
+        self.text.push(character);
+
+        // names carry on in lower case after a capital
+        if character.is_uppercase() {
+            let lower = character.to_lowercase().next();
+            if let Some(idx) = self.wheel.iter().position(|c| Some(*c) == lower) {
+                self.selected = idx + 1;
+            }
+        }
+    }
+}
+
+impl Widget for TextEntry {
+    type Output = TextEntryAction;
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output> {
+        match input {
+            Input::Rotate(detents) => {
+                self.selected = wrap_index(self.selected, detents, self.wheel.len() + 1);
+                self.dirty = true;
+                None
+            }
+            Input::Click => {
+                self.insert();
+                self.dirty = true;
+                None
+            }
+            Input::LongPress if self.text.is_empty() => Some(TextEntryAction::Cancel),
+            Input::LongPress => {
+                self.text.pop();
+                self.dirty = true;
+                None
+            }
+            Input::DoubleClick => match self.text.trim() {
+                "" => None,
+                text => Some(TextEntryAction::Confirm(String::from(text))),
+            },
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+
+        let origin = self.bounds.top_left;
+        let glyph = theme.title_font.character_size;
+
+        // the text so far, the rest of the suggestion greyed out and the cursor under it
+        let cursor = Text::with_baseline(
+            &self.text,
+            origin + Point::new(4, 0),
+            theme.title_style(),
+            Baseline::Top,
+        )
+        .draw(target)?;
+        if let Some(name) = self.suggestion() {
+            let rest: String = name.chars().skip(self.text.chars().count()).collect();
+            let ghost = MonoTextStyleBuilder::new()
+                .font(theme.title_font)
+                .text_color(theme.inactive)
+                .build();
+            Text::with_baseline(&rest, cursor, ghost, Baseline::Top).draw(target)?;
+        }
+        let underline = cursor + Point::new(0, glyph.height as i32 + 1);
+        Line::new(underline, underline + Point::new(glyph.width as i32 - 1, 0))
+            .into_styled(PrimitiveStyle::with_stroke(theme.accent, 2))
+            .draw(target)?;
+
+        // the wheel, centred on the selected slot
+        let cell = Size::new(glyph.width + 12, glyph.height + 4);
+        let side = ((self.bounds.size.width / cell.width).saturating_sub(1) / 2) as i32;
+        let centre = self.bounds.center().x;
+        let centred = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+        for offset in -side..=side {
+            let slot = wrap_index(self.selected, offset, self.wheel.len() + 1);
+            let top_left = Point::new(
+                centre - cell.width as i32 / 2 + offset * cell.width as i32,
+                origin.y + WHEEL_TOP,
+            );
+            let style = if offset == 0 {
+                target.fill_solid(&Rectangle::new(top_left, cell), theme.accent)?;
+                theme.selected_title_style()
+            } else {
+                theme.title_style()
+            };
+
+            Text::with_text_style(
+                &self.label(slot),
+                top_left + Point::new(cell.width as i32 / 2, 2),
+                style,
+                centred,
+            )
+            .draw(target)?;
+        }
+
+        Text::with_baseline(
+            i18n::text(Key::TextEntryHint),
+            origin + Point::new(4, WHEEL_TOP + cell.height as i32 + 8),
+            theme.text_style(),
+            Baseline::Top,
+        )
+        .draw(target)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use embedded_graphics::mono_font::iso_8859_1::{FONT_10X20, FONT_6X10};
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle, MonoTextStyleBuilder};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+
+/// Colours and fonts shared by all widgets.
+#[derive(Copy, Clone)]
+pub struct Theme {
+    pub background: Rgb565,
+    pub foreground: Rgb565,
+    /// Used for selections and filled meters.
+    pub accent: Rgb565,
+    /// Text drawn on top of `accent`.
+    pub on_accent: Rgb565,
+    pub inactive: Rgb565,
+    pub warning: Rgb565,
+
+    pub font: &'static MonoFont<'static>,
+    pub title_font: &'static MonoFont<'static>,
+}
+
+impl Theme {
+    pub fn text_style(&self) -> MonoTextStyle<'static, Rgb565> {
+        MonoTextStyleBuilder::new()
+            .font(self.font)
+            .text_color(self.foreground)
+            .background_color(self.background)
+            .build()
+    }
+
+    pub fn selected_text_style(&self) -> MonoTextStyle<'static, Rgb565> {
+        MonoTextStyleBuilder::new()
+            .font(self.font)
+            .text_color(self.on_accent)
+            .background_color(self.accent)
+            .build()
+    }
+
+    pub fn title_style(&self) -> MonoTextStyle<'static, Rgb565> {
+        MonoTextStyleBuilder::new()
+            .font(self.title_font)
+            .text_color(self.foreground)
+            .background_color(self.background)
+            .build()
+    }
+
+    pub fn selected_title_style(&self) -> MonoTextStyle<'static, Rgb565> {
+        MonoTextStyleBuilder::new()
+            .font(self.title_font)
+            .text_color(self.on_accent)
+            .background_color(self.accent)
+            .build()
+    }
+
+    /// Height of one line of body text including spacing.
+    pub fn line_height(&self) -> u32 {
+        self.font.character_size.height + 2
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background: Rgb565::BLACK,
+            foreground: Rgb565::WHITE,
+            accent: Rgb565::new(31, 40, 8),
+            on_accent: Rgb565::BLACK,
+            inactive: Rgb565::new(10, 20, 10),
+            warning: Rgb565::RED,
+
+            font: &FONT_6X10,
+            title_font: &FONT_10X20,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::clock::{ClockFormat, TimeOfDay};
+use crate::i18n::{self, Key};
+use crate::ui::{wrap_index, Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TimeEntryAction {
+    Confirm(TimeOfDay),
+    /// Long press on the hours.
+    Cancel,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Field {
+    Hour,
+    Minute,
+}
+
+/// Time of day set a field at a time. Turning the encoder changes the highlighted field, a click
+/// moves on from the hours and confirms on the minutes, a double click confirms straight away and
+/// a long press goes back.
+pub struct TimeEntry {
+    bounds: Rectangle,
+    hour: u8,
+    minute: u8,
+    format: ClockFormat,
+    field: Field,
+    dirty: bool,
+}
+
+impl TimeEntry {
+    pub fn new(bounds: Rectangle, time: TimeOfDay, format: ClockFormat) -> Self {
+        Self {
+            bounds,
+            hour: time.hour(),
+            minute: time.minute(),
+            format,
+            field: Field::Hour,
+            dirty: true,
+        }
+    }
+
+    pub fn time(&self) -> TimeOfDay {
+        TimeOfDay::new(self.hour, self.minute)
+    }
+
+    /// The hour as shown, with am or pm after it on a 12 hour clock.
+    fn hour_text(&self) -> (String, &'static str) {
+        match self.format {
+            ClockFormat::TwentyFourHour => (format!("{:02}", self.hour), ""),
+            ClockFormat::TwelveHour => {
+                let suffix = if self.hour < 12 { " am" } else { " pm" };
+                let hour = match self.hour % 12 {
+                    0 => 12,
+                    hour => hour,
+                };
+                (format!("{:2}", hour), suffix)
+            }
+        }
+    }
+}
+
+impl Widget for TimeEntry {
+    type Output = TimeEntryAction;
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, input: Input) -> Option<Self::Output> {
+        match (self.field, input) {
+            (Field::Hour, Input::Rotate(detents)) => {
+                self.hour = wrap_index(self.hour as usize, detents, 24) as u8;
+            }
+            (Field::Minute, Input::Rotate(detents)) => {
+                self.minute = wrap_index(self.minute as usize, detents, 60) as u8;
+            }
+            (Field::Hour, Input::Click) => self.field = Field::Minute,
+            (Field::Minute, Input::Click) | (_, Input::DoubleClick) => {
+                return Some(TimeEntryAction::Confirm(self.time()))
+            }
+            (Field::Hour, Input::LongPress) => return Some(TimeEntryAction::Cancel),
+            (Field::Minute, Input::LongPress) => self.field = Field::Hour,
+        }
+
+        self.dirty = true;
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&self.bounds, theme.background)?;
+
+        let (hour, suffix) = self.hour_text();
+        let minute = format!("{:02}", self.minute);
+        let style = |field: Field| match self.field == field {
+            true => theme.selected_title_style(),
+            false => theme.title_style(),
+        };
+
+        // hours, minutes and am or pm centred on one line, the field being set highlighted
+        let glyph = theme.title_font.character_size;
+        let chars = hour.len() + 1 + minute.len() + suffix.len();
+        let width = (chars as u32 * glyph.width) as i32;
+        let origin = self.bounds.top_left;
+        let mut position = Point::new(self.bounds.center().x - width / 2, origin.y + 20);
+        position =
+            Text::with_baseline(&hour, position, style(Field::Hour), Baseline::Top).draw(target)?;
+        position =
+            Text::with_baseline(":", position, theme.title_style(), Baseline::Top).draw(target)?;
+        position = Text::with_baseline(&minute, position, style(Field::Minute), Baseline::Top)
+            .draw(target)?;
+        Text::with_baseline(suffix, position, style(Field::Hour), Baseline::Top).draw(target)?;
+
+        Text::with_baseline(
+            i18n::text(Key::TimeEntryHint),
+            origin + Point::new(4, 20 + glyph.height as i32 + 16),
+            theme.text_style(),
+            Baseline::Top,
+        )
+        .draw(target)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
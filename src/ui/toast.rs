@@ -0,0 +1,112 @@
+use crate::ui::{Input, Theme, Widget};
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub const TOAST_TIME: Duration = Duration::from_secs(2);
+
+/// Queue of short notifications shown one after another in a fixed strip.
+///
+/// Toasts don't take input, once the last one has gone the strip is cleared to the background
+/// and whatever was underneath needs invalidating.
+pub struct Toasts {
+    bounds: Rectangle,
+    queue: VecDeque<(String, Duration)>,
+    current: Option<(String, Duration)>,
+    dirty: bool,
+}
+
+impl Toasts {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            queue: VecDeque::new(),
+            current: None,
+            dirty: false,
+        }
+    }
+
+    pub fn push(&mut self, message: String) {
+        self.push_for(message, TOAST_TIME);
+    }
+
+    pub fn push_for(&mut self, message: String, duration: Duration) {
+        self.queue.push_back((message, duration));
+
+        if self.current.is_none() {
+            self.next();
+        }
+    }
+
+    pub fn is_showing(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Advances the current toast by `elapsed`, moving on to the next one when it runs out.
+    pub fn tick(&mut self, elapsed: Duration) {
+        if let Some((_, remaining)) = &mut self.current {
+            *remaining = remaining.saturating_sub(elapsed);
+
+            if remaining.is_zero() {
+                self.next();
+            }
+        }
+    }
+
+    fn next(&mut self) {
+        self.current = self.queue.pop_front();
+        self.dirty = true;
+    }
+}
+
+impl Widget for Toasts {
+    type Output = ();
+
+    fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    fn handle_input(&mut self, _input: Input) -> Option<Self::Output> {
+        None
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn invalidate(&mut self) {
+        self.dirty = self.current.is_some();
+    }
+
+    fn draw<D>(&mut self, target: &mut D, theme: &Theme) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        match &self.current {
+            Some((message, _)) => {
+                self.bounds
+                    .into_styled(PrimitiveStyle::with_fill(theme.accent))
+                    .draw(target)?;
+
+                Text::with_text_style(
+                    message,
+                    self.bounds.center(),
+                    theme.selected_text_style(),
+                    TextStyleBuilder::new()
+                        .alignment(Alignment::Center)
+                        .baseline(Baseline::Middle)
+                        .build(),
+                )
+                .draw(target)?;
+            }
+            None => target.fill_solid(&self.bounds, theme.background)?,
+        }
+
+        self.dirty = false;
+        Ok(())
+    }
+}